@@ -0,0 +1,395 @@
+//! Derive macros for the [Qdrant](https://qdrant.tech/) Rust client
+//!
+//! Use these through the `derive` feature of [`qdrant-client`](https://docs.rs/qdrant-client),
+//! which re-exports them next to the traits they implement.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Expr, Fields, Ident, LitInt, LitStr, Type};
+
+/// Derive conversions between a struct and Qdrant points
+///
+/// Generates `TryFrom<T> for PointStruct`, `TryFrom<RetrievedPoint>` and `TryFrom<ScoredPoint>`
+/// for `T`, and implements the `QdrantPoint` trait describing the vectors of the collection.
+/// Converting into a point fails if a payload field cannot be serialized, such as a map with
+/// non-string keys or an integer beyond `i64`.
+///
+/// Field attributes:
+///
+/// - `#[qdrant(id)]` - the point ID, exactly one field must have it
+/// - `#[qdrant(vector)]` - the default (unnamed) dense vector
+/// - `#[qdrant(vector = "name")]` - a named dense vector, with optional `size = 384` and
+///   `distance = "Cosine"`; the size is required unless the field is an array
+/// - `#[qdrant(sparse_vector = "name")]` - a named sparse vector
+/// - `#[qdrant(skip)]` - not stored, set to its default value when converting back
+/// - `#[qdrant(rename = "name")]` - store the payload field under another key
+///
+/// All other fields are stored in the payload.
+#[proc_macro_derive(QdrantPoint, attributes(qdrant))]
+pub fn derive_qdrant_point(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Role of a struct field in the point
+enum FieldKind {
+    Id,
+    Vector {
+        name: String,
+        size: Expr,
+        distance: Ident,
+    },
+    SparseVector {
+        name: String,
+    },
+    Payload {
+        key: String,
+    },
+    Skip,
+}
+
+struct PointField {
+    ident: Ident,
+    kind: FieldKind,
+}
+
+const DISTANCES: &[&str] = &["Cosine", "Euclid", "Dot", "Manhattan"];
+
+fn parse_field(field: &syn::Field) -> syn::Result<PointField> {
+    let ident = field
+        .ident
+        .clone()
+        .ok_or_else(|| syn::Error::new(field.span(), "expected a named field"))?;
+
+    let mut id = false;
+    let mut skip = false;
+    let mut vector: Option<String> = None;
+    let mut sparse_vector: Option<String> = None;
+    let mut rename: Option<String> = None;
+    let mut size: Option<Expr> = None;
+    let mut distance: Option<LitStr> = None;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("qdrant"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                id = true;
+            } else if meta.path.is_ident("skip") {
+                skip = true;
+            } else if meta.path.is_ident("vector") {
+                vector = Some(match meta.input.peek(syn::Token![=]) {
+                    true => meta.value()?.parse::<LitStr>()?.value(),
+                    false => String::new(),
+                });
+            } else if meta.path.is_ident("sparse_vector") {
+                sparse_vector = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("size") {
+                size = Some(Expr::Lit(syn::ExprLit {
+                    attrs: Vec::new(),
+                    lit: syn::Lit::Int(meta.value()?.parse::<LitInt>()?),
+                }));
+            } else if meta.path.is_ident("distance") {
+                let value = meta.value()?.parse::<LitStr>()?;
+                if !DISTANCES.contains(&value.value().as_str()) {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        format!("unknown distance, expected one of {}", DISTANCES.join(", ")),
+                    ));
+                }
+                distance = Some(value);
+            } else {
+                return Err(meta.error("unknown qdrant attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let roles = [id, skip, vector.is_some(), sparse_vector.is_some()];
+    if roles.iter().filter(|role| **role).count() > 1 {
+        return Err(syn::Error::new(
+            ident.span(),
+            "only one of `id`, `skip`, `vector` and `sparse_vector` can be used on a field",
+        ));
+    }
+    if rename.is_some() && roles.iter().any(|role| *role) {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`rename` can only be used on payload fields",
+        ));
+    }
+    if (size.is_some() || distance.is_some()) && vector.is_none() {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`size` and `distance` can only be used on `vector` fields",
+        ));
+    }
+
+    let kind = if id {
+        FieldKind::Id
+    } else if skip {
+        FieldKind::Skip
+    } else if let Some(name) = vector {
+        let size = size.or_else(|| array_len(&field.ty)).ok_or_else(|| {
+            syn::Error::new(
+                field.ty.span(),
+                "vector size is unknown, add `size = ...` to the qdrant attribute",
+            )
+        })?;
+        let distance = distance.map_or_else(
+            || Ident::new("Cosine", Span::call_site()),
+            |distance| Ident::new(&distance.value(), distance.span()),
+        );
+        FieldKind::Vector {
+            name,
+            size,
+            distance,
+        }
+    } else if let Some(name) = sparse_vector {
+        FieldKind::SparseVector { name }
+    } else {
+        FieldKind::Payload {
+            key: rename.unwrap_or_else(|| ident.to_string()),
+        }
+    };
+
+    Ok(PointField { ident, kind })
+}
+
+/// Length of an array type such as `[f32; 384]`
+fn array_len(ty: &Type) -> Option<Expr> {
+    match ty {
+        Type::Array(array) => Some(array.len.clone()),
+        Type::Group(group) => array_len(&group.elem),
+        Type::Paren(paren) => array_len(&paren.elem),
+        _ => None,
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "QdrantPoint can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new(
+            input.span(),
+            "QdrantPoint can only be derived for structs with named fields",
+        ));
+    };
+
+    let fields = named
+        .named
+        .iter()
+        .map(parse_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut ids = fields
+        .iter()
+        .filter(|field| matches!(field.kind, FieldKind::Id));
+    let id = ids.next().ok_or_else(|| {
+        syn::Error::new(
+            input.ident.span(),
+            "missing point ID, mark a field with `#[qdrant(id)]`",
+        )
+    })?;
+    if let Some(duplicate) = ids.next() {
+        return Err(syn::Error::new(
+            duplicate.ident.span(),
+            "duplicate point ID, only one field can have `#[qdrant(id)]`",
+        ));
+    }
+
+    let mut names: Vec<&str> = Vec::new();
+    for field in &fields {
+        let name = match &field.kind {
+            FieldKind::Vector { name, .. } | FieldKind::SparseVector { name } => name,
+            _ => continue,
+        };
+        if names.contains(&name.as_str()) {
+            return Err(syn::Error::new(
+                field.ident.span(),
+                format!("duplicate vector name {name:?}"),
+            ));
+        }
+        names.push(name);
+    }
+
+    let dense_count = fields
+        .iter()
+        .filter(|field| matches!(field.kind, FieldKind::Vector { .. }))
+        .count();
+    let default_vector = fields
+        .iter()
+        .find(|field| matches!(&field.kind, FieldKind::Vector { name, .. } if name.is_empty()));
+    if let (Some(field), 2..) = (default_vector, dense_count) {
+        return Err(syn::Error::new(
+            field.ident.span(),
+            "the default vector cannot be combined with named dense vectors",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let krate = quote!(::qdrant_client);
+    let id_ident = &id.ident;
+
+    // Struct to point
+    let mut payload_inserts = Vec::new();
+    let mut vector_inserts = Vec::new();
+    // Point to struct
+    let mut field_values = Vec::new();
+    // Collection configuration
+    let mut dense_params = Vec::new();
+    let mut sparse_params = Vec::new();
+
+    for field in &fields {
+        let field_ident = &field.ident;
+        match &field.kind {
+            FieldKind::Id => {
+                field_values.push(quote! {
+                    #field_ident: #krate::derive::IdField::from_point_id(
+                        id.ok_or_else(|| #krate::QdrantError::ConversionError(
+                            "point has no ID".to_string(),
+                        ))?,
+                    )?
+                });
+            }
+            FieldKind::Vector {
+                name,
+                size,
+                distance,
+            } => {
+                vector_inserts.push(quote! {
+                    (#name, #krate::derive::VectorField::into_vector(value.#field_ident))
+                });
+                field_values.push(quote! {
+                    #field_ident: #krate::derive::__private::vector(vectors.as_ref(), #name)?
+                });
+                dense_params.push(quote! {
+                    config.add_named_vector_params(
+                        #name,
+                        #krate::qdrant::VectorParamsBuilder::new(
+                            (#size) as u64,
+                            #krate::qdrant::Distance::#distance,
+                        ),
+                    );
+                });
+            }
+            FieldKind::SparseVector { name } => {
+                vector_inserts.push(quote! {
+                    (#name, #krate::derive::VectorField::into_vector(value.#field_ident))
+                });
+                field_values.push(quote! {
+                    #field_ident: #krate::derive::__private::vector(vectors.as_ref(), #name)?
+                });
+                sparse_params.push(quote! {
+                    config.add_named_vector_params(
+                        #name,
+                        #krate::qdrant::SparseVectorParams::default(),
+                    );
+                });
+            }
+            FieldKind::Payload { key } => {
+                payload_inserts.push(quote! {
+                    payload.insert(
+                        #key,
+                        #krate::serde_ser::to_value(&value.#field_ident)?,
+                    );
+                });
+                field_values.push(quote! {
+                    #field_ident: #krate::derive::__private::payload_field(&mut payload, #key)?
+                });
+            }
+            FieldKind::Skip => {
+                field_values.push(quote! {
+                    #field_ident: ::core::default::Default::default()
+                });
+            }
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::TryFrom<#ident #ty_generics>
+            for #krate::qdrant::PointStruct #where_clause
+        {
+            type Error = #krate::serde_ser::SerPayloadError;
+
+            fn try_from(value: #ident #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                let mut payload = #krate::Payload::new();
+                #(#payload_inserts)*
+                let vectors = #krate::derive::__private::vectors([#(#vector_inserts),*]);
+                ::core::result::Result::Ok(#krate::qdrant::PointStruct::new(
+                    #krate::derive::IdField::into_point_id(value.#id_ident),
+                    vectors,
+                    payload,
+                ))
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<#krate::qdrant::RetrievedPoint>
+            for #ident #ty_generics #where_clause
+        {
+            type Error = #krate::QdrantError;
+
+            fn try_from(point: #krate::qdrant::RetrievedPoint) -> ::core::result::Result<Self, Self::Error> {
+                <Self as #krate::derive::QdrantPoint>::from_point_parts(
+                    point.id,
+                    point.payload,
+                    point.vectors,
+                )
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<#krate::qdrant::ScoredPoint>
+            for #ident #ty_generics #where_clause
+        {
+            type Error = #krate::QdrantError;
+
+            fn try_from(point: #krate::qdrant::ScoredPoint) -> ::core::result::Result<Self, Self::Error> {
+                <Self as #krate::derive::QdrantPoint>::from_point_parts(
+                    point.id,
+                    point.payload,
+                    point.vectors,
+                )
+            }
+        }
+
+        impl #impl_generics #krate::derive::QdrantPoint for #ident #ty_generics #where_clause {
+            fn vectors_config() -> #krate::qdrant::VectorsConfig {
+                #[allow(unused_mut)]
+                let mut config = #krate::qdrant::VectorsConfigBuilder::default();
+                #(#dense_params)*
+                config.into()
+            }
+
+            fn sparse_vectors_config() -> #krate::qdrant::SparseVectorConfig {
+                #[allow(unused_mut)]
+                let mut config = #krate::qdrant::SparseVectorsConfigBuilder::default();
+                #(#sparse_params)*
+                config.into()
+            }
+
+            #[allow(unused_mut, unused_variables)]
+            fn from_point_parts(
+                id: ::core::option::Option<#krate::qdrant::PointId>,
+                mut payload: ::std::collections::HashMap<::std::string::String, #krate::qdrant::Value>,
+                vectors: ::core::option::Option<#krate::qdrant::VectorsOutput>,
+            ) -> ::core::result::Result<Self, #krate::QdrantError> {
+                ::core::result::Result::Ok(Self {
+                    #(#field_values),*
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use qdrant_client::derive::QdrantPoint;
+use qdrant_client::qdrant::vectors_config::Config;
+use qdrant_client::qdrant::{
+    Distance, NamedVectorsOutput, PointStruct, RetrievedPoint, ScoredPoint, Vector, VectorOutput,
+    VectorsOutput,
+};
+use qdrant_client::serde_ser::SerPayloadError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct Author {
+    name: String,
+}
+
+#[derive(QdrantPoint, Clone, Debug, PartialEq)]
+struct Article {
+    #[qdrant(id)]
+    id: u64,
+    #[qdrant(vector = "dense", size = 2, distance = "Dot")]
+    embedding: Vec<f32>,
+    #[qdrant(sparse_vector = "bm25")]
+    keywords: Vec<(u32, f32)>,
+    #[qdrant(rename = "headline")]
+    title: String,
+    author: Author,
+    rating: Option<f64>,
+    #[qdrant(skip)]
+    cached: Option<String>,
+}
+
+#[derive(QdrantPoint, Clone, Debug, PartialEq)]
+struct Image {
+    #[qdrant(id)]
+    id: String,
+    #[qdrant(vector)]
+    embedding: [f32; 3],
+}
+
+fn article() -> Article {
+    Article {
+        id: 7,
+        embedding: vec![0.5, 0.25],
+        keywords: vec![(3, 0.5), (9, 1.0)],
+        title: "Vector search".to_string(),
+        author: Author {
+            name: "Ada".to_string(),
+        },
+        rating: None,
+        cached: Some("ignored".to_string()),
+    }
+}
+
+/// Turn an upserted point into what the server returns
+fn vectors_output(point: &PointStruct) -> VectorsOutput {
+    use qdrant_client::qdrant::vector::Vector as VectorInput;
+    use qdrant_client::qdrant::vector_output::Vector as VectorOutputKind;
+    use qdrant_client::qdrant::vectors::VectorsOptions;
+    use qdrant_client::qdrant::vectors_output::VectorsOptions as OutputOptions;
+
+    let output = |vector: &Vector| VectorOutput {
+        vector: Some(match vector.vector.clone().unwrap() {
+            VectorInput::Dense(dense) => VectorOutputKind::Dense(dense),
+            VectorInput::Sparse(sparse) => VectorOutputKind::Sparse(sparse),
+            VectorInput::MultiDense(multi) => VectorOutputKind::MultiDense(multi),
+            other => panic!("unexpected vector kind in test fixture: {other:?}"),
+        }),
+        ..Default::default()
+    };
+
+    let options = match point
+        .vectors
+        .as_ref()
+        .unwrap()
+        .vectors_options
+        .as_ref()
+        .unwrap()
+    {
+        VectorsOptions::Vector(vector) => OutputOptions::Vector(output(vector)),
+        VectorsOptions::Vectors(named) => OutputOptions::Vectors(NamedVectorsOutput {
+            vectors: named
+                .vectors
+                .iter()
+                .map(|(name, vector)| (name.clone(), output(vector)))
+                .collect(),
+        }),
+    };
+    VectorsOutput {
+        vectors_options: Some(options),
+    }
+}
+
+#[test]
+fn struct_into_point() {
+    let point = PointStruct::try_from(article()).unwrap();
+
+    assert_eq!(point.id, Some(7.into()));
+    assert_eq!(point.payload["headline"], "Vector search".into());
+    assert!(point.payload["rating"].is_null());
+    assert!(!point.payload.contains_key("title"));
+    assert!(!point.payload.contains_key("cached"));
+    assert!(!point.payload.contains_key("embedding"));
+}
+
+#[test]
+fn point_into_struct() {
+    let point = PointStruct::try_from(article()).unwrap();
+
+    let retrieved = RetrievedPoint {
+        id: point.id.clone(),
+        payload: point.payload.clone(),
+        vectors: Some(vectors_output(&point)),
+        ..Default::default()
+    };
+    let expected = Article {
+        cached: None,
+        ..article()
+    };
+    assert_eq!(Article::try_from(retrieved).unwrap(), expected);
+
+    let scored = ScoredPoint {
+        id: point.id.clone(),
+        payload: point.payload.clone(),
+        vectors: Some(vectors_output(&point)),
+        score: 0.9,
+        ..Default::default()
+    };
+    assert_eq!(Article::try_from(scored).unwrap(), expected);
+
+    // Vectors are required
+    let without_vectors = ScoredPoint {
+        id: point.id.clone(),
+        payload: point.payload.clone(),
+        ..Default::default()
+    };
+    let err = Article::try_from(without_vectors).unwrap_err();
+    assert!(err.to_string().contains("dense"), "{err}");
+
+    // Missing optional payload fields are allowed, missing required fields are not
+    let mut payload = point.payload.clone();
+    payload.remove("rating");
+    let retrieved = RetrievedPoint {
+        id: point.id.clone(),
+        payload: payload.clone(),
+        vectors: Some(vectors_output(&point)),
+        ..Default::default()
+    };
+    assert!(Article::try_from(retrieved).is_ok());
+    payload.remove("headline");
+    let retrieved = RetrievedPoint {
+        id: point.id.clone(),
+        payload,
+        vectors: Some(vectors_output(&point)),
+        ..Default::default()
+    };
+    let err = Article::try_from(retrieved).unwrap_err();
+    assert!(err.to_string().contains("headline"), "{err}");
+}
+
+#[test]
+fn default_vector_round_trip() {
+    let image = Image {
+        id: "8a5c0b31-2c4e-4d55-9a6d-3f3e5c7b9d10".to_string(),
+        embedding: [1.0, 0.0, 0.5],
+    };
+    let point = PointStruct::try_from(image.clone()).unwrap();
+    assert!(point.payload.is_empty());
+
+    let retrieved = RetrievedPoint {
+        id: point.id.clone(),
+        vectors: Some(vectors_output(&point)),
+        ..Default::default()
+    };
+    assert_eq!(Image::try_from(retrieved).unwrap(), image);
+}
+
+#[test]
+fn collection_config() {
+    let request = Article::create_collection("articles").build();
+    let Some(Config::ParamsMap(map)) = request.vectors_config.unwrap().config else {
+        panic!("expected named vectors");
+    };
+    let dense = &map.map["dense"];
+    assert_eq!(dense.size, 2);
+    assert_eq!(dense.distance, Distance::Dot as i32);
+    assert!(request
+        .sparse_vectors_config
+        .unwrap()
+        .map
+        .contains_key("bm25"));
+
+    let request = Image::create_collection("images").build();
+    let Some(Config::Params(params)) = request.vectors_config.unwrap().config else {
+        panic!("expected default vector");
+    };
+    assert_eq!(params.size, 3);
+    assert_eq!(params.distance, Distance::Cosine as i32);
+    assert!(request.sparse_vectors_config.is_none());
+
+    assert_eq!(Image::sparse_vectors_config().map, HashMap::new());
+}
+
+#[test]
+fn large_integers_are_an_error() {
+    #[derive(QdrantPoint, Clone, Debug, PartialEq)]
+    struct Counter {
+        #[qdrant(id)]
+        id: u64,
+        #[qdrant(vector, size = 1)]
+        embedding: Vec<f32>,
+        hits: u64,
+    }
+
+    let counter = |hits| Counter {
+        id: 1,
+        embedding: vec![1.0],
+        hits,
+    };
+    let point = PointStruct::try_from(counter(3)).unwrap();
+    assert_eq!(point.payload["hits"], 3.into());
+    assert_eq!(
+        PointStruct::try_from(counter(u64::MAX)),
+        Err(SerPayloadError::IntegerConversion {
+            got: i128::from(u64::MAX),
+            max: i64::MAX,
+        }),
+    );
+}
+
+#[test]
+fn unserializable_payload_is_an_error() {
+    #[derive(QdrantPoint, Clone, Debug, PartialEq)]
+    struct Grid {
+        #[qdrant(id)]
+        id: u64,
+        #[qdrant(vector, size = 1)]
+        embedding: Vec<f32>,
+        cells: HashMap<(u8, u8), String>,
+    }
+
+    let grid = Grid {
+        id: 1,
+        embedding: vec![1.0],
+        cells: HashMap::from([((0, 0), "start".to_string())]),
+    };
+    assert_eq!(
+        PointStruct::try_from(grid),
+        Err(SerPayloadError::KeyMustBeString),
+    );
+}
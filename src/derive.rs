@@ -0,0 +1,268 @@
+//! Typed points
+//!
+//! <small><em>Requires `derive` feature</em></small>
+//!
+//! Derive [`QdrantPoint`](macro@QdrantPoint) to convert a struct into a [`PointStruct`], and
+//! retrieved or scored points back into the struct. Converting into a point fails if a payload
+//! field cannot be serialized:
+//!
+//! ```rust
+//! use qdrant_client::derive::QdrantPoint;
+//! use qdrant_client::qdrant::PointStruct;
+//!
+//! #[derive(QdrantPoint, Clone, Debug, PartialEq)]
+//! struct Article {
+//!     #[qdrant(id)]
+//!     id: u64,
+//!     #[qdrant(vector = "dense", size = 3, distance = "Dot")]
+//!     embedding: Vec<f32>,
+//!     #[qdrant(sparse_vector = "bm25")]
+//!     keywords: Vec<(u32, f32)>,
+//!     #[qdrant(rename = "headline")]
+//!     title: String,
+//!     tags: Vec<String>,
+//!     #[qdrant(skip)]
+//!     cached: Option<String>,
+//! }
+//!
+//! let article = Article {
+//!     id: 1,
+//!     embedding: vec![0.1, 0.2, 0.3],
+//!     keywords: vec![(7, 0.5)],
+//!     title: "Qdrant".to_string(),
+//!     tags: vec!["search".to_string()],
+//!     cached: None,
+//! };
+//!
+//! let point = PointStruct::try_from(article.clone()).unwrap();
+//! assert_eq!(point.payload["headline"], "Qdrant".into());
+//!
+//! // Create the collection for these points
+//! let request = Article::create_collection("articles");
+//! ```
+//!
+//! See the [derive macro](macro@QdrantPoint) for all attributes.
+
+use std::collections::HashMap;
+
+pub use qdrant_client_derive::QdrantPoint;
+use serde::de::{DeserializeOwned, IntoDeserializer};
+
+use crate::qdrant::value::Kind;
+use crate::qdrant::vector_output::Vector as VectorOutput;
+use crate::qdrant::{
+    CreateCollectionBuilder, PointId, SparseVectorConfig, Value, Vector, VectorsConfig,
+    VectorsOutput,
+};
+use crate::QdrantError;
+
+/// Struct stored as Qdrant points
+///
+/// Implement this through the [derive macro](macro@QdrantPoint), which also generates the
+/// conversions to and from points.
+pub trait QdrantPoint: Sized {
+    /// Dense vector configuration for the collection
+    fn vectors_config() -> VectorsConfig;
+
+    /// Sparse vector configuration for the collection
+    fn sparse_vectors_config() -> SparseVectorConfig;
+
+    /// Request to create a collection storing these points
+    ///
+    /// Further collection parameters can be set on the returned builder.
+    fn create_collection(collection_name: impl Into<String>) -> CreateCollectionBuilder {
+        let mut builder = CreateCollectionBuilder::new(collection_name);
+        let vectors_config = Self::vectors_config();
+        if vectors_config.config.is_some() {
+            builder = builder.vectors_config(vectors_config);
+        }
+        let sparse_vectors_config = Self::sparse_vectors_config();
+        if !sparse_vectors_config.map.is_empty() {
+            builder = builder.sparse_vectors_config(sparse_vectors_config);
+        }
+        builder
+    }
+
+    /// Construct from the parts of a retrieved or scored point
+    #[doc(hidden)]
+    fn from_point_parts(
+        id: Option<PointId>,
+        payload: HashMap<String, Value>,
+        vectors: Option<VectorsOutput>,
+    ) -> Result<Self, QdrantError>;
+}
+
+/// Types usable as point ID field
+pub trait IdField: Sized {
+    fn into_point_id(self) -> PointId;
+
+    fn from_point_id(id: PointId) -> Result<Self, QdrantError>;
+}
+
+impl IdField for PointId {
+    fn into_point_id(self) -> PointId {
+        self
+    }
+
+    fn from_point_id(id: PointId) -> Result<Self, QdrantError> {
+        Ok(id)
+    }
+}
+
+impl IdField for u64 {
+    fn into_point_id(self) -> PointId {
+        self.into()
+    }
+
+    fn from_point_id(id: PointId) -> Result<Self, QdrantError> {
+        use crate::qdrant::point_id::PointIdOptions;
+
+        match id.point_id_options {
+            Some(PointIdOptions::Num(num)) => Ok(num),
+            _ => Err(QdrantError::ConversionError(format!(
+                "expected numeric point ID, got {id:?}"
+            ))),
+        }
+    }
+}
+
+impl IdField for String {
+    fn into_point_id(self) -> PointId {
+        self.into()
+    }
+
+    fn from_point_id(id: PointId) -> Result<Self, QdrantError> {
+        use crate::qdrant::point_id::PointIdOptions;
+
+        match id.point_id_options {
+            Some(PointIdOptions::Uuid(uuid)) => Ok(uuid),
+            _ => Err(QdrantError::ConversionError(format!(
+                "expected UUID point ID, got {id:?}"
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl IdField for uuid::Uuid {
+    fn into_point_id(self) -> PointId {
+        self.into()
+    }
+
+    fn from_point_id(id: PointId) -> Result<Self, QdrantError> {
+        let uuid = String::from_point_id(id)?;
+        uuid::Uuid::parse_str(&uuid)
+            .map_err(|err| QdrantError::ConversionError(format!("invalid UUID {uuid:?}: {err}")))
+    }
+}
+
+/// Types usable as dense or sparse vector field
+pub trait VectorField: Sized {
+    fn into_vector(self) -> Vector;
+
+    fn from_vector(vector: VectorOutput) -> Result<Self, QdrantError>;
+}
+
+impl VectorField for Vec<f32> {
+    fn into_vector(self) -> Vector {
+        Vector::new_dense(self)
+    }
+
+    fn from_vector(vector: VectorOutput) -> Result<Self, QdrantError> {
+        match vector {
+            VectorOutput::Dense(dense) => Ok(dense.data),
+            _ => Err(QdrantError::ConversionError(
+                "expected dense vector".to_string(),
+            )),
+        }
+    }
+}
+
+impl<const N: usize> VectorField for [f32; N] {
+    fn into_vector(self) -> Vector {
+        Vector::new_dense(self.to_vec())
+    }
+
+    fn from_vector(vector: VectorOutput) -> Result<Self, QdrantError> {
+        let data = Vec::<f32>::from_vector(vector)?;
+        let len = data.len();
+        data.try_into().map_err(|_| {
+            QdrantError::ConversionError(format!("expected vector of size {N}, got {len}"))
+        })
+    }
+}
+
+impl VectorField for crate::qdrant::SparseVector {
+    fn into_vector(self) -> Vector {
+        Vector::new_sparse(self.indices, self.values)
+    }
+
+    fn from_vector(vector: VectorOutput) -> Result<Self, QdrantError> {
+        match vector {
+            VectorOutput::Sparse(sparse) => Ok(sparse),
+            _ => Err(QdrantError::ConversionError(
+                "expected sparse vector".to_string(),
+            )),
+        }
+    }
+}
+
+impl VectorField for Vec<(u32, f32)> {
+    fn into_vector(self) -> Vector {
+        self.into()
+    }
+
+    fn from_vector(vector: VectorOutput) -> Result<Self, QdrantError> {
+        let sparse = crate::qdrant::SparseVector::from_vector(vector)?;
+        Ok(sparse.indices.into_iter().zip(sparse.values).collect())
+    }
+}
+
+/// Support for the derive macro, not public API
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+    use crate::qdrant::Vectors;
+
+    /// Take a field from the payload, a missing field is deserialized from null
+    pub fn payload_field<T: DeserializeOwned>(
+        payload: &mut HashMap<String, Value>,
+        key: &str,
+    ) -> Result<T, QdrantError> {
+        let value = payload.remove(key).unwrap_or(Value {
+            kind: Some(Kind::NullValue(0)),
+        });
+        T::deserialize(value.into_deserializer()).map_err(|err| {
+            QdrantError::ConversionError(format!("invalid payload field {key:?}: {err}"))
+        })
+    }
+
+    /// Take a vector by name, an empty name is the default vector
+    pub fn vector<T: VectorField>(
+        vectors: Option<&VectorsOutput>,
+        name: &str,
+    ) -> Result<T, QdrantError> {
+        let vector = vectors
+            .and_then(|vectors| vectors.get_vector_by_name(name))
+            .ok_or_else(|| {
+                QdrantError::ConversionError(format!(
+                    "missing vector {name:?}, retrieve points with vectors"
+                ))
+            })?;
+        T::from_vector(vector)
+            .map_err(|err| QdrantError::ConversionError(format!("vector {name:?}: {err}")))
+    }
+
+    /// Vectors of a point, a lone default vector is not named
+    pub fn vectors<const N: usize>(vectors: [(&str, Vector); N]) -> Vectors {
+        let mut vectors = Vec::from(vectors);
+        if let [("", _)] = vectors.as_slice() {
+            return vectors.remove(0).1.into();
+        }
+        vectors
+            .into_iter()
+            .map(|(name, vector)| (name.to_string(), vector))
+            .collect::<HashMap<_, _>>()
+            .into()
+    }
+}
@@ -0,0 +1,259 @@
+use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+#[cfg(feature = "time")]
+use time::format_description::well_known::Rfc3339;
+#[cfg(feature = "time")]
+use time::OffsetDateTime;
+
+use crate::qdrant::value::Kind;
+use crate::qdrant::{DatetimeRange, Timestamp, Value};
+use crate::QdrantError;
+
+/// Conversions between [`Timestamp`] and date time types of the `chrono` and `time` crates
+///
+/// `Timestamp` is defined in `prost-types`, so these can't be `From` implementations.
+///
+/// <small><em>Requires `chrono` or `time` feature</em></small>
+pub trait TimestampExt: Sized {
+    /// Timestamp of a `chrono` date time
+    #[cfg(feature = "chrono")]
+    fn from_chrono<Tz: TimeZone>(datetime: &DateTime<Tz>) -> Self;
+
+    /// This timestamp as a `chrono` date time in UTC
+    #[cfg(feature = "chrono")]
+    fn to_chrono(&self) -> Result<DateTime<Utc>, QdrantError>;
+
+    /// Timestamp of a `time` date time
+    #[cfg(feature = "time")]
+    fn from_time(datetime: &OffsetDateTime) -> Self;
+
+    /// This timestamp as a `time` date time in UTC
+    #[cfg(feature = "time")]
+    fn to_time(&self) -> Result<OffsetDateTime, QdrantError>;
+}
+
+impl TimestampExt for Timestamp {
+    #[cfg(feature = "chrono")]
+    fn from_chrono<Tz: TimeZone>(datetime: &DateTime<Tz>) -> Self {
+        // Nanoseconds exceed a second during leap seconds
+        Timestamp {
+            seconds: datetime.timestamp(),
+            nanos: datetime.timestamp_subsec_nanos() as i32,
+        }
+        .normalized()
+    }
+
+    #[cfg(feature = "chrono")]
+    fn to_chrono(&self) -> Result<DateTime<Utc>, QdrantError> {
+        let timestamp = self.normalized();
+        DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32)
+            .ok_or_else(|| out_of_range(&timestamp))
+    }
+
+    #[cfg(feature = "time")]
+    fn from_time(datetime: &OffsetDateTime) -> Self {
+        Timestamp {
+            seconds: datetime.unix_timestamp(),
+            nanos: datetime.nanosecond() as i32,
+        }
+    }
+
+    #[cfg(feature = "time")]
+    fn to_time(&self) -> Result<OffsetDateTime, QdrantError> {
+        let timestamp = self.normalized();
+        let nanos = i128::from(timestamp.seconds) * 1_000_000_000 + i128::from(timestamp.nanos);
+        OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(|_| out_of_range(&timestamp))
+    }
+}
+
+fn out_of_range(timestamp: &Timestamp) -> QdrantError {
+    QdrantError::ConversionError(format!(
+        "Timestamp {}s {}ns is out of range",
+        timestamp.seconds, timestamp.nanos,
+    ))
+}
+
+/// The datetime string of a payload value
+fn datetime_str(value: &Value) -> Result<&str, QdrantError> {
+    match &value.kind {
+        Some(Kind::StringValue(string)) => Ok(string),
+        _ => Err(QdrantError::ConversionError(format!(
+            "Expected a datetime string, got {value}",
+        ))),
+    }
+}
+
+/// Implement `From` for `DatetimeRange` from all bounded kinds of ranges
+macro_rules! datetime_range_conversions {
+    ([$($generics:tt)*] $datetime:ty, $to_timestamp:path) => {
+        /// Range including the start and excluding the end
+        impl<$($generics)*> From<Range<$datetime>> for DatetimeRange {
+            fn from(range: Range<$datetime>) -> Self {
+                Self {
+                    gte: Some($to_timestamp(&range.start)),
+                    lt: Some($to_timestamp(&range.end)),
+                    ..Default::default()
+                }
+            }
+        }
+
+        /// Range including the start and the end
+        impl<$($generics)*> From<RangeInclusive<$datetime>> for DatetimeRange {
+            fn from(range: RangeInclusive<$datetime>) -> Self {
+                Self {
+                    gte: Some($to_timestamp(range.start())),
+                    lte: Some($to_timestamp(range.end())),
+                    ..Default::default()
+                }
+            }
+        }
+
+        /// Range including the start
+        impl<$($generics)*> From<RangeFrom<$datetime>> for DatetimeRange {
+            fn from(range: RangeFrom<$datetime>) -> Self {
+                Self {
+                    gte: Some($to_timestamp(&range.start)),
+                    ..Default::default()
+                }
+            }
+        }
+
+        /// Range excluding the end
+        impl<$($generics)*> From<RangeTo<$datetime>> for DatetimeRange {
+            fn from(range: RangeTo<$datetime>) -> Self {
+                Self {
+                    lt: Some($to_timestamp(&range.end)),
+                    ..Default::default()
+                }
+            }
+        }
+
+        /// Range including the end
+        impl<$($generics)*> From<RangeToInclusive<$datetime>> for DatetimeRange {
+            fn from(range: RangeToInclusive<$datetime>) -> Self {
+                Self {
+                    lte: Some($to_timestamp(&range.end)),
+                    ..Default::default()
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+datetime_range_conversions!([Tz: TimeZone] DateTime<Tz>, Timestamp::from_chrono);
+
+#[cfg(feature = "time")]
+datetime_range_conversions!([] OffsetDateTime, Timestamp::from_time);
+
+/// RFC 3339 string, keeping the offset
+#[cfg(feature = "chrono")]
+impl<Tz: TimeZone> From<DateTime<Tz>> for Value
+where
+    Tz::Offset: std::fmt::Display,
+{
+    fn from(datetime: DateTime<Tz>) -> Self {
+        datetime.to_rfc3339().into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Value> for DateTime<FixedOffset> {
+    type Error = QdrantError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let string = datetime_str(&value)?;
+        DateTime::parse_from_rfc3339(string).map_err(|err| {
+            QdrantError::ConversionError(format!("Invalid RFC 3339 datetime {string:?}: {err}"))
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Value> for DateTime<Utc> {
+    type Error = QdrantError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        DateTime::<FixedOffset>::try_from(value).map(|datetime| datetime.to_utc())
+    }
+}
+
+/// RFC 3339 string, keeping the offset
+#[cfg(feature = "time")]
+impl From<OffsetDateTime> for Value {
+    fn from(datetime: OffsetDateTime) -> Self {
+        // RFC 3339 only covers four digit years, fall back to UTC which has no such limit
+        datetime
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| Timestamp::from_time(&datetime).to_string())
+            .into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Value> for OffsetDateTime {
+    type Error = QdrantError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let string = datetime_str(&value)?;
+        OffsetDateTime::parse(string, &Rfc3339).map_err(|err| {
+            QdrantError::ConversionError(format!("Invalid RFC 3339 datetime {string:?}: {err}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_conversions() {
+        let datetime = DateTime::parse_from_rfc3339("2023-02-08T10:49:00.5+01:00").unwrap();
+        let timestamp = Timestamp::from_chrono(&datetime);
+        assert_eq!(
+            timestamp,
+            Timestamp::date_time_nanos(2023, 2, 8, 9, 49, 0, 500_000_000).unwrap()
+        );
+        assert_eq!(timestamp.to_chrono().unwrap(), datetime);
+
+        let value = Value::from(datetime);
+        assert_eq!(value, Value::from("2023-02-08T10:49:00.500+01:00"));
+        assert_eq!(
+            DateTime::<FixedOffset>::try_from(value.clone()).unwrap(),
+            datetime
+        );
+        assert_eq!(DateTime::<Utc>::try_from(value).unwrap(), datetime);
+        assert!(DateTime::<Utc>::try_from(Value::from(1i64)).is_err());
+        assert!(DateTime::<Utc>::try_from(Value::from("2023-02-08")).is_err());
+
+        let range = DatetimeRange::from(datetime..);
+        assert_eq!(range.gte, Some(timestamp));
+        assert_eq!((range.gt, range.lt, range.lte), (None, None, None));
+        let range = DatetimeRange::from(..=datetime);
+        assert_eq!((range.gte, range.lte), (None, Some(timestamp)));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_conversions() {
+        let datetime = OffsetDateTime::parse("2023-02-08T10:49:00.5+01:00", &Rfc3339).unwrap();
+        let timestamp = Timestamp::from_time(&datetime);
+        assert_eq!(
+            timestamp,
+            Timestamp::date_time_nanos(2023, 2, 8, 9, 49, 0, 500_000_000).unwrap()
+        );
+        assert_eq!(timestamp.to_time().unwrap(), datetime);
+
+        let value = Value::from(datetime);
+        assert_eq!(value, Value::from("2023-02-08T10:49:00.5+01:00"));
+        assert_eq!(OffsetDateTime::try_from(value).unwrap(), datetime);
+
+        let end = datetime + time::Duration::DAY;
+        let range = DatetimeRange::from(datetime..end);
+        assert_eq!(range.gte, Some(timestamp));
+        assert_eq!(range.lt, Some(Timestamp::from_time(&end)));
+    }
+}
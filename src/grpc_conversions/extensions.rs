@@ -1,17 +1,18 @@
 use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
 #[cfg(feature = "uuid")]
 use uuid::Uuid;
 
 use crate::payload::Payload;
-#[cfg(feature = "uuid")]
 use crate::qdrant::point_id::PointIdOptions;
 use crate::qdrant::value::Kind;
 use crate::qdrant::{
     HardwareUsage, InferenceUsage, ListValue, ModelUsage, PointId, PointStruct, RetrievedPoint,
     ScoredPoint, Struct, Usage, Value, Vectors,
 };
+use crate::QdrantError;
 
 /// Null value
 static NULL_VALUE: Value = Value {
@@ -304,6 +305,136 @@ impl Hash for PointId {
     }
 }
 
+impl Eq for PointId {}
+
+impl PointId {
+    /// Same ID in the form the server returns it, UUIDs lower-case and hyphenated
+    ///
+    /// The server accepts UUIDs in other forms too, compare normalized IDs to match points sent
+    /// to the server with the points it returns.
+    pub(crate) fn normalized(&self) -> PointId {
+        match &self.point_id_options {
+            Some(PointIdOptions::Uuid(uuid)) if is_uuid(uuid) => canonical_uuid(uuid).into(),
+            _ => self.clone(),
+        }
+    }
+
+    /// Deterministic UUID point ID for a string key
+    ///
+    /// Derives a version 5 UUID from the key in the given namespace, so the same key always maps
+    /// to the same point. Use one namespace per collection or data source.
+    ///
+    /// ```rust
+    /// use qdrant_client::qdrant::PointId;
+    /// use uuid::Uuid;
+    ///
+    /// let namespace = Uuid::parse_str("6ba7b811-9dad-11d1-80b4-00c04fd430c8").unwrap();
+    /// let id = PointId::from_key(&namespace, "doc-42");
+    /// assert_eq!(id, PointId::from_key(&namespace, "doc-42"));
+    /// ```
+    ///
+    /// <small><em>Requires `uuid` feature</em></small>
+    #[cfg(feature = "uuid")]
+    pub fn from_key(namespace: &Uuid, key: &str) -> Self {
+        Uuid::new_v5(namespace, key.as_bytes()).into()
+    }
+
+    /// Random UUID point ID
+    ///
+    /// <small><em>Requires `uuid` feature</em></small>
+    #[cfg(feature = "uuid")]
+    pub fn random() -> Self {
+        Uuid::new_v4().into()
+    }
+}
+
+/// Formats numeric IDs as numbers and UUIDs as strings, an empty ID as nothing
+impl Display for PointId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use crate::qdrant::point_id::PointIdOptions::{Num, Uuid};
+        match &self.point_id_options {
+            Some(Num(num)) => write!(f, "{num}"),
+            Some(Uuid(uuid)) => write!(f, "{uuid}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses unsigned integers as numeric IDs, anything else must be a UUID
+impl FromStr for PointId {
+    type Err = QdrantError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(num) = s.parse::<u64>() {
+            return Ok(num.into());
+        }
+        if is_uuid(s) {
+            Ok(canonical_uuid(s).into())
+        } else {
+            Err(QdrantError::ConversionError(format!(
+                "Point ID {s:?} is neither an unsigned integer nor a UUID",
+            )))
+        }
+    }
+}
+
+/// Whether the string is a UUID in hyphenated or simple form
+fn is_uuid(s: &str) -> bool {
+    let hex =
+        |part: &str, len: usize| part.len() == len && part.bytes().all(|b| b.is_ascii_hexdigit());
+    match s.split('-').collect::<Vec<_>>().as_slice() {
+        [simple] => hex(simple, 32),
+        [a, b, c, d, e] => hex(a, 8) && hex(b, 4) && hex(c, 4) && hex(d, 4) && hex(e, 12),
+        _ => false,
+    }
+}
+
+/// Lower-case hyphenated form of a UUID in hyphenated or simple form
+fn canonical_uuid(uuid: &str) -> String {
+    let hex: String = uuid
+        .chars()
+        .filter(|c| *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..],
+    )
+}
+
+impl TryFrom<PointId> for u64 {
+    type Error = QdrantError;
+
+    fn try_from(id: PointId) -> Result<Self, Self::Error> {
+        match id.point_id_options {
+            Some(PointIdOptions::Num(num)) => Ok(num),
+            _ => Err(QdrantError::ConversionError(format!(
+                "Point ID {id} is not numeric",
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl TryFrom<PointId> for Uuid {
+    type Error = QdrantError;
+
+    fn try_from(id: PointId) -> Result<Self, Self::Error> {
+        match &id.point_id_options {
+            Some(PointIdOptions::Uuid(uuid)) => Uuid::parse_str(uuid).map_err(|err| {
+                QdrantError::ConversionError(format!("Invalid UUID point ID {uuid:?}: {err}"))
+            }),
+            _ => Err(QdrantError::ConversionError(format!(
+                "Point ID {id} is not a UUID",
+            ))),
+        }
+    }
+}
+
 impl Hash for ScoredPoint {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.id.hash(state)
@@ -433,4 +564,44 @@ mod tests {
         // Check that we have exactly 3 models
         assert_eq!(aggregated.models.len(), 3);
     }
+
+    #[test]
+    fn point_id_parsing() {
+        assert_eq!("42".parse::<PointId>().unwrap(), PointId::from(42));
+        let uuid = "936DA01F-9ABD-4D9D-80C7-02AF85C822A8";
+        let id = uuid.parse::<PointId>().unwrap();
+        assert_eq!(id.to_string(), uuid.to_ascii_lowercase());
+        assert_eq!(
+            "936DA01F9ABD4D9D80C702AF85C822A8"
+                .parse::<PointId>()
+                .unwrap(),
+            id,
+        );
+        assert_eq!(PointId::from(uuid).normalized(), id);
+        assert_eq!(PointId::from(42).normalized(), PointId::from(42));
+        assert!("-1".parse::<PointId>().is_err());
+        assert!("doc-42".parse::<PointId>().is_err());
+
+        assert_eq!(PointId::from(7).to_string(), "7");
+        assert_eq!(u64::try_from(PointId::from(7)).unwrap(), 7);
+        assert!(u64::try_from(id).is_err());
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn point_id_uuids() {
+        let namespace = Uuid::NAMESPACE_URL;
+        let id = PointId::from_key(&namespace, "https://qdrant.tech");
+        assert_eq!(id, PointId::from_key(&namespace, "https://qdrant.tech"));
+        assert_ne!(
+            id,
+            PointId::from_key(&Uuid::NAMESPACE_DNS, "https://qdrant.tech")
+        );
+
+        let uuid = Uuid::try_from(id.clone()).unwrap();
+        assert_eq!(uuid.get_version_num(), 5);
+        assert_eq!(id.to_string().parse::<PointId>().unwrap(), id);
+        assert!(Uuid::try_from(PointId::from(1)).is_err());
+        assert_ne!(PointId::random(), PointId::random());
+    }
 }
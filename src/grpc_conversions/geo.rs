@@ -0,0 +1,284 @@
+#[cfg(feature = "geo-types")]
+use geo_types::{Coord, LineString, Point, Polygon, Rect};
+
+#[cfg(feature = "geo-types")]
+use crate::qdrant::GeoBoundingBox;
+use crate::qdrant::{GeoLineString, GeoPoint, GeoPolygon};
+use crate::QdrantError;
+
+#[cfg(feature = "geo-types")]
+impl From<Coord<f64>> for GeoPoint {
+    fn from(coord: Coord<f64>) -> Self {
+        Self {
+            lon: coord.x,
+            lat: coord.y,
+        }
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl From<GeoPoint> for Coord<f64> {
+    fn from(point: GeoPoint) -> Self {
+        Self {
+            x: point.lon,
+            y: point.lat,
+        }
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl From<Point<f64>> for GeoPoint {
+    fn from(point: Point<f64>) -> Self {
+        point.0.into()
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl From<GeoPoint> for Point<f64> {
+    fn from(point: GeoPoint) -> Self {
+        Point(point.into())
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl From<Point<f64>> for crate::qdrant::Value {
+    fn from(point: Point<f64>) -> Self {
+        GeoPoint::from(point).into()
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl From<LineString<f64>> for GeoLineString {
+    fn from(line: LineString<f64>) -> Self {
+        Self {
+            points: line.0.into_iter().map(GeoPoint::from).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl From<GeoLineString> for LineString<f64> {
+    fn from(line: GeoLineString) -> Self {
+        line.points.into_iter().map(Coord::from).collect()
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl From<Polygon<f64>> for GeoPolygon {
+    fn from(polygon: Polygon<f64>) -> Self {
+        let (exterior, interiors) = polygon.into_inner();
+        Self {
+            exterior: Some(exterior.into()),
+            interiors: interiors.into_iter().map(GeoLineString::from).collect(),
+        }
+    }
+}
+
+/// Rings are closed if needed, a missing exterior becomes empty
+#[cfg(feature = "geo-types")]
+impl From<GeoPolygon> for Polygon<f64> {
+    fn from(polygon: GeoPolygon) -> Self {
+        Polygon::new(
+            polygon.exterior.unwrap_or_default().into(),
+            polygon
+                .interiors
+                .into_iter()
+                .map(LineString::from)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl From<Rect<f64>> for GeoBoundingBox {
+    fn from(rect: Rect<f64>) -> Self {
+        let (min, max) = (rect.min(), rect.max());
+        Self {
+            top_left: Some(GeoPoint {
+                lon: min.x,
+                lat: max.y,
+            }),
+            bottom_right: Some(GeoPoint {
+                lon: max.x,
+                lat: min.y,
+            }),
+        }
+    }
+}
+
+/// Fails for boxes crossing the antimeridian, a [`Rect`] cannot represent them
+#[cfg(feature = "geo-types")]
+impl TryFrom<GeoBoundingBox> for Rect<f64> {
+    type Error = QdrantError;
+
+    fn try_from(bounding_box: GeoBoundingBox) -> Result<Self, Self::Error> {
+        match (bounding_box.top_left, bounding_box.bottom_right) {
+            (Some(top_left), Some(bottom_right)) if top_left.lon > bottom_right.lon => Err(
+                QdrantError::ConversionError("Bounding box crosses the antimeridian".to_string()),
+            ),
+            (Some(top_left), Some(bottom_right)) if top_left.lat < bottom_right.lat => {
+                Err(QdrantError::ConversionError(
+                    "Bounding box top left corner is below the bottom right corner".to_string(),
+                ))
+            }
+            (Some(top_left), Some(bottom_right)) => {
+                Ok(Rect::new(Coord::from(top_left), Coord::from(bottom_right)))
+            }
+            _ => Err(QdrantError::ConversionError(
+                "Bounding box is missing a corner".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl GeoPolygon {
+    /// Polygon from GeoJSON
+    ///
+    /// Accepts a `Polygon` geometry, or a `Feature` with one. Positions are `[lon, lat]`, any
+    /// altitude is ignored. The first ring is the exterior, further rings are holes.
+    ///
+    /// ```rust
+    /// use qdrant_client::qdrant::{Condition, GeoPolygon};
+    /// use serde_json::json;
+    ///
+    /// let geojson = json!({
+    ///     "type": "Feature",
+    ///     "properties": {"name": "Mitte"},
+    ///     "geometry": {
+    ///         "type": "Polygon",
+    ///         "coordinates": [[[13.37, 52.50], [13.43, 52.50], [13.43, 52.54], [13.37, 52.50]]]
+    ///     }
+    /// });
+    ///
+    /// let condition = Condition::geo_polygon("location", GeoPolygon::from_geojson(&geojson).unwrap());
+    /// ```
+    pub fn from_geojson(geojson: &serde_json::Value) -> Result<Self, QdrantError> {
+        let invalid =
+            |reason: &str| QdrantError::ConversionError(format!("Invalid GeoJSON: {reason}"));
+
+        match geojson.get("type").and_then(serde_json::Value::as_str) {
+            Some("Feature") => Self::from_geojson(
+                geojson
+                    .get("geometry")
+                    .ok_or_else(|| invalid("feature without geometry"))?,
+            ),
+            Some("Polygon") => {
+                let mut rings = geojson
+                    .get("coordinates")
+                    .and_then(serde_json::Value::as_array)
+                    .ok_or_else(|| invalid("polygon without coordinates"))?
+                    .iter()
+                    .map(|ring| geojson_ring(ring).ok_or_else(|| invalid("malformed ring")));
+                let exterior = rings
+                    .next()
+                    .ok_or_else(|| invalid("polygon without rings"))??;
+                Ok(Self {
+                    exterior: Some(exterior),
+                    interiors: rings.collect::<Result<_, _>>()?,
+                })
+            }
+            Some(other) => Err(invalid(&format!("expected a Polygon, got {other}"))),
+            None => Err(invalid("missing type")),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn geojson_ring(ring: &serde_json::Value) -> Option<GeoLineString> {
+    ring.as_array()?
+        .iter()
+        .map(|position| match position.as_array()?.as_slice() {
+            [lon, lat, ..] => Some(GeoPoint {
+                lon: lon.as_f64()?,
+                lat: lat.as_f64()?,
+            }),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(GeoLineString::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(lon: f64, lat: f64) -> GeoPoint {
+        GeoPoint { lon, lat }
+    }
+
+    #[cfg(feature = "geo-types")]
+    #[test]
+    fn geo_types_conversions() {
+        use geo_types::{coord, line_string, polygon};
+
+        assert_eq!(GeoPoint::from(Point::new(13.4, 52.5)), point(13.4, 52.5));
+        assert_eq!(Point::from(point(13.4, 52.5)), Point::new(13.4, 52.5));
+
+        let polygon = polygon!(
+            exterior: [(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 0.)],
+            interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 1.)]],
+        );
+        let geo_polygon = GeoPolygon::from(polygon.clone());
+        assert_eq!(
+            geo_polygon.exterior.as_ref().unwrap().points[1],
+            point(10., 0.)
+        );
+        assert_eq!(geo_polygon.interiors.len(), 1);
+        assert_eq!(Polygon::from(geo_polygon), polygon);
+
+        let line = line_string![(x: 0., y: 0.), (x: 1., y: 1.)];
+        assert_eq!(LineString::from(GeoLineString::from(line.clone())), line);
+
+        let rect = Rect::new(coord! { x: 10., y: 0. }, coord! { x: 0., y: 5. });
+        let bounding_box = GeoBoundingBox::from(rect);
+        assert_eq!(bounding_box.top_left, Some(point(0., 5.)));
+        assert_eq!(bounding_box.bottom_right, Some(point(10., 0.)));
+        assert_eq!(Rect::try_from(bounding_box).unwrap(), rect);
+        assert!(Rect::try_from(GeoBoundingBox::default()).is_err());
+
+        // From 170 degrees east across the antimeridian to 170 degrees west
+        let antimeridian = GeoBoundingBox {
+            top_left: Some(point(170., 10.)),
+            bottom_right: Some(point(-170., -10.)),
+        };
+        assert!(Rect::try_from(antimeridian).is_err());
+        let upside_down = GeoBoundingBox {
+            top_left: Some(point(0., -10.)),
+            bottom_right: Some(point(10., 10.)),
+        };
+        assert!(Rect::try_from(upside_down).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn geojson_polygon() {
+        use serde_json::json;
+
+        let polygon = GeoPolygon::from_geojson(&json!({
+            "type": "Polygon",
+            "coordinates": [
+                [[0, 0, 100], [10, 0], [10, 10], [0, 0]],
+                [[1, 1], [2, 1], [2, 2], [1, 1]],
+            ],
+        }))
+        .unwrap();
+        assert_eq!(
+            polygon.exterior.unwrap().points,
+            vec![
+                point(0., 0.),
+                point(10., 0.),
+                point(10., 10.),
+                point(0., 0.)
+            ],
+        );
+        assert_eq!(polygon.interiors[0].points[2], point(2., 2.));
+
+        let point_geometry = json!({"type": "Point", "coordinates": [0, 0]});
+        assert!(GeoPolygon::from_geojson(&point_geometry).is_err());
+        let malformed = json!({"type": "Polygon", "coordinates": [[[0]]]});
+        assert!(GeoPolygon::from_geojson(&malformed).is_err());
+        let feature = json!({"type": "Feature", "geometry": null});
+        assert!(GeoPolygon::from_geojson(&feature).is_err());
+    }
+}
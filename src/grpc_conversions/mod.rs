@@ -1,4 +1,8 @@
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub mod datetime;
 mod extensions;
+#[cfg(any(feature = "geo-types", feature = "serde"))]
+mod geo;
 pub mod metadata;
 mod primitives;
 pub mod vectors;
@@ -0,0 +1,615 @@
+//! Payload paths using the Qdrant key syntax
+//!
+//! Paths address nested payload values, such as `country.name`, `country.cities[].population`
+//! or `a.b[0]`. Keys made of anything but alphanumerics, `_` and `-` are quoted, like `a."b.c"`
+//! or `"first name"`.
+//!
+//! Documentation: <https://qdrant.tech/documentation/concepts/filtering/#nested-key>
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::qdrant::value::Kind;
+use crate::qdrant::{ListValue, Struct, Value};
+use crate::{Payload, QdrantError};
+
+/// Element of a payload path
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum JsonPathItem {
+    /// Object key, like `a`
+    Key(String),
+    /// Array element, like `[0]`
+    Index(usize),
+    /// All array elements, `[]`
+    Wildcard,
+}
+
+/// Parsed payload path
+///
+/// The first element is always a key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct JsonPath {
+    pub(crate) first_key: String,
+    pub(crate) rest: Vec<JsonPathItem>,
+}
+
+impl JsonPath {
+    pub(crate) fn new(first_key: impl Into<String>) -> Self {
+        Self {
+            first_key: first_key.into(),
+            rest: Vec::new(),
+        }
+    }
+
+    /// This path extended by a key
+    pub(crate) fn key(&self, key: impl Into<String>) -> Self {
+        let mut path = self.clone();
+        path.rest.push(JsonPathItem::Key(key.into()));
+        path
+    }
+
+    pub(crate) fn parse(path: &str) -> Result<Self, QdrantError> {
+        let invalid =
+            |reason: &str| QdrantError::ConversionError(format!("Invalid path {path:?}: {reason}"));
+
+        let mut chars = path.chars().peekable();
+        let mut first_key = None;
+        let mut rest = Vec::new();
+
+        loop {
+            // Key, quoted or bare
+            let key = if chars.peek() == Some(&'"') {
+                chars.next();
+                let mut key = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break key,
+                        Some(c) => key.push(c),
+                        None => return Err(invalid("unterminated quote")),
+                    }
+                }
+            } else {
+                let mut key = String::new();
+                while let Some(c) = chars.next_if(|&c| is_bare_key_char(c)) {
+                    key.push(c);
+                }
+                if key.is_empty() {
+                    return Err(invalid("expected a key"));
+                }
+                key
+            };
+            match first_key {
+                None => first_key = Some(key),
+                Some(_) => rest.push(JsonPathItem::Key(key)),
+            }
+
+            // Array indices
+            while chars.next_if_eq(&'[').is_some() {
+                let mut index = String::new();
+                while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                    index.push(c);
+                }
+                if chars.next() != Some(']') {
+                    return Err(invalid("expected `]`"));
+                }
+                rest.push(match index.as_str() {
+                    "" => JsonPathItem::Wildcard,
+                    index => JsonPathItem::Index(
+                        index.parse().map_err(|_| invalid("index out of range"))?,
+                    ),
+                });
+            }
+
+            match chars.next() {
+                None => break,
+                Some('.') => continue,
+                Some(_) => return Err(invalid("expected `.` or `[`")),
+            }
+        }
+
+        Ok(Self {
+            first_key: first_key.expect("path has a first key"),
+            rest,
+        })
+    }
+
+    fn has_wildcard(&self) -> bool {
+        self.rest.contains(&JsonPathItem::Wildcard)
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_key(f: &mut fmt::Formatter<'_>, key: &str) -> fmt::Result {
+            if key.is_empty() || !key.chars().all(is_bare_key_char) {
+                write!(f, "\"{key}\"")
+            } else {
+                f.write_str(key)
+            }
+        }
+
+        write_key(f, &self.first_key)?;
+        for item in &self.rest {
+            match item {
+                JsonPathItem::Key(key) => {
+                    f.write_str(".")?;
+                    write_key(f, key)?;
+                }
+                JsonPathItem::Index(index) => write!(f, "[{index}]")?,
+                JsonPathItem::Wildcard => f.write_str("[]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Characters allowed in unquoted keys, like on the server
+fn is_bare_key_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+fn collect_values<'a>(value: &'a Value, path: &[JsonPathItem], out: &mut Vec<&'a Value>) {
+    let Some((head, tail)) = path.split_first() else {
+        out.push(value);
+        return;
+    };
+    match (head, &value.kind) {
+        (JsonPathItem::Key(key), Some(Kind::StructValue(Struct { fields }))) => {
+            if let Some(value) = fields.get(key) {
+                collect_values(value, tail, out);
+            }
+        }
+        (JsonPathItem::Index(index), Some(Kind::ListValue(ListValue { values }))) => {
+            if let Some(value) = values.get(*index) {
+                collect_values(value, tail, out);
+            }
+        }
+        (JsonPathItem::Wildcard, Some(Kind::ListValue(ListValue { values }))) => {
+            for value in values {
+                collect_values(value, tail, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn get_value_mut<'a>(value: &'a mut Value, path: &[JsonPathItem]) -> Option<&'a mut Value> {
+    let Some((head, tail)) = path.split_first() else {
+        return Some(value);
+    };
+    let next = match (head, &mut value.kind) {
+        (JsonPathItem::Key(key), Some(Kind::StructValue(Struct { fields }))) => fields.get_mut(key),
+        (JsonPathItem::Index(index), Some(Kind::ListValue(ListValue { values }))) => {
+            values.get_mut(*index)
+        }
+        _ => None,
+    }?;
+    get_value_mut(next, tail)
+}
+
+/// Set the value at `path`, creating missing objects along the way
+///
+/// Non-object values are replaced by objects when a key is set in them. Array elements are never
+/// created, setting an index out of bounds does nothing.
+fn set_value(target: &mut Value, path: &[JsonPathItem], value: &Value) {
+    let Some((head, tail)) = path.split_first() else {
+        *target = value.clone();
+        return;
+    };
+    match head {
+        JsonPathItem::Key(key) => {
+            if !matches!(target.kind, Some(Kind::StructValue(_))) {
+                target.kind = Some(Kind::StructValue(Struct::default()));
+            }
+            if let Some(Kind::StructValue(Struct { fields })) = &mut target.kind {
+                set_in_fields(fields, key, tail, value);
+            }
+        }
+        JsonPathItem::Index(index) => {
+            if let Some(Kind::ListValue(ListValue { values })) = &mut target.kind {
+                if let Some(target) = values.get_mut(*index) {
+                    set_value(target, tail, value);
+                }
+            }
+        }
+        JsonPathItem::Wildcard => {
+            if let Some(Kind::ListValue(ListValue { values })) = &mut target.kind {
+                for target in values {
+                    set_value(target, tail, value);
+                }
+            }
+        }
+    }
+}
+
+fn set_in_fields(
+    fields: &mut HashMap<String, Value>,
+    key: &str,
+    path: &[JsonPathItem],
+    value: &Value,
+) {
+    match path.first() {
+        None => {
+            fields.insert(key.to_string(), value.clone());
+        }
+        // Only objects are created, arrays must exist already
+        Some(JsonPathItem::Key(_)) => set_value(
+            fields.entry(key.to_string()).or_insert_with(|| Value {
+                kind: Some(Kind::StructValue(Struct::default())),
+            }),
+            path,
+            value,
+        ),
+        Some(JsonPathItem::Index(_) | JsonPathItem::Wildcard) => {
+            if let Some(target) = fields.get_mut(key) {
+                set_value(target, path, value);
+            }
+        }
+    }
+}
+
+fn remove_values(target: &mut Value, path: &[JsonPathItem], out: &mut Vec<Value>) {
+    let Some((head, tail)) = path.split_first() else {
+        return;
+    };
+    match (head, &mut target.kind) {
+        (JsonPathItem::Key(key), Some(Kind::StructValue(Struct { fields }))) => {
+            remove_in_fields(fields, key, tail, out);
+        }
+        (JsonPathItem::Index(index), Some(Kind::ListValue(ListValue { values }))) => {
+            if tail.is_empty() {
+                if *index < values.len() {
+                    out.push(values.remove(*index));
+                }
+            } else if let Some(target) = values.get_mut(*index) {
+                remove_values(target, tail, out);
+            }
+        }
+        (JsonPathItem::Wildcard, Some(Kind::ListValue(ListValue { values }))) => {
+            if tail.is_empty() {
+                out.append(values);
+            } else {
+                for target in values {
+                    remove_values(target, tail, out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn remove_in_fields(
+    fields: &mut HashMap<String, Value>,
+    key: &str,
+    path: &[JsonPathItem],
+    out: &mut Vec<Value>,
+) {
+    if path.is_empty() {
+        out.extend(fields.remove(key));
+    } else if let Some(target) = fields.get_mut(key) {
+        remove_values(target, path, out);
+    }
+}
+
+/// Payload paths
+///
+/// Paths use the same syntax as payload keys in filters and in
+/// [`SetPayloadPointsBuilder::key`](crate::qdrant::SetPayloadPointsBuilder::key), such as
+/// `country.name`, `a.b[0]` and `country.cities[].population`.
+///
+/// Documentation: <https://qdrant.tech/documentation/concepts/filtering/#nested-key>
+impl Payload {
+    /// Get the value at `path`
+    ///
+    /// Returns `None` if nothing is found, if the path is invalid or if it contains `[]`. Use
+    /// [`iter_path`](Self::iter_path) for paths matching multiple values.
+    ///
+    /// ```rust
+    /// use qdrant_client::Payload;
+    /// use serde_json::json;
+    ///
+    /// let payload = Payload::try_from(json!({"a": {"b": [1, 2, 3]}})).unwrap();
+    /// assert_eq!(payload.get_path("a.b[1]"), Some(&2.into()));
+    /// assert_eq!(payload.get_path("a.c"), None);
+    /// ```
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        let path = JsonPath::parse(path).ok().filter(|p| !p.has_wildcard())?;
+        let mut values = Vec::with_capacity(1);
+        collect_values(self.0.get(&path.first_key)?, &path.rest, &mut values);
+        values.pop()
+    }
+
+    /// Get a mutable reference to the value at `path`
+    ///
+    /// Returns `None` if nothing is found, if the path is invalid or if it contains `[]`.
+    pub fn get_path_mut(&mut self, path: &str) -> Option<&mut Value> {
+        let path = JsonPath::parse(path).ok().filter(|p| !p.has_wildcard())?;
+        get_value_mut(self.0.get_mut(&path.first_key)?, &path.rest)
+    }
+
+    /// Iterate over all values at `path`
+    ///
+    /// Each `[]` in the path matches all elements of an array. Invalid paths match nothing.
+    ///
+    /// ```rust
+    /// use qdrant_client::Payload;
+    /// use serde_json::json;
+    ///
+    /// let payload = Payload::try_from(json!({
+    ///     "country": {
+    ///         "cities": [
+    ///             {"name": "Berlin", "population": 3.7},
+    ///             {"name": "Munich", "population": 1.5},
+    ///         ]
+    ///     }
+    /// }))
+    /// .unwrap();
+    ///
+    /// let populations: Vec<_> = payload
+    ///     .iter_path("country.cities[].population")
+    ///     .filter_map(|value| value.as_double())
+    ///     .collect();
+    /// assert_eq!(populations, vec![3.7, 1.5]);
+    /// ```
+    pub fn iter_path<'a>(&'a self, path: &str) -> impl Iterator<Item = &'a Value> + 'a {
+        let mut values = Vec::new();
+        if let Ok(path) = JsonPath::parse(path) {
+            if let Some(value) = self.0.get(&path.first_key) {
+                collect_values(value, &path.rest, &mut values);
+            }
+        }
+        values.into_iter()
+    }
+
+    /// Set the value at `path`
+    ///
+    /// Missing objects along the path are created, replacing values that are not objects. Array
+    /// elements are not created: indices out of bounds and `[]` on missing arrays set nothing.
+    /// Each `[]` sets the value in all elements of an array.
+    ///
+    /// Returns an error if the path is invalid.
+    ///
+    /// ```rust
+    /// use qdrant_client::Payload;
+    /// use serde_json::json;
+    ///
+    /// let mut payload = Payload::new();
+    /// payload.set_path("a.b", 1).unwrap();
+    /// assert_eq!(serde_json::Value::from(payload), json!({"a": {"b": 1}}));
+    /// ```
+    pub fn set_path(&mut self, path: &str, value: impl Into<Value>) -> Result<(), QdrantError> {
+        self.set_json_path(&JsonPath::parse(path)?, &value.into());
+        Ok(())
+    }
+
+    /// Remove the values at `path`, returning them
+    ///
+    /// Array elements removed by index shift later elements, `[]` at the end of a path empties
+    /// the array. Invalid paths remove nothing.
+    pub fn remove_path(&mut self, path: &str) -> Vec<Value> {
+        JsonPath::parse(path)
+            .map(|path| self.remove_json_path(&path))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn set_json_path(&mut self, path: &JsonPath, value: &Value) {
+        set_in_fields(&mut self.0, &path.first_key, &path.rest, value);
+    }
+
+    pub(crate) fn remove_json_path(&mut self, path: &JsonPath) -> Vec<Value> {
+        let mut removed = Vec::new();
+        remove_in_fields(&mut self.0, &path.first_key, &path.rest, &mut removed);
+        removed
+    }
+}
+
+/// Value paths
+///
+/// Paths are relative to this value, which must be a struct. See [`Payload::get_path`].
+impl Value {
+    /// Get the value at `path` within this struct value
+    ///
+    /// Returns `None` if nothing is found, if the path is invalid or if it contains `[]`.
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        let path = JsonPath::parse(path).ok().filter(|p| !p.has_wildcard())?;
+        let mut values = Vec::with_capacity(1);
+        collect_values(self, &path.items(), &mut values);
+        values.pop()
+    }
+
+    /// Get a mutable reference to the value at `path` within this struct value
+    ///
+    /// Returns `None` if nothing is found, if the path is invalid or if it contains `[]`.
+    pub fn get_path_mut(&mut self, path: &str) -> Option<&mut Value> {
+        let path = JsonPath::parse(path).ok().filter(|p| !p.has_wildcard())?;
+        get_value_mut(self, &path.items())
+    }
+
+    /// Iterate over all values at `path` within this struct value
+    ///
+    /// Each `[]` in the path matches all elements of an array. Invalid paths match nothing.
+    pub fn iter_path<'a>(&'a self, path: &str) -> impl Iterator<Item = &'a Value> + 'a {
+        let mut values = Vec::new();
+        if let Ok(path) = JsonPath::parse(path) {
+            collect_values(self, &path.items(), &mut values);
+        }
+        values.into_iter()
+    }
+
+    /// Set the value at `path` within this value, see [`Payload::set_path`]
+    ///
+    /// This value is replaced by a struct if it is not one.
+    pub fn set_path(&mut self, path: &str, value: impl Into<Value>) -> Result<(), QdrantError> {
+        let path = JsonPath::parse(path)?;
+        set_value(self, &path.items(), &value.into());
+        Ok(())
+    }
+
+    /// Remove the values at `path` within this struct value, see [`Payload::remove_path`]
+    pub fn remove_path(&mut self, path: &str) -> Vec<Value> {
+        let mut removed = Vec::new();
+        if let Ok(path) = JsonPath::parse(path) {
+            remove_values(self, &path.items(), &mut removed);
+        }
+        removed
+    }
+}
+
+impl JsonPath {
+    /// All elements of the path, starting with the first key
+    pub(crate) fn items(&self) -> Vec<JsonPathItem> {
+        std::iter::once(JsonPathItem::Key(self.first_key.clone()))
+            .chain(self.rest.iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn payload(value: serde_json::Value) -> Payload {
+        Payload::try_from(value).unwrap()
+    }
+
+    fn json(payload: Payload) -> serde_json::Value {
+        serde_json::Value::from(payload)
+    }
+
+    #[test]
+    fn parse_paths() {
+        use JsonPathItem::*;
+
+        let path = JsonPath::parse("country.cities[].population").unwrap();
+        assert_eq!(path.first_key, "country");
+        assert_eq!(
+            path.rest,
+            vec![Key("cities".into()), Wildcard, Key("population".into())],
+        );
+
+        let path = JsonPath::parse(r#"a[0][]."b.c""#).unwrap();
+        assert_eq!(path.rest, vec![Index(0), Wildcard, Key("b.c".into())]);
+
+        let path = JsonPath::parse(r#""first name"."user@id""#).unwrap();
+        assert_eq!(path.first_key, "first name");
+        assert_eq!(path.rest, vec![Key("user@id".into())]);
+        assert_eq!(JsonPath::new("first name").to_string(), r#""first name""#);
+        assert_eq!(JsonPath::new("user@id").to_string(), r#""user@id""#);
+
+        for path in [
+            "a.b",
+            "a[0].b",
+            "a[].b[3]",
+            "snake_case.kebab-case.ünïcode",
+            r#""a.b".c"#,
+            r#"a."[x]""#,
+            r#""first name".last"#,
+            r#"a."user@id""#,
+        ] {
+            assert_eq!(JsonPath::parse(path).unwrap().to_string(), path);
+        }
+
+        for invalid in [
+            "",
+            ".a",
+            "a.",
+            "a..b",
+            "a[x]",
+            "a[0",
+            "a]",
+            "[0]",
+            "a[0]b",
+            "first name",
+            "user@id",
+        ] {
+            assert!(JsonPath::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn get_paths() {
+        let payload = payload(json!({
+            "a": {"b": [10, {"c": 20}]},
+            "country": {"cities": [{"population": 1}, {"population": 2}, {"name": "x"}]},
+            "d.e": 5,
+        }));
+
+        assert_eq!(payload.get_path("a.b[0]"), Some(&10.into()));
+        assert_eq!(payload.get_path("a.b[1].c"), Some(&20.into()));
+        assert_eq!(payload.get_path(r#""d.e""#), Some(&5.into()));
+        assert_eq!(payload.get_path("a.b[2]"), None);
+        assert_eq!(payload.get_path("a.x.y"), None);
+        assert_eq!(payload.get_path("country.cities[].population"), None);
+
+        let populations: Vec<_> = payload.iter_path("country.cities[].population").collect();
+        assert_eq!(populations, vec![&1.into(), &2.into()]);
+        assert_eq!(payload.iter_path("a.b[]").count(), 2);
+        // Arrays are only traversed with `[]`
+        assert_eq!(payload.iter_path("country.cities.population").count(), 0);
+
+        let a = payload.get_path("a").unwrap();
+        assert_eq!(a.get_path("a.b[1].c"), None);
+        assert_eq!(
+            a.get_path("b[1]").and_then(|v| v.get_path("c")),
+            Some(&20.into())
+        );
+    }
+
+    #[test]
+    fn set_paths() {
+        let mut payload = payload(json!({
+            "a": 1,
+            "list": [{"x": 1}, {"x": 2}],
+        }));
+
+        payload.set_path("a.b.c", "new").unwrap();
+        payload.set_path("list[].y", true).unwrap();
+        payload.set_path("list[0].x", 10).unwrap();
+        payload.set_path("list[5].x", 10).unwrap();
+        payload.set_path("missing[0]", 10).unwrap();
+        assert!(payload.set_path("a..b", 1).is_err());
+
+        assert_eq!(
+            json(payload.clone()),
+            json!({
+                "a": {"b": {"c": "new"}},
+                "list": [{"x": 10, "y": true}, {"x": 2, "y": true}],
+            }),
+        );
+
+        *payload.get_path_mut("list[1].x").unwrap() = 20.into();
+        assert_eq!(payload.get_path("list[1].x"), Some(&20.into()));
+    }
+
+    #[test]
+    fn remove_paths() {
+        let mut payload = payload(json!({
+            "a": {"b": 1, "c": 2},
+            "list": [{"x": 1, "y": 1}, {"x": 2}],
+            "nums": [1, 2, 3],
+        }));
+
+        assert_eq!(payload.remove_path("a.b"), vec![Value::from(1)]);
+        assert_eq!(payload.remove_path("list[].x").len(), 2);
+        assert_eq!(payload.remove_path("nums[1]"), vec![Value::from(2)]);
+        assert_eq!(payload.remove_path("nothing.here"), vec![]);
+
+        assert_eq!(
+            json(payload.clone()),
+            json!({
+                "a": {"c": 2},
+                "list": [{"y": 1}, {}],
+                "nums": [1, 3],
+            }),
+        );
+
+        assert_eq!(payload.remove_path("nums[]").len(), 2);
+        assert_eq!(
+            payload.get_path("nums"),
+            Some(&Value::from(Vec::<i64>::new()))
+        );
+    }
+}
@@ -121,6 +121,29 @@
 //! documentation for details.
 //!
 //! Documentation: <https://qdrant.tech/documentation/concepts/search/#query-api>
+//!
+//! # REST JSON
+//!
+//! With the `serde` feature, filters, queries and query, scroll and count requests implement
+//! `Serialize` and `Deserialize` in the JSON shape of the REST API. This allows loading a request
+//! body written for the REST API and sending it over gRPC. Parameters that are part of the URL in
+//! the REST API, such as the collection name, are not part of the JSON:
+//!
+//! ```no_run
+//!# use qdrant_client::{Qdrant, QdrantError};
+//! use qdrant_client::qdrant::QueryPoints;
+//!
+//!# async fn query(client: &Qdrant)
+//!# -> Result<(), Box<dyn std::error::Error>> {
+//! let mut request: QueryPoints = serde_json::from_str(
+//!     r#"{"query": [0.2, 0.1, 0.9], "filter": {"must": [{"key": "city", "match": {"value": "London"}}]}}"#,
+//! )?;
+//! request.collection_name = "my_collection".to_string();
+//!
+//! let response = client.query(request).await?;
+//!# Ok(())
+//!# }
+//! ```
 
 #![doc(html_logo_url = "https://qdrant.tech/favicon/android-chrome-192x192.png")]
 #![doc(issue_tracker_base_url = "https://github.com/qdrant/rust-client/issues/")]
@@ -141,20 +164,122 @@ mod expressions;
 mod filters;
 mod grpc_conversions;
 mod grpc_macros;
+mod json_path;
 mod manual_builder;
 mod payload;
+mod payload_diff;
 mod qdrant_client;
 #[cfg(feature = "serde")]
 mod serde_impl;
+#[cfg(feature = "serde")]
+mod serde_rest;
 
+#[cfg(feature = "derive")]
+pub mod derive;
 #[cfg(feature = "serde")]
 pub mod serde_deser;
+#[cfg(feature = "serde")]
+pub mod serde_ser;
 
 // Re-exports
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use crate::grpc_conversions::datetime::TimestampExt;
 pub use crate::payload::Payload;
+pub use crate::payload_diff::PayloadDiff;
 pub use crate::qdrant_client::error::QdrantError;
 pub use crate::qdrant_client::{Qdrant, QdrantBuilder};
 
+/// Automatic batching of concurrent requests
+pub mod batching {
+    pub use crate::qdrant_client::batching::{BatchingConfig, RequestBatcher};
+}
+
+/// Snapshots of a collection across all nodes of a cluster
+#[cfg(all(feature = "download_snapshots", feature = "serde"))]
+pub mod cluster_snapshot {
+    pub use crate::qdrant_client::cluster_snapshot::{
+        ClusterSnapshot, ClusterSnapshotManifest, NodeSnapshot, ShardReplica,
+    };
+}
+
+/// Declarative collection specs
+pub mod collection_spec {
+    pub use crate::qdrant_client::collection_spec::{
+        CollectionPlan, CollectionSpec, PlanAction, SpecConflict,
+    };
+}
+
+/// Upserts skipping unchanged points
+pub mod content_hash {
+    pub use crate::qdrant_client::content_hash::{
+        ContentHashConfig, ContentHashUpsertResponse, DEFAULT_CONTENT_HASH_FIELD,
+    };
+}
+
+/// Query multiple collections with client-side fusion
+pub mod federated {
+    pub use crate::qdrant_client::federated::{
+        DedupeKey, FederatedFusion, FederatedHit, FederatedQuery, FederatedResponse,
+        FederatedTarget, HitSource, ScoreOrder,
+    };
+}
+
+/// Hybrid search over dense, sparse and late interaction vectors
+pub mod hybrid {
+    pub use crate::qdrant_client::hybrid::{
+        BranchMatch, HybridBranch, HybridHit, HybridQuery, HybridRerank, HybridResponse,
+    };
+}
+
+/// Balancing shard replicas across peers
+pub mod rebalance {
+    pub use crate::qdrant_client::rebalance::{
+        RebalancePlan, RebalancePlanner, ShardMove, ShardMoveKind,
+    };
+}
+
+/// Zero downtime reindexing through alias switches
+pub mod reindex {
+    pub use crate::qdrant_client::reindex::{PointTransform, Reindex, ReindexReport};
+}
+
+/// Client-side reranking of query results
+pub mod rerank {
+    pub use crate::qdrant_client::rerank::{MmrReranker, PayloadBoost, RerankContext, Reranker};
+}
+
+/// Points with deserialized payloads
+#[cfg(feature = "serde")]
+pub mod typed {
+    pub use crate::qdrant_client::typed::{TypedHit, TypedScrollResponse};
+}
+
+/// Snapshot creation with retention policies
+pub mod snapshot_manager {
+    #[cfg(feature = "download_snapshots")]
+    pub use crate::qdrant_client::snapshot_manager::{LocalDirSink, SnapshotSink};
+    pub use crate::qdrant_client::snapshot_manager::{
+        ManagedSnapshot, RetentionPolicy, SnapshotManager, SnapshotReport,
+    };
+}
+
+/// Tenant scoped access to multitenant collections
+pub mod tenant {
+    pub use crate::qdrant_client::tenant::{TenantClient, DEFAULT_TENANT_FIELD};
+}
+
+/// Optimistic concurrency control for points
+pub mod versioned {
+    pub use crate::qdrant_client::versioned::{
+        VersionConflict, VersionedUpsert, VersionedUpsertResponse, DEFAULT_VERSION_FIELD,
+    };
+}
+
+/// Waiting for collection states
+pub mod wait {
+    pub use crate::qdrant_client::wait::WaitCondition;
+}
+
 /// Client configuration
 pub mod config {
     pub use crate::qdrant_client::config::{
@@ -4,6 +4,7 @@
 //! need access to those private fields. For this reason we introduce a few manually created builder here.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use derive_builder::Builder;
 
@@ -402,6 +403,18 @@ pub mod points_update_operation {
     builder_type_conversions!(ClearPayload, ClearPayloadBuilder);
 }
 
+/// Progress of a snapshot download, see [`SnapshotDownloadBuilder::progress`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnapshotDownloadProgress {
+    /// Number of bytes on disk, including bytes of a resumed download
+    pub downloaded: u64,
+    /// Size of the snapshot in bytes, if known
+    pub total: Option<u64>,
+}
+
+/// Callback receiving the progress of a snapshot download
+pub type SnapshotDownloadProgressFn = Arc<dyn Fn(SnapshotDownloadProgress) + Send + Sync>;
+
 #[derive(Builder)]
 #[builder(
     build_fn(private, name = "build_inner"),
@@ -410,11 +423,24 @@ pub mod points_update_operation {
 )]
 pub struct SnapshotDownload {
     pub out_path: PathBuf,
+    /// Collection to download a snapshot of, ignored for full snapshots
     pub collection_name: String,
     #[builder(default, setter(strip_option, into))]
     pub snapshot_name: Option<String>,
+    /// REST API URI of the node, defaults to the client URI on port 6333
     #[builder(default, setter(strip_option, into))]
     pub rest_api_uri: Option<String>,
+    /// Download a full snapshot of the node instead of a collection snapshot
+    #[builder(default, setter(custom))]
+    pub full_snapshot: bool,
+    /// Continue a previously interrupted download, defaults to `true`
+    #[builder(default = "true")]
+    pub resume: bool,
+    /// Verify the SHA256 checksum of the snapshot if the server provides one, defaults to `true`
+    #[builder(default = "true")]
+    pub verify_checksum: bool,
+    #[builder(default, setter(custom))]
+    pub progress: Option<SnapshotDownloadProgressFn>,
 }
 
 impl SnapshotDownloadBuilder {
@@ -425,6 +451,22 @@ impl SnapshotDownloadBuilder {
         builder
     }
 
+    /// Download a full snapshot of the node
+    pub fn new_full(out_path: impl Into<PathBuf>) -> Self {
+        let mut builder = Self::new(out_path, String::new());
+        builder.full_snapshot = Some(true);
+        builder
+    }
+
+    /// Call `progress` whenever a chunk of the snapshot was written to disk
+    pub fn progress(
+        mut self,
+        progress: impl Fn(SnapshotDownloadProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Some(Arc::new(progress)));
+        self
+    }
+
     pub fn build(self) -> SnapshotDownload {
         self.build_inner().unwrap()
     }
@@ -435,3 +477,112 @@ impl From<SnapshotDownloadBuilder> for SnapshotDownload {
         value.build()
     }
 }
+
+/// Which data wins when recovering a snapshot into a collection that has replicas
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SnapshotPriority {
+    /// Prefer the data of existing replicas, the server default
+    Replica,
+    /// Prefer the snapshot data over existing replicas
+    Snapshot,
+    /// Restore the snapshot without synchronizing with other replicas
+    NoSync,
+}
+
+impl SnapshotPriority {
+    /// Name of the priority in the REST API
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SnapshotPriority::Replica => "replica",
+            SnapshotPriority::Snapshot => "snapshot",
+            SnapshotPriority::NoSync => "no_sync",
+        }
+    }
+}
+
+#[derive(Builder)]
+#[builder(
+    build_fn(private, name = "build_inner"),
+    pattern = "owned",
+    custom_constructor
+)]
+pub struct SnapshotUpload {
+    /// Local snapshot file to upload
+    pub file_path: PathBuf,
+    /// Collection to recover the snapshot into, created if it does not exist
+    pub collection_name: String,
+    #[builder(default, setter(strip_option))]
+    pub priority: Option<SnapshotPriority>,
+    /// Expected SHA256 checksum, the server rejects the snapshot if it does not match
+    #[builder(default, setter(strip_option, into))]
+    pub checksum: Option<String>,
+    /// Compute the SHA256 checksum of the file before uploading if none is given, defaults to
+    /// `true`
+    #[builder(default = "true")]
+    pub compute_checksum: bool,
+    /// REST API URI of the node, defaults to the client URI on port 6333
+    #[builder(default, setter(strip_option, into))]
+    pub rest_api_uri: Option<String>,
+}
+
+impl SnapshotUploadBuilder {
+    pub fn new(file_path: impl Into<PathBuf>, collection_name: impl Into<String>) -> Self {
+        let mut builder = Self::create_empty();
+        builder.file_path = Some(file_path.into());
+        builder.collection_name = Some(collection_name.into());
+        builder
+    }
+
+    pub fn build(self) -> SnapshotUpload {
+        self.build_inner().unwrap()
+    }
+}
+
+impl From<SnapshotUploadBuilder> for SnapshotUpload {
+    fn from(value: SnapshotUploadBuilder) -> Self {
+        value.build()
+    }
+}
+
+#[derive(Builder)]
+#[builder(
+    build_fn(private, name = "build_inner"),
+    pattern = "owned",
+    custom_constructor
+)]
+pub struct SnapshotRecover {
+    /// Collection to recover the snapshot into, created if it does not exist
+    pub collection_name: String,
+    /// URL of the snapshot, or a `file://` path on the node
+    pub location: String,
+    #[builder(default, setter(strip_option))]
+    pub priority: Option<SnapshotPriority>,
+    /// Expected SHA256 checksum, the server rejects the snapshot if it does not match
+    #[builder(default, setter(strip_option, into))]
+    pub checksum: Option<String>,
+    /// API key the node uses to fetch the snapshot from another Qdrant node
+    #[builder(default, setter(strip_option, into))]
+    pub location_api_key: Option<String>,
+    /// REST API URI of the node, defaults to the client URI on port 6333
+    #[builder(default, setter(strip_option, into))]
+    pub rest_api_uri: Option<String>,
+}
+
+impl SnapshotRecoverBuilder {
+    pub fn new(collection_name: impl Into<String>, location: impl Into<String>) -> Self {
+        let mut builder = Self::create_empty();
+        builder.collection_name = Some(collection_name.into());
+        builder.location = Some(location.into());
+        builder
+    }
+
+    pub fn build(self) -> SnapshotRecover {
+        self.build_inner().unwrap()
+    }
+}
+
+impl From<SnapshotRecoverBuilder> for SnapshotRecover {
+    fn from(value: SnapshotRecoverBuilder) -> Self {
+        value.build()
+    }
+}
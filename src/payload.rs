@@ -5,6 +5,8 @@ use serde::de::IntoDeserializer;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "serde")]
+use crate::qdrant::value::Kind;
 use crate::qdrant::{GeoPoint, Struct, Value};
 #[cfg(feature = "serde")]
 use crate::QdrantError;
@@ -104,6 +106,37 @@ impl Payload {
             Struct { fields: self.0 }.into_deserializer(),
         )?)
     }
+
+    /// Serializes `T` directly into a payload. This requires `T` to implement `serde::Serialize`.
+    /// Returns an error if `T` does not serialize into a struct or map, or contains integers not
+    /// fitting into an `i64`.
+    ///
+    /// ```rust
+    /// use qdrant_client::Payload;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct MyData {
+    ///     value1: String,
+    ///     value2: u32,
+    /// }
+    ///
+    /// let payload = Payload::from_serializable(&MyData {
+    ///     value1: "Qdrant".to_string(),
+    ///     value2: 42,
+    /// })
+    /// .unwrap();
+    ///
+    /// let payload = serde_json::Value::from(payload);
+    /// assert_eq!(payload["value2"], 42);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_serializable<T: Serialize + ?Sized>(value: &T) -> Result<Self, QdrantError> {
+        match crate::serde_ser::to_value(value)?.kind {
+            Some(Kind::StructValue(Struct { fields })) => Ok(Self(fields)),
+            _ => Err(crate::serde_ser::SerPayloadError::NotAnObject.into()),
+        }
+    }
 }
 
 impl From<HashMap<String, Value>> for Payload {
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use crate::json_path::JsonPath;
+use crate::manual_builder::points_update_operation::{DeletePayloadBuilder, SetPayloadBuilder};
+use crate::qdrant::points_selector::PointsSelectorOneOf;
+use crate::qdrant::points_update_operation::Operation;
+use crate::qdrant::value::Kind;
+use crate::qdrant::{PointsUpdateOperation, Struct, Value};
+use crate::Payload;
+
+/// Changes between two payloads, see [`Payload::diff`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PayloadDiff {
+    /// Values to set, grouped by the object they are set in, `None` for the top level
+    set: Vec<(Option<JsonPath>, HashMap<String, Value>)>,
+    /// Paths to delete
+    delete: Vec<JsonPath>,
+}
+
+impl PayloadDiff {
+    /// Whether the payloads are equal
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty() && self.delete.is_empty()
+    }
+
+    /// Update operations applying the changes to the selected points
+    ///
+    /// Deletions come first, followed by one set operation per changed object. Pass them to
+    /// [`update_points_batch`](crate::Qdrant::update_points_batch).
+    pub fn operations(
+        &self,
+        points_selector: impl Into<PointsSelectorOneOf>,
+    ) -> Vec<PointsUpdateOperation> {
+        let points_selector = points_selector.into();
+        let mut operations = Vec::with_capacity(self.set.len() + 1);
+
+        if !self.delete.is_empty() {
+            let keys: Vec<String> = self.delete.iter().map(JsonPath::to_string).collect();
+            let mut delete = DeletePayloadBuilder::new(keys);
+            delete.points_selector(points_selector.clone());
+            operations.push(Operation::from(delete.build()).into());
+        }
+
+        for (key, payload) in &self.set {
+            let mut set = SetPayloadBuilder::new(payload.clone());
+            set.points_selector(points_selector.clone());
+            if let Some(key) = key {
+                set.key(key.to_string());
+            }
+            operations.push(Operation::from(set.build()).into());
+        }
+
+        operations
+    }
+}
+
+/// Collect changes from `old` to `new` in the object at `path`
+fn diff_fields(
+    old: &HashMap<String, Value>,
+    new: &HashMap<String, Value>,
+    path: Option<&JsonPath>,
+    diff: &mut PayloadDiff,
+) {
+    let child = |key: &str| match path {
+        Some(path) => path.key(key),
+        None => JsonPath::new(key),
+    };
+
+    let mut deleted: Vec<&String> = old.keys().filter(|key| !new.contains_key(*key)).collect();
+    deleted.sort();
+    diff.delete
+        .extend(deleted.into_iter().map(|key| child(key)));
+
+    let mut changed = HashMap::new();
+    let mut nested = Vec::new();
+    for (key, new_value) in new {
+        match (
+            old.get(key).and_then(struct_fields),
+            struct_fields(new_value),
+        ) {
+            // Update objects in place to keep unchanged fields
+            (Some(old_fields), Some(new_fields)) => nested.push((key, old_fields, new_fields)),
+            _ if old.get(key) == Some(new_value) => {}
+            _ => {
+                changed.insert(key.clone(), new_value.clone());
+            }
+        }
+    }
+    if !changed.is_empty() {
+        diff.set.push((path.cloned(), changed));
+    }
+
+    nested.sort_by_key(|&(key, ..)| key);
+    for (key, old_fields, new_fields) in nested {
+        diff_fields(old_fields, new_fields, Some(&child(key)), diff);
+    }
+}
+
+fn struct_fields(value: &Value) -> Option<&HashMap<String, Value>> {
+    match &value.kind {
+        Some(Kind::StructValue(Struct { fields })) => Some(fields),
+        _ => None,
+    }
+}
+
+/// Payload diff and merge
+impl Payload {
+    /// Changes turning the `old` payload into the `new` one
+    ///
+    /// Changed fields within objects are set using a nested key, leaving unchanged fields alone.
+    /// Other changed values, including arrays, are replaced as a whole. Removed fields are
+    /// deleted.
+    ///
+    /// ```rust
+    /// use qdrant_client::qdrant::{PointId, UpdateBatchPointsBuilder};
+    /// use qdrant_client::Payload;
+    /// use serde_json::json;
+    ///
+    /// let old = Payload::try_from(json!({"name": "Berlin", "stats": {"population": 3.6, "area": 891}})).unwrap();
+    /// let new = Payload::try_from(json!({"stats": {"population": 3.7, "area": 891}})).unwrap();
+    ///
+    /// let diff = Payload::diff(&old, &new);
+    /// // Deletes `name`, sets `population` in `stats`
+    /// let operations = diff.operations(vec![PointId::from(1)]);
+    /// assert_eq!(operations.len(), 2);
+    ///
+    /// let request = UpdateBatchPointsBuilder::new("cities", operations);
+    ///
+    /// let mut merged = old.clone();
+    /// merged.merge(&diff);
+    /// assert_eq!(merged, new);
+    /// ```
+    pub fn diff(old: &Payload, new: &Payload) -> PayloadDiff {
+        let mut diff = PayloadDiff::default();
+        diff_fields(&old.0, &new.0, None, &mut diff);
+        diff
+    }
+
+    /// Apply changes from [`Payload::diff`] to this payload
+    ///
+    /// Uses the same semantics as the update operations of the diff: fields are deleted first,
+    /// then values are set, creating or replacing objects along nested keys.
+    pub fn merge(&mut self, diff: &PayloadDiff) {
+        for path in &diff.delete {
+            self.remove_json_path(path);
+        }
+        for (path, payload) in &diff.set {
+            for (key, value) in payload {
+                let path = match path {
+                    Some(path) => path.key(key.clone()),
+                    None => JsonPath::new(key.clone()),
+                };
+                self.set_json_path(&path, value);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::qdrant::points_update_operation::{DeletePayload, SetPayload};
+    use crate::qdrant::PointId;
+
+    fn payload(value: serde_json::Value) -> Payload {
+        Payload::try_from(value).unwrap()
+    }
+
+    fn assert_round_trip(old: &Payload, new: &Payload) -> PayloadDiff {
+        let diff = Payload::diff(old, new);
+        let mut merged = old.clone();
+        merged.merge(&diff);
+        assert_eq!(&merged, new);
+        diff
+    }
+
+    #[test]
+    fn diff_equal_payloads() {
+        let old = payload(json!({"a": 1, "b": {"c": [1, 2]}}));
+        let diff = assert_round_trip(&old, &old.clone());
+        assert!(diff.is_empty());
+        assert!(diff.operations(vec![PointId::from(1)]).is_empty());
+    }
+
+    #[test]
+    fn diff_operations() {
+        let old = payload(json!({
+            "name": "Berlin",
+            "gone": true,
+            "tags": ["a", "b"],
+            "stats": {"population": 3.6, "area": 891, "old": 1, "deep": {"x": 1}},
+            "d.e": {"f": 1},
+            "scalar": 5,
+        }));
+        let new = payload(json!({
+            "name": "Berlin",
+            "tags": ["a", "c"],
+            "stats": {"population": 3.7, "area": 891, "deep": {"x": 2}},
+            "d.e": {"f": 2},
+            "scalar": {"now": "object"},
+            "added": null,
+        }));
+
+        let diff = assert_round_trip(&old, &new);
+        let operations: Vec<Operation> = diff
+            .operations(vec![PointId::from(7)])
+            .into_iter()
+            .map(|operation| operation.operation.unwrap())
+            .collect();
+
+        let Operation::DeletePayload(DeletePayload { keys, .. }) = &operations[0] else {
+            panic!("expected delete first");
+        };
+        assert_eq!(keys, &["gone", "stats.old"]);
+
+        let sets: Vec<(Option<String>, Payload)> = operations[1..]
+            .iter()
+            .map(|operation| match operation {
+                Operation::SetPayload(SetPayload {
+                    key,
+                    payload,
+                    points_selector,
+                    ..
+                }) => {
+                    assert!(points_selector.is_some());
+                    (key.clone(), Payload::from(payload.clone()))
+                }
+                _ => panic!("expected set"),
+            })
+            .collect();
+        assert_eq!(
+            sets,
+            vec![
+                (
+                    None,
+                    payload(json!({
+                        "tags": ["a", "c"],
+                        "scalar": {"now": "object"},
+                        "added": null,
+                    })),
+                ),
+                (Some(r#""d.e""#.to_string()), payload(json!({"f": 2}))),
+                (
+                    Some("stats".to_string()),
+                    payload(json!({"population": 3.7}))
+                ),
+                (Some("stats.deep".to_string()), payload(json!({"x": 2}))),
+            ],
+        );
+    }
+
+    #[test]
+    fn diff_quotes_keys() {
+        let old = payload(json!({"first name": {"user@id": 1, "old": true}, "last name": "A"}));
+        let new = payload(json!({"first name": {"user@id": 2}}));
+
+        let diff = assert_round_trip(&old, &new);
+        let operations: Vec<Operation> = diff
+            .operations(vec![PointId::from(7)])
+            .into_iter()
+            .map(|operation| operation.operation.unwrap())
+            .collect();
+
+        let Operation::DeletePayload(DeletePayload { keys, .. }) = &operations[0] else {
+            panic!("expected delete first");
+        };
+        assert_eq!(keys, &[r#""last name""#, r#""first name".old"#]);
+        let Operation::SetPayload(SetPayload { key, payload, .. }) = &operations[1] else {
+            panic!("expected set");
+        };
+        assert_eq!(key.as_deref(), Some(r#""first name""#));
+        assert_eq!(payload.keys().collect::<Vec<_>>(), ["user@id"]);
+    }
+
+    #[test]
+    fn merge_emptied_objects() {
+        let old = payload(json!({"a": {"b": 1, "c": 2}, "e": {}}));
+        let new = payload(json!({"a": {}, "e": {"f": [1]}}));
+        let diff = assert_round_trip(&old, &new);
+        assert_eq!(diff.delete.len(), 2);
+    }
+}
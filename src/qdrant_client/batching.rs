@@ -0,0 +1,599 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::channel::oneshot;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use parking_lot::Mutex;
+use prost::Message;
+use tonic::Status;
+
+use crate::qdrant::{
+    BatchResult, GetPoints, GetResponse, PointId, QueryBatchPoints, QueryPoints, QueryResponse,
+    RetrievedPoint,
+};
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+/// Request batching configuration
+///
+/// ```rust
+/// use std::time::Duration;
+/// use qdrant_client::batching::BatchingConfig;
+///
+/// let config = BatchingConfig::default()
+///     .max_batch_size(32)
+///     .max_wait(Duration::from_millis(5));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BatchingConfig {
+    /// Maximum number of requests to send in a single batch
+    ///
+    /// A batch is sent immediately once it reaches this size.
+    pub max_batch_size: usize,
+
+    /// Maximum time to wait for more requests after the first request of a batch arrived
+    pub max_wait: Duration,
+}
+
+impl BatchingConfig {
+    /// Set the maximum number of requests to send in a single batch
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    /// Set the maximum time to wait for more requests before a batch is sent
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+}
+
+impl Default for BatchingConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 64,
+            max_wait: Duration::from_millis(2),
+        }
+    }
+}
+
+/// Batches concurrent requests into single API calls
+///
+/// Requests arriving within a small time window are gathered and sent together, after which the
+/// results are handed back to the individual callers. Only requests that can be answered by one
+/// API call are batched together:
+///
+/// - [`query`](Self::query) requests are grouped by collection, read consistency, shard key and
+///   timeout, and sent as a single [`query_batch`](Qdrant::query_batch)
+/// - [`get_points`](Self::get_points) requests are additionally grouped by their payload and
+///   vector selectors, and sent as a single [`get_points`](Qdrant::get_points) for all IDs
+///
+/// Create a batcher with [`Qdrant::batcher`]. It is cheap to clone, all clones share the same
+/// batches. Requests must be made from within a Tokio runtime.
+#[derive(Clone)]
+pub struct RequestBatcher {
+    query: Arc<Coalescer<QueryPoints, QueryResponse>>,
+    get_points: Arc<Coalescer<GetPoints, GetResponse>>,
+}
+
+/// # Request batching
+///
+/// Automatically batch concurrent requests.
+impl Qdrant {
+    /// Create a batcher that gathers concurrent requests into batches.
+    ///
+    /// Useful when many independent requests are made concurrently, for example by an API server
+    /// handling requests of different users. See [`RequestBatcher`] for details.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use std::time::Duration;
+    /// use qdrant_client::batching::BatchingConfig;
+    /// use qdrant_client::qdrant::QueryPointsBuilder;
+    ///
+    ///# async fn batched(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let batcher = client.batcher(
+    ///     BatchingConfig::default()
+    ///         .max_batch_size(32)
+    ///         .max_wait(Duration::from_millis(5)),
+    /// );
+    ///
+    /// // Concurrent queries are sent as a single batch
+    /// let (first, second) = futures::try_join!(
+    ///     batcher.query(QueryPointsBuilder::new("my_collection").query(vec![0.1, 0.2, 0.3])),
+    ///     batcher.query(QueryPointsBuilder::new("my_collection").query(vec![0.3, 0.2, 0.1])),
+    /// )?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub fn batcher(&self, config: BatchingConfig) -> RequestBatcher {
+        let client = self.clone();
+        let query = Coalescer::new(config.clone(), query_batch_key, move |requests| {
+            let client = client.clone();
+            async move { dispatch_queries(&client, requests).await }.boxed()
+        });
+
+        let client = self.clone();
+        let get_points = Coalescer::new(config, get_points_batch_key, move |requests| {
+            let client = client.clone();
+            async move { dispatch_get_points(&client, requests).await }.boxed()
+        });
+
+        RequestBatcher {
+            query: Arc::new(query),
+            get_points: Arc::new(get_points),
+        }
+    }
+}
+
+impl RequestBatcher {
+    /// Query points in a collection, batched with other concurrent queries.
+    ///
+    /// Behaves like [`Qdrant::query`]. The response contains the time of the whole batch, usage
+    /// is not reported because it cannot be attributed to individual queries.
+    pub async fn query(&self, request: impl Into<QueryPoints>) -> QdrantResult<QueryResponse> {
+        self.query.submit(request.into()).await
+    }
+
+    /// Retrieve specific points from a collection, batched with other concurrent retrievals.
+    ///
+    /// Behaves like [`Qdrant::get_points`]. The response contains the time of the whole batch,
+    /// usage is not reported because it cannot be attributed to individual requests.
+    pub async fn get_points(&self, request: impl Into<GetPoints>) -> QdrantResult<GetResponse> {
+        self.get_points.submit(request.into()).await
+    }
+}
+
+/// Requests sharing a key can be sent in the same batch
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+struct BatchKey {
+    collection_name: String,
+    timeout: Option<u64>,
+    /// Encoded request options that must be equal within a batch
+    options: Vec<Option<Vec<u8>>>,
+}
+
+fn encode_option(message: &Option<impl Message>) -> Option<Vec<u8>> {
+    message.as_ref().map(Message::encode_to_vec)
+}
+
+fn query_batch_key(request: &QueryPoints) -> BatchKey {
+    BatchKey {
+        collection_name: request.collection_name.clone(),
+        timeout: request.timeout,
+        options: vec![
+            encode_option(&request.read_consistency),
+            encode_option(&request.shard_key_selector),
+        ],
+    }
+}
+
+fn get_points_batch_key(request: &GetPoints) -> BatchKey {
+    BatchKey {
+        collection_name: request.collection_name.clone(),
+        timeout: request.timeout,
+        options: vec![
+            encode_option(&request.read_consistency),
+            encode_option(&request.shard_key_selector),
+            encode_option(&request.with_payload),
+            encode_option(&request.with_vectors),
+        ],
+    }
+}
+
+async fn dispatch_queries(
+    client: &Qdrant,
+    requests: Vec<QueryPoints>,
+) -> QdrantResult<Vec<QueryResponse>> {
+    let count = requests.len();
+    let first = &requests[0];
+    let batch = QueryBatchPoints {
+        collection_name: first.collection_name.clone(),
+        read_consistency: first.read_consistency,
+        timeout: first.timeout,
+        query_points: requests,
+    };
+
+    let response = client.query_batch(batch).await?;
+    if response.result.len() != count {
+        return Err(QdrantError::ConversionError(format!(
+            "expected {count} query results in batch, got {}",
+            response.result.len(),
+        )));
+    }
+
+    let time = response.time;
+    Ok(response
+        .result
+        .into_iter()
+        .map(|BatchResult { result }| QueryResponse {
+            result,
+            time,
+            usage: None,
+        })
+        .collect())
+}
+
+async fn dispatch_get_points(
+    client: &Qdrant,
+    requests: Vec<GetPoints>,
+) -> QdrantResult<Vec<GetResponse>> {
+    // Retrieve the union of all IDs once, the server returns UUIDs normalized
+    let mut ids = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for id in requests.iter().flat_map(|request| &request.ids) {
+        let id = id.normalized();
+        if seen.insert(id.clone()) {
+            ids.push(id);
+        }
+    }
+
+    let first = &requests[0];
+    let batch = GetPoints {
+        collection_name: first.collection_name.clone(),
+        ids,
+        with_payload: first.with_payload.clone(),
+        with_vectors: first.with_vectors.clone(),
+        read_consistency: first.read_consistency,
+        shard_key_selector: first.shard_key_selector.clone(),
+        timeout: first.timeout,
+    };
+
+    let response = client.get_points(batch).await?;
+    Ok(split_retrieved_points(
+        &requests,
+        response.result,
+        response.time,
+    ))
+}
+
+/// Hand the retrieved points to each request that asked for them, in request order
+fn split_retrieved_points(
+    requests: &[GetPoints],
+    points: Vec<RetrievedPoint>,
+    time: f64,
+) -> Vec<GetResponse> {
+    let points: HashMap<PointId, RetrievedPoint> = points
+        .into_iter()
+        .filter_map(|point| Some((point.id.as_ref()?.normalized(), point)))
+        .collect();
+
+    requests
+        .iter()
+        .map(|request| GetResponse {
+            result: request
+                .ids
+                .iter()
+                .filter_map(|id| points.get(&id.normalized()).cloned())
+                .collect(),
+            time,
+            usage: None,
+        })
+        .collect()
+}
+
+/// Copy an error of a batch for each of its callers
+fn share_error(err: &QdrantError) -> QdrantError {
+    match err {
+        QdrantError::ResponseError { status } => QdrantError::ResponseError {
+            status: status.clone(),
+        },
+        QdrantError::ResourceExhaustedError {
+            status,
+            retry_after_seconds,
+        } => QdrantError::ResourceExhaustedError {
+            status: status.clone(),
+            retry_after_seconds: *retry_after_seconds,
+        },
+        QdrantError::ConversionError(message) => QdrantError::ConversionError(message.clone()),
+        err => QdrantError::ResponseError {
+            status: Status::unknown(err.to_string()),
+        },
+    }
+}
+
+type Dispatch<Req, Resp> =
+    dyn Fn(Vec<Req>) -> BoxFuture<'static, QdrantResult<Vec<Resp>>> + Send + Sync;
+
+type Entry<Req, Resp> = (Req, oneshot::Sender<QdrantResult<Resp>>);
+
+struct Group<Req, Resp> {
+    /// Distinguishes this group from earlier groups with the same key
+    generation: u64,
+    entries: Vec<Entry<Req, Resp>>,
+}
+
+struct State<Req, Resp> {
+    next_generation: u64,
+    groups: HashMap<BatchKey, Group<Req, Resp>>,
+}
+
+/// Gathers requests into groups, and dispatches each group once full or once it waited long enough
+struct Coalescer<Req, Resp> {
+    config: BatchingConfig,
+    key: fn(&Req) -> BatchKey,
+    dispatch: Box<Dispatch<Req, Resp>>,
+    state: Mutex<State<Req, Resp>>,
+}
+
+enum Flush<Req, Resp> {
+    /// The group is full, dispatch it now
+    Now(Vec<Entry<Req, Resp>>),
+    /// A new group was started, dispatch it after waiting
+    After(u64),
+    /// The request joined a pending group
+    Pending,
+}
+
+impl<Req, Resp> Coalescer<Req, Resp>
+where
+    Req: Send + 'static,
+    Resp: Send + 'static,
+{
+    fn new(
+        config: BatchingConfig,
+        key: fn(&Req) -> BatchKey,
+        dispatch: impl Fn(Vec<Req>) -> BoxFuture<'static, QdrantResult<Vec<Resp>>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self {
+            config,
+            key,
+            dispatch: Box::new(dispatch),
+            state: Mutex::new(State {
+                next_generation: 0,
+                groups: HashMap::new(),
+            }),
+        }
+    }
+
+    async fn submit(self: &Arc<Self>, request: Req) -> QdrantResult<Resp> {
+        let key = (self.key)(&request);
+        let (sender, receiver) = oneshot::channel();
+
+        let flush = {
+            let mut state = self.state.lock();
+            let state = &mut *state;
+            let generation = state.next_generation;
+            let group = state.groups.entry(key.clone()).or_insert_with(|| Group {
+                generation,
+                entries: Vec::new(),
+            });
+            if group.generation == generation {
+                state.next_generation += 1;
+            }
+            group.entries.push((request, sender));
+
+            if group.entries.len() >= self.config.max_batch_size {
+                Flush::Now(
+                    state
+                        .groups
+                        .remove(&key)
+                        .map(|group| group.entries)
+                        .unwrap_or_default(),
+                )
+            } else if group.entries.len() == 1 {
+                Flush::After(group.generation)
+            } else {
+                Flush::Pending
+            }
+        };
+
+        match flush {
+            Flush::Now(entries) => {
+                tokio::spawn(self.clone().dispatch(entries));
+            }
+            Flush::After(generation) => {
+                let this = self.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(this.config.max_wait).await;
+                    let entries = {
+                        let mut state = this.state.lock();
+                        match state.groups.get(&key) {
+                            // The group may have been dispatched already because it was full
+                            Some(group) if group.generation == generation => {
+                                state.groups.remove(&key).map(|group| group.entries)
+                            }
+                            _ => None,
+                        }
+                    };
+                    if let Some(entries) = entries {
+                        this.dispatch(entries).await;
+                    }
+                });
+            }
+            Flush::Pending => {}
+        }
+
+        receiver.await.unwrap_or_else(|_| {
+            Err(QdrantError::ResponseError {
+                status: Status::cancelled("batched request was dropped before completion"),
+            })
+        })
+    }
+
+    async fn dispatch(self: Arc<Self>, entries: Vec<Entry<Req, Resp>>) {
+        let (requests, senders): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+        let count = requests.len();
+
+        match (self.dispatch)(requests).await {
+            Ok(responses) if responses.len() == count => {
+                for (sender, response) in senders.into_iter().zip(responses) {
+                    // The caller may have gone away, which is fine
+                    let _ = sender.send(Ok(response));
+                }
+            }
+            Ok(responses) => {
+                let err = QdrantError::ConversionError(format!(
+                    "expected {count} responses in batch, got {}",
+                    responses.len(),
+                ));
+                for sender in senders {
+                    let _ = sender.send(Err(share_error(&err)));
+                }
+            }
+            Err(err) => {
+                for sender in senders {
+                    let _ = sender.send(Err(share_error(&err)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::{GetPointsBuilder, PointId, QueryPointsBuilder};
+
+    type BatchSizes = Arc<Mutex<Vec<usize>>>;
+
+    /// Coalescer answering each query with its limit, recording the size of each batch
+    fn recording_coalescer(
+        config: BatchingConfig,
+    ) -> (Arc<Coalescer<QueryPoints, u64>>, BatchSizes) {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let recorded = batches.clone();
+        let coalescer = Coalescer::new(
+            config,
+            query_batch_key,
+            move |requests: Vec<QueryPoints>| {
+                recorded.lock().push(requests.len());
+                let limits = requests
+                    .iter()
+                    .map(|r| r.limit.unwrap_or_default())
+                    .collect();
+                async move { Ok(limits) }.boxed()
+            },
+        );
+        (Arc::new(coalescer), batches)
+    }
+
+    #[tokio::test]
+    async fn concurrent_queries_are_batched() {
+        let (coalescer, batches) = recording_coalescer(
+            BatchingConfig::default()
+                .max_batch_size(10)
+                .max_wait(Duration::from_millis(20)),
+        );
+
+        let results = futures::future::join_all((1..=3).map(|limit| {
+            coalescer.submit(QueryPointsBuilder::new("collection").limit(limit).build())
+        }))
+        .await;
+
+        let results: Vec<u64> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(results, vec![1, 2, 3]);
+        assert_eq!(*batches.lock(), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn full_batches_are_sent_immediately() {
+        let (coalescer, batches) = recording_coalescer(
+            BatchingConfig::default()
+                .max_batch_size(2)
+                .max_wait(Duration::from_millis(20)),
+        );
+
+        let results = futures::future::join_all((1..=5).map(|limit| {
+            coalescer.submit(QueryPointsBuilder::new("collection").limit(limit).build())
+        }))
+        .await;
+
+        let results: Vec<u64> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(results, vec![1, 2, 3, 4, 5]);
+        assert_eq!(*batches.lock(), vec![2, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn queries_are_grouped_by_collection() {
+        let (coalescer, batches) = recording_coalescer(BatchingConfig::default());
+
+        let (first, second) = futures::join!(
+            coalescer.submit(QueryPointsBuilder::new("first").limit(1).build()),
+            coalescer.submit(QueryPointsBuilder::new("second").limit(2).build()),
+        );
+
+        assert_eq!(first.unwrap(), 1);
+        assert_eq!(second.unwrap(), 2);
+        assert_eq!(*batches.lock(), vec![1, 1]);
+    }
+
+    #[tokio::test]
+    async fn batch_errors_are_shared() {
+        let coalescer = Arc::new(Coalescer::new(
+            BatchingConfig::default(),
+            query_batch_key,
+            |_: Vec<QueryPoints>| {
+                async { Err::<Vec<u64>, _>(Status::unavailable("down").into()) }.boxed()
+            },
+        ));
+
+        let (first, second) = futures::join!(
+            coalescer.submit(QueryPointsBuilder::new("collection").build()),
+            coalescer.submit(QueryPointsBuilder::new("collection").build()),
+        );
+
+        for result in [first, second] {
+            match result {
+                Err(QdrantError::ResponseError { status }) => {
+                    assert_eq!(status.code(), tonic::Code::Unavailable)
+                }
+                other => panic!("unexpected result: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn get_points_key_includes_selectors() {
+        let with_payload = GetPointsBuilder::new("collection", vec![1.into()]).with_payload(true);
+        let without_payload = GetPointsBuilder::new("collection", vec![2.into()]);
+        assert_ne!(
+            get_points_batch_key(&with_payload.build()),
+            get_points_batch_key(&without_payload.build()),
+        );
+    }
+
+    #[test]
+    fn retrieved_points_are_split_per_request() {
+        let point = |id: u64| RetrievedPoint {
+            id: Some(PointId::from(id)),
+            ..Default::default()
+        };
+        let requests = [
+            GetPointsBuilder::new("collection", vec![3.into(), 1.into()]).build(),
+            GetPointsBuilder::new("collection", vec![1.into(), 2.into()]).build(),
+        ];
+
+        // Point 2 does not exist
+        let responses = split_retrieved_points(&requests, vec![point(1), point(3)], 0.5);
+
+        assert_eq!(responses[0].result, vec![point(3), point(1)]);
+        assert_eq!(responses[1].result, vec![point(1)]);
+        assert_eq!(responses[1].time, 0.5);
+    }
+
+    #[test]
+    fn retrieved_points_match_normalized_uuids() {
+        let uuid = "936da01f-9abd-4d9d-80c7-02af85c822a8";
+        let requests = [
+            GetPointsBuilder::new("collection", vec![uuid.to_ascii_uppercase().into()]).build(),
+            GetPointsBuilder::new("collection", vec![uuid.replace('-', "").into()]).build(),
+        ];
+        let point = RetrievedPoint {
+            id: Some(PointId::from(uuid)),
+            ..Default::default()
+        };
+
+        let responses = split_retrieved_points(&requests, vec![point.clone()], 0.5);
+
+        assert_eq!(responses[0].result, vec![point.clone()]);
+        assert_eq!(responses[1].result, vec![point]);
+    }
+}
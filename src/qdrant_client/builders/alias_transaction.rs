@@ -0,0 +1,158 @@
+use crate::qdrant::alias_operations::Action;
+use crate::qdrant::{AliasDescription, ChangeAliases, CreateAlias, DeleteAlias, RenameAlias};
+
+/// Alias operations applied together in a single atomic request
+///
+/// Commit it with [`Qdrant::change_aliases`](crate::Qdrant::change_aliases). Operations are
+/// applied in the order they were added, and either all or none of them take effect.
+///
+/// ```rust
+/// use qdrant_client::qdrant::AliasTransaction;
+///
+/// let transaction = AliasTransaction::new()
+///     .delete_alias("production")
+///     .create_alias("articles_v2", "production")
+///     .rename_alias("staging", "previous");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AliasTransaction {
+    actions: Vec<Action>,
+    timeout: Option<u64>,
+}
+
+impl AliasTransaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create `alias_name` for `collection_name`
+    pub fn create_alias(
+        mut self,
+        collection_name: impl Into<String>,
+        alias_name: impl Into<String>,
+    ) -> Self {
+        self.actions.push(Action::CreateAlias(CreateAlias {
+            collection_name: collection_name.into(),
+            alias_name: alias_name.into(),
+        }));
+        self
+    }
+
+    /// Rename `old_alias_name` to `new_alias_name`
+    pub fn rename_alias(
+        mut self,
+        old_alias_name: impl Into<String>,
+        new_alias_name: impl Into<String>,
+    ) -> Self {
+        self.actions.push(Action::RenameAlias(RenameAlias {
+            old_alias_name: old_alias_name.into(),
+            new_alias_name: new_alias_name.into(),
+        }));
+        self
+    }
+
+    /// Delete `alias_name`
+    pub fn delete_alias(mut self, alias_name: impl Into<String>) -> Self {
+        self.actions.push(Action::DeleteAlias(DeleteAlias {
+            alias_name: alias_name.into(),
+        }));
+        self
+    }
+
+    /// Wait for operation commit timeout in seconds
+    ///
+    /// If timeout is reached, request will return with service error.
+    pub fn timeout(mut self, timeout: u64) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Point `alias_name` to `collection_name`, given the current `aliases`
+    ///
+    /// Deletes the alias first if it exists, as creating an existing alias fails.
+    pub(crate) fn point_alias(
+        self,
+        alias_name: &str,
+        collection_name: &str,
+        aliases: &[AliasDescription],
+    ) -> Self {
+        let exists = aliases.iter().any(|alias| alias.alias_name == alias_name);
+        let transaction = if exists {
+            self.delete_alias(alias_name)
+        } else {
+            self
+        };
+        transaction.create_alias(collection_name, alias_name)
+    }
+
+    /// Whether no operations were added
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    pub fn build(self) -> ChangeAliases {
+        ChangeAliases {
+            actions: self.actions.into_iter().map(Into::into).collect(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+impl From<AliasTransaction> for ChangeAliases {
+    fn from(transaction: AliasTransaction) -> Self {
+        transaction.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::AliasOperations;
+
+    #[test]
+    fn alias_transaction_keeps_order() {
+        let change = AliasTransaction::new()
+            .delete_alias("production")
+            .create_alias("articles_v2", "production")
+            .rename_alias("staging", "previous")
+            .timeout(10)
+            .build();
+        assert_eq!(change.timeout, Some(10));
+        assert_eq!(
+            change.actions,
+            [
+                Action::DeleteAlias(DeleteAlias {
+                    alias_name: "production".to_string(),
+                }),
+                Action::CreateAlias(CreateAlias {
+                    collection_name: "articles_v2".to_string(),
+                    alias_name: "production".to_string(),
+                }),
+                Action::RenameAlias(RenameAlias {
+                    old_alias_name: "staging".to_string(),
+                    new_alias_name: "previous".to_string(),
+                }),
+            ]
+            .map(AliasOperations::from),
+        );
+        assert!(AliasTransaction::new().is_empty());
+    }
+
+    #[test]
+    fn point_alias_deletes_existing_alias() {
+        let aliases = [AliasDescription {
+            alias_name: "production".to_string(),
+            collection_name: "articles_v1".to_string(),
+        }];
+        let transaction = AliasTransaction::new()
+            .point_alias("production", "articles_v2", &aliases)
+            .point_alias("latest", "articles_v2", &aliases);
+        assert_eq!(
+            transaction,
+            AliasTransaction::new()
+                .delete_alias("production")
+                .create_alias("articles_v2", "production")
+                .create_alias("articles_v2", "latest"),
+        );
+    }
+}
@@ -1,3 +1,4 @@
+mod alias_transaction;
 mod query;
 // Keeping this public because 1.10-1.10.1 users may use it
 pub mod sparse_vectors_config;
@@ -9,5 +10,6 @@ mod vectors_config;
 // qdrant.rs due to name collisions.
 
 // Re-exports
+pub use self::alias_transaction::AliasTransaction;
 pub use self::sparse_vectors_config::SparseVectorsConfigBuilder;
 pub use self::vectors_config::VectorsConfigBuilder;
@@ -0,0 +1,357 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use futures::future::try_join_all;
+use serde::{Deserialize, Serialize};
+
+use crate::qdrant::{
+    CollectionClusterInfoResponse, ReplicaState, ShardKey, SnapshotDownloadBuilder,
+};
+use crate::qdrant_client::config::QdrantConfig;
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+/// Snapshot of a collection on every node holding a replica, see [`Qdrant::snapshot_cluster`]
+///
+/// ```rust
+/// use qdrant_client::cluster_snapshot::ClusterSnapshot;
+///
+/// let request = ClusterSnapshot::new("my_collection", "/var/backups/qdrant")
+///     .peer(5531049232473152, "https://node-1.example.com:6334")
+///     .peer(2278839421957733, "https://node-2.example.com:6334");
+/// ```
+#[derive(Clone, Debug)]
+pub struct ClusterSnapshot {
+    collection_name: String,
+    out_dir: PathBuf,
+    peers: HashMap<u64, String>,
+}
+
+impl ClusterSnapshot {
+    /// Snapshot `collection_name`, downloading snapshots into `out_dir`
+    pub fn new(collection_name: impl Into<String>, out_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            collection_name: collection_name.into(),
+            out_dir: out_dir.into(),
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Set the gRPC URL of a peer
+    ///
+    /// Snapshots are downloaded from the REST API on port 6333 of the same host.
+    pub fn peer(mut self, peer_id: u64, url: impl Into<String>) -> Self {
+        self.peers.insert(peer_id, url.into());
+        self
+    }
+
+    /// Set the gRPC URLs of multiple peers
+    pub fn peers(mut self, peers: impl IntoIterator<Item = (u64, impl Into<String>)>) -> Self {
+        self.peers.extend(
+            peers
+                .into_iter()
+                .map(|(peer_id, url)| (peer_id, url.into())),
+        );
+        self
+    }
+}
+
+/// Replica of a shard on a peer
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShardReplica {
+    pub shard_id: u32,
+    pub shard_key: Option<ShardKey>,
+    /// State of the replica when the snapshot was taken
+    #[serde(with = "replica_state")]
+    pub state: ReplicaState,
+}
+
+/// Snapshot taken on a single node
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeSnapshot {
+    pub peer_id: u64,
+    /// gRPC URL of the peer
+    pub url: String,
+    pub snapshot_name: String,
+    /// SHA256 checksum of the snapshot, if reported by the node
+    pub checksum: Option<String>,
+    /// Size of the snapshot in bytes
+    pub size: u64,
+    /// Local path the snapshot was downloaded to
+    pub path: PathBuf,
+    /// Shard replicas contained in the snapshot
+    pub shards: Vec<ShardReplica>,
+}
+
+/// Description of a cluster snapshot, mapping shards to the snapshots containing them
+///
+/// Serialize it next to the snapshot files, for example with `serde_json`, so a restore can
+/// recover each node from its snapshot, or pick a snapshot per shard.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClusterSnapshotManifest {
+    pub collection_name: String,
+    pub shard_count: u64,
+    /// One snapshot per node holding a replica, ordered by peer ID
+    pub nodes: Vec<NodeSnapshot>,
+}
+
+impl ClusterSnapshotManifest {
+    /// Snapshots containing a replica of `shard_id`, active replicas first
+    pub fn snapshots_for_shard(&self, shard_id: u32) -> Vec<(&NodeSnapshot, &ShardReplica)> {
+        let mut snapshots: Vec<_> = self
+            .nodes
+            .iter()
+            .flat_map(|node| {
+                node.shards
+                    .iter()
+                    .filter(move |shard| shard.shard_id == shard_id)
+                    .map(move |shard| (node, shard))
+            })
+            .collect();
+        snapshots.sort_by_key(|(_, shard)| shard.state != ReplicaState::Active);
+        snapshots
+    }
+}
+
+/// Replica states as their names, like `"Active"`
+mod replica_state {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::qdrant::ReplicaState;
+
+    pub fn serialize<S: Serializer>(
+        state: &ReplicaState,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(state.as_str_name())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ReplicaState, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        ReplicaState::from_str_name(&name)
+            .ok_or_else(|| D::Error::custom(format!("unknown replica state {name:?}")))
+    }
+}
+
+/// Shard replicas per peer, from the cluster info of any node
+fn shard_placement(info: &CollectionClusterInfoResponse) -> BTreeMap<u64, Vec<ShardReplica>> {
+    let replica = |shard_id, shard_key: &Option<ShardKey>, state| ShardReplica {
+        shard_id,
+        shard_key: shard_key.clone(),
+        state: ReplicaState::try_from(state).unwrap_or(ReplicaState::Dead),
+    };
+
+    let mut placement: BTreeMap<u64, Vec<ShardReplica>> = BTreeMap::new();
+    for shard in &info.local_shards {
+        placement.entry(info.peer_id).or_default().push(replica(
+            shard.shard_id,
+            &shard.shard_key,
+            shard.state,
+        ));
+    }
+    for shard in &info.remote_shards {
+        placement.entry(shard.peer_id).or_default().push(replica(
+            shard.shard_id,
+            &shard.shard_key,
+            shard.state,
+        ));
+    }
+    for shards in placement.values_mut() {
+        shards.sort_by_key(|shard| shard.shard_id);
+    }
+    placement
+}
+
+/// # Cluster snapshots
+///
+/// Snapshots of a distributed collection across all nodes holding its shards.
+///
+/// Documentation: <https://qdrant.tech/documentation/concepts/snapshots/#snapshots-in-distributed-mode>
+impl Qdrant {
+    /// Snapshot a collection on every node holding a replica, and download the snapshots.
+    ///
+    /// Reads the shard placement from [`collection_cluster_info`](Self::collection_cluster_info),
+    /// then creates a snapshot on each node through a client with this client's configuration
+    /// and the URL given for the peer. All nodes are snapshotted concurrently. Snapshots are
+    /// downloaded to `<out_dir>/<peer_id>/<snapshot_name>` and verified against their checksum.
+    ///
+    /// Fails before creating any snapshot if a peer holding a replica has no URL.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::cluster_snapshot::ClusterSnapshot;
+    ///
+    ///# async fn snapshot_cluster(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let manifest = client
+    ///     .snapshot_cluster(
+    ///         ClusterSnapshot::new("my_collection", "/var/backups/qdrant")
+    ///             .peer(5531049232473152, "https://node-1.example.com:6334")
+    ///             .peer(2278839421957733, "https://node-2.example.com:6334"),
+    ///     )
+    ///     .await?;
+    ///
+    /// let json = serde_json::to_string(&manifest).map_err(std::io::Error::from)?;
+    /// std::fs::write("/var/backups/qdrant/manifest.json", json)?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn snapshot_cluster(
+        &self,
+        request: ClusterSnapshot,
+    ) -> QdrantResult<ClusterSnapshotManifest> {
+        let info = self
+            .collection_cluster_info(request.collection_name.clone())
+            .await?;
+        let placement = shard_placement(&info);
+
+        let mut nodes = Vec::with_capacity(placement.len());
+        for (peer_id, shards) in placement {
+            let url = request.peers.get(&peer_id).ok_or_else(|| {
+                QdrantError::ConversionError(format!("No URL given for peer {peer_id}"))
+            })?;
+            let client = Qdrant::new(QdrantConfig {
+                uri: url.clone(),
+                check_compatibility: false,
+                ..self.config.clone()
+            })?;
+            nodes.push((peer_id, url.clone(), client, shards));
+        }
+
+        let nodes = try_join_all(nodes.into_iter().map(|(peer_id, url, client, shards)| {
+            let collection_name = &request.collection_name;
+            let out_dir = request.out_dir.join(peer_id.to_string());
+            async move {
+                let snapshot = client
+                    .create_snapshot(collection_name)
+                    .await?
+                    .snapshot_description
+                    .ok_or_else(|| {
+                        QdrantError::ConversionError(format!(
+                            "Peer {peer_id} did not describe the created snapshot"
+                        ))
+                    })?;
+
+                tokio::fs::create_dir_all(&out_dir).await?;
+                let path = out_dir.join(&snapshot.name);
+                client
+                    .download_snapshot(
+                        SnapshotDownloadBuilder::new(&path, collection_name)
+                            .snapshot_name(&snapshot.name),
+                    )
+                    .await?;
+
+                Ok::<_, QdrantError>(NodeSnapshot {
+                    peer_id,
+                    url,
+                    snapshot_name: snapshot.name,
+                    checksum: snapshot.checksum,
+                    size: u64::try_from(snapshot.size).unwrap_or_default(),
+                    path,
+                    shards,
+                })
+            }
+        }))
+        .await?;
+
+        Ok(ClusterSnapshotManifest {
+            collection_name: request.collection_name,
+            shard_count: info.shard_count,
+            nodes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::{LocalShardInfo, RemoteShardInfo};
+
+    fn cluster_info() -> CollectionClusterInfoResponse {
+        CollectionClusterInfoResponse {
+            peer_id: 1,
+            shard_count: 2,
+            local_shards: vec![LocalShardInfo {
+                shard_id: 1,
+                points_count: 10,
+                state: ReplicaState::Active as i32,
+                shard_key: Some(ShardKey::from("eu")),
+            }],
+            remote_shards: vec![
+                RemoteShardInfo {
+                    shard_id: 0,
+                    peer_id: 2,
+                    state: ReplicaState::Active as i32,
+                    shard_key: None,
+                },
+                RemoteShardInfo {
+                    shard_id: 1,
+                    peer_id: 2,
+                    state: ReplicaState::Partial as i32,
+                    shard_key: Some(ShardKey::from("eu")),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cluster_shard_placement() {
+        let placement = shard_placement(&cluster_info());
+        assert_eq!(placement.keys().copied().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(placement[&1].len(), 1);
+        assert_eq!(placement[&1][0].shard_key, Some(ShardKey::from("eu")));
+        let shard_ids: Vec<_> = placement[&2].iter().map(|shard| shard.shard_id).collect();
+        assert_eq!(shard_ids, [0, 1]);
+        assert_eq!(placement[&2][1].state, ReplicaState::Partial);
+    }
+
+    #[test]
+    fn cluster_manifest_json() {
+        let manifest = ClusterSnapshotManifest {
+            collection_name: "test".to_string(),
+            shard_count: 2,
+            nodes: shard_placement(&cluster_info())
+                .into_iter()
+                .map(|(peer_id, shards)| NodeSnapshot {
+                    peer_id,
+                    url: format!("http://node-{peer_id}:6334"),
+                    snapshot_name: format!("test-{peer_id}.snapshot"),
+                    checksum: (peer_id == 1).then(|| "abc".to_string()),
+                    size: 1024,
+                    path: PathBuf::from(format!("backups/{peer_id}/test-{peer_id}.snapshot")),
+                    shards,
+                })
+                .collect(),
+        };
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["nodes"][0]["shards"][0]["shard_key"], "eu");
+        assert_eq!(json["nodes"][0]["checksum"], "abc");
+        assert_eq!(json["nodes"][1]["checksum"], serde_json::Value::Null);
+        assert_eq!(
+            json["nodes"][1]["shards"][0]["shard_key"],
+            serde_json::Value::Null
+        );
+        assert_eq!(json["nodes"][1]["shards"][1]["state"], "Partial");
+        assert_eq!(json["nodes"][1]["path"], "backups/2/test-2.snapshot");
+        let parsed: ClusterSnapshotManifest = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(parsed, manifest);
+
+        // Active replicas come first
+        let peers: Vec<_> = manifest
+            .snapshots_for_shard(1)
+            .iter()
+            .map(|(node, _)| node.peer_id)
+            .collect();
+        assert_eq!(peers, [1, 2]);
+        assert!(manifest.snapshots_for_shard(5).is_empty());
+
+        let mut invalid = json;
+        invalid["nodes"][0]["shards"][0]["state"] = "Unknown".into();
+        assert!(serde_json::from_value::<ClusterSnapshotManifest>(invalid).is_err());
+    }
+}
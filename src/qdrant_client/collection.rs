@@ -7,10 +7,10 @@ use tonic::Status;
 use crate::auth::TokenInterceptor;
 use crate::qdrant::collections_client::CollectionsClient;
 use crate::qdrant::{
-    alias_operations, AliasOperations, ChangeAliases, CollectionClusterInfoRequest,
-    CollectionClusterInfoResponse, CollectionExistsRequest, CollectionOperationResponse,
-    CreateAlias, CreateCollection, DeleteAlias, DeleteCollection, GetCollectionInfoRequest,
-    GetCollectionInfoResponse, ListAliasesRequest, ListAliasesResponse,
+    alias_operations, AliasOperations, AliasTransaction, ChangeAliases,
+    CollectionClusterInfoRequest, CollectionClusterInfoResponse, CollectionExistsRequest,
+    CollectionOperationResponse, CreateAlias, CreateCollection, DeleteAlias, DeleteCollection,
+    GetCollectionInfoRequest, GetCollectionInfoResponse, ListAliasesRequest, ListAliasesResponse,
     ListCollectionAliasesRequest, ListCollectionsRequest, ListCollectionsResponse, RenameAlias,
     UpdateCollection, UpdateCollectionClusterSetupRequest, UpdateCollectionClusterSetupResponse,
 };
@@ -318,24 +318,85 @@ impl Qdrant {
     /// - [`create_alias`](Self::create_alias)
     /// - [`rename_alias`](Self::rename_alias)
     /// - [`delete_alias`](Self::delete_alias)
+    /// - [`change_aliases`](Self::change_aliases) to apply multiple operations atomically
     async fn update_aliases(
         &self,
         change_aliases: impl Into<alias_operations::Action> + Clone,
     ) -> QdrantResult<CollectionOperationResponse> {
         let action = change_aliases.into();
-        let change = &ChangeAliases {
+        self.change_aliases(ChangeAliases {
             actions: vec![AliasOperations {
                 action: Some(action),
             }],
             timeout: None,
-        };
+        })
+        .await
+    }
+
+    /// Apply multiple alias operations atomically.
+    ///
+    /// Either all operations take effect or none of them.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::qdrant::AliasTransaction;
+    ///
+    ///# async fn change_aliases(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// client
+    ///     .change_aliases(
+    ///         AliasTransaction::new()
+    ///             .delete_alias("production")
+    ///             .create_alias("articles_v2", "production")
+    ///             .rename_alias("staging", "previous"),
+    ///     )
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    ///
+    /// Documentation: <https://qdrant.tech/documentation/concepts/collections/#collection-aliases>
+    pub async fn change_aliases(
+        &self,
+        request: impl Into<ChangeAliases>,
+    ) -> QdrantResult<CollectionOperationResponse> {
+        let request = &request.into();
         self.with_collections_client(|mut collection_api| async move {
-            let result = collection_api.update_aliases(change.clone()).await?;
+            let result = collection_api.update_aliases(request.clone()).await?;
             Ok(result.into_inner())
         })
         .await
     }
 
+    /// Point an alias to a collection, in a single atomic operation.
+    ///
+    /// Creates the alias if it doesn't exist, otherwise moves it from the collection it points
+    /// to. Queries through the alias never fail during the swap.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    ///# async fn swap_alias(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// client.swap_alias("production", "articles_v2").await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    ///
+    /// Documentation: <https://qdrant.tech/documentation/concepts/collections/#switch-collection>
+    pub async fn swap_alias(
+        &self,
+        alias_name: impl Into<String>,
+        collection_name: impl Into<String>,
+    ) -> QdrantResult<CollectionOperationResponse> {
+        let aliases = self.list_aliases().await?.aliases;
+        let transaction = AliasTransaction::new().point_alias(
+            &alias_name.into(),
+            &collection_name.into(),
+            &aliases,
+        );
+        self.change_aliases(transaction).await
+    }
+
     /// Delete existing collection name alias.
     ///
     /// ```no_run
@@ -432,8 +493,6 @@ impl Qdrant {
 mod tests {
     use std::time::Duration;
 
-    use tokio::time::sleep;
-
     use super::*;
     use crate::builders::CreateCollectionBuilder;
     use crate::payload::Payload;
@@ -441,6 +500,7 @@ mod tests {
         CountPointsBuilder, Distance, PointStruct, SearchPointsBuilder, UpsertPointsBuilder,
         VectorParamsBuilder,
     };
+    use crate::qdrant_client::wait::WaitCondition;
 
     #[tokio::test]
     async fn create_collection_and_do_the_search() -> QdrantResult<()> {
@@ -456,7 +516,13 @@ mod tests {
             .vectors_config(VectorParamsBuilder::new(3, Distance::Cosine));
         let _result = client.create_collection(create_collection).await?;
 
-        sleep(Duration::from_secs(1)).await;
+        client
+            .wait_for_collection(
+                collection_name,
+                [WaitCondition::Green],
+                Duration::from_secs(10),
+            )
+            .await?;
 
         assert!(client.collection_exists(collection_name).await?);
 
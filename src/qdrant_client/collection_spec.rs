@@ -0,0 +1,862 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use prost::Message;
+
+use crate::qdrant::quantization_config::Quantization;
+use crate::qdrant::{
+    quantization_config_diff, vectors_config, vectors_config_diff, AliasDescription,
+    AliasTransaction, CollectionInfo, CollectionParamsDiff, CreateCollection,
+    CreateFieldIndexCollection, Datatype, DeleteFieldIndexCollectionBuilder, FieldType,
+    PayloadSchemaType, QuantizationConfig, QuantizationConfigDiff, UpdateCollection, VectorParams,
+    VectorParamsDiff, VectorParamsDiffMap, VectorsConfig, VectorsConfigDiff,
+};
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+/// Desired state of a collection, see [`Qdrant::ensure_collection`]
+///
+/// Settings left unset in the [`CreateCollection`] request are not reconciled, so an existing
+/// collection only has to match what the spec states.
+///
+/// ```rust
+/// use qdrant_client::collection_spec::CollectionSpec;
+/// use qdrant_client::qdrant::{
+///     CreateCollectionBuilder, CreateFieldIndexCollectionBuilder, Distance, FieldType,
+///     HnswConfigDiffBuilder, VectorParamsBuilder,
+/// };
+///
+/// let spec = CollectionSpec::new(
+///     CreateCollectionBuilder::new("articles")
+///         .vectors_config(VectorParamsBuilder::new(384, Distance::Cosine))
+///         .hnsw_config(HnswConfigDiffBuilder::default().m(32)),
+/// )
+/// .index(CreateFieldIndexCollectionBuilder::new("", "author", FieldType::Keyword))
+/// .alias("articles_current")
+/// .drop_stale_indexes(true);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CollectionSpec {
+    create: CreateCollection,
+    indexes: Vec<CreateFieldIndexCollection>,
+    aliases: Vec<String>,
+    drop_stale_indexes: bool,
+}
+
+impl CollectionSpec {
+    pub fn new(create: impl Into<CreateCollection>) -> Self {
+        Self {
+            create: create.into(),
+            indexes: Vec::new(),
+            aliases: Vec::new(),
+            drop_stale_indexes: false,
+        }
+    }
+
+    /// Add a payload index, its collection name is set to the collection of the spec
+    pub fn index(mut self, index: impl Into<CreateFieldIndexCollection>) -> Self {
+        let mut index = index.into();
+        index.collection_name = self.create.collection_name.clone();
+        index.wait = Some(true);
+        self.indexes.push(index);
+        self
+    }
+
+    /// Add an alias that should point to the collection
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Delete payload indexes that are not part of the spec, defaults to `false`
+    pub fn drop_stale_indexes(mut self, drop_stale_indexes: bool) -> Self {
+        self.drop_stale_indexes = drop_stale_indexes;
+        self
+    }
+
+    pub fn collection_name(&self) -> &str {
+        &self.create.collection_name
+    }
+
+    pub(crate) fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// The spec without its aliases, to set them separately
+    pub(crate) fn without_aliases(&self) -> Self {
+        Self {
+            aliases: Vec::new(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Change needed to bring a collection to its spec
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlanAction {
+    CreateCollection(CreateCollection),
+    /// Update mutable settings, `changed` names the settings that differ
+    UpdateCollection {
+        update: UpdateCollection,
+        changed: Vec<String>,
+    },
+    CreateIndex(CreateFieldIndexCollection),
+    /// Delete the index of a payload field
+    DeleteIndex(String),
+    /// Point an alias to the collection, moving it from the collection in `from` if set
+    SetAlias {
+        alias: String,
+        from: Option<String>,
+    },
+}
+
+/// Setting of an existing collection that differs from the spec and can't be changed
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpecConflict {
+    /// Path of the setting, like `vectors_config.text.size`
+    pub setting: String,
+    /// Value required by the spec
+    pub expected: String,
+    /// Value of the existing collection
+    pub actual: String,
+}
+
+/// Changes needed to bring a collection to its spec, see [`Qdrant::plan_collection`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CollectionPlan {
+    pub collection_name: String,
+    /// Changes in the order they are applied
+    pub actions: Vec<PlanAction>,
+    /// Differences that can't be applied, recreate the collection to resolve them
+    pub conflicts: Vec<SpecConflict>,
+}
+
+impl CollectionPlan {
+    /// Whether the collection already matches its spec
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty() && self.conflicts.is_empty()
+    }
+}
+
+impl fmt::Display for PlanAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanAction::CreateCollection(create) => {
+                write!(f, "create collection `{}`", create.collection_name)
+            }
+            PlanAction::UpdateCollection { update, changed } => write!(
+                f,
+                "update collection `{}`: {}",
+                update.collection_name,
+                changed.join(", "),
+            ),
+            PlanAction::CreateIndex(index) => write!(f, "create index on `{}`", index.field_name),
+            PlanAction::DeleteIndex(field_name) => write!(f, "delete index on `{field_name}`"),
+            PlanAction::SetAlias { alias, from: None } => write!(f, "create alias `{alias}`"),
+            PlanAction::SetAlias {
+                alias,
+                from: Some(from),
+            } => write!(f, "move alias `{alias}` from `{from}`"),
+        }
+    }
+}
+
+impl fmt::Display for SpecConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is {}, spec requires {}",
+            self.setting, self.actual, self.expected,
+        )
+    }
+}
+
+impl fmt::Display for CollectionPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "collection `{}`", self.collection_name)?;
+        if self.is_empty() {
+            return write!(f, " matches its spec");
+        }
+        for action in &self.actions {
+            write!(f, "\n- {action}")?;
+        }
+        for conflict in &self.conflicts {
+            write!(f, "\n- conflict: {conflict}")?;
+        }
+        Ok(())
+    }
+}
+
+/// # Declarative collections
+///
+/// Create collections from a spec, or reconcile existing ones with it.
+impl Qdrant {
+    /// Create a collection from its spec, or update it to match.
+    ///
+    /// Mutable settings are changed with a single [`update_collection`](Self::update_collection),
+    /// missing payload indexes are created, indexes of another type are recreated, and aliases
+    /// are created or moved to the collection. Nothing is applied if an immutable setting, like
+    /// vector size or distance, differs; the plan is returned in
+    /// [`QdrantError::CollectionSpecConflict`] instead.
+    ///
+    /// Payload indexes are compared by type only. Returns the applied plan.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::collection_spec::CollectionSpec;
+    /// use qdrant_client::qdrant::{
+    ///     CreateCollectionBuilder, CreateFieldIndexCollectionBuilder, Distance, FieldType,
+    ///     VectorParamsBuilder,
+    /// };
+    ///
+    ///# async fn ensure_collection(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let spec = CollectionSpec::new(
+    ///     CreateCollectionBuilder::new("articles")
+    ///         .vectors_config(VectorParamsBuilder::new(384, Distance::Cosine)),
+    /// )
+    /// .index(CreateFieldIndexCollectionBuilder::new("", "author", FieldType::Keyword));
+    ///
+    /// let plan = client.ensure_collection(&spec).await?;
+    /// println!("{plan}");
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn ensure_collection(&self, spec: &CollectionSpec) -> QdrantResult<CollectionPlan> {
+        let plan = self.plan_collection(spec).await?;
+        if !plan.conflicts.is_empty() {
+            return Err(QdrantError::CollectionSpecConflict(Box::new(plan)));
+        }
+
+        let collection_name = &plan.collection_name;
+        for action in &plan.actions {
+            match action {
+                PlanAction::CreateCollection(create) => {
+                    self.create_collection(create.clone()).await?;
+                }
+                PlanAction::UpdateCollection { update, .. } => {
+                    self.update_collection(update.clone()).await?;
+                }
+                PlanAction::CreateIndex(index) => {
+                    self.create_field_index(index.clone()).await?;
+                }
+                PlanAction::DeleteIndex(field_name) => {
+                    self.delete_field_index(
+                        DeleteFieldIndexCollectionBuilder::new(collection_name, field_name)
+                            .wait(true),
+                    )
+                    .await?;
+                }
+                PlanAction::SetAlias { alias, from } => {
+                    let mut transaction = AliasTransaction::new();
+                    if from.is_some() {
+                        transaction = transaction.delete_alias(alias);
+                    }
+                    self.change_aliases(transaction.create_alias(collection_name, alias))
+                        .await?;
+                }
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Plan the changes [`ensure_collection`](Self::ensure_collection) would apply, without
+    /// applying them.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    ///# use qdrant_client::collection_spec::CollectionSpec;
+    ///# async fn plan_collection(client: &Qdrant, spec: &CollectionSpec)
+    ///# -> Result<(), QdrantError> {
+    /// let plan = client.plan_collection(spec).await?;
+    /// for conflict in &plan.conflicts {
+    ///     eprintln!("cannot apply: {conflict}");
+    /// }
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn plan_collection(&self, spec: &CollectionSpec) -> QdrantResult<CollectionPlan> {
+        let aliases = if spec.aliases.is_empty() {
+            Vec::new()
+        } else {
+            self.list_aliases().await?.aliases
+        };
+
+        let collection_name = spec.collection_name();
+        if !self.collection_exists(collection_name).await? {
+            return Ok(spec.plan_create(&aliases));
+        }
+        let info = self
+            .collection_info(collection_name)
+            .await?
+            .result
+            .ok_or_else(|| {
+                QdrantError::ConversionError(format!(
+                    "No info returned for collection {collection_name}"
+                ))
+            })?;
+        Ok(spec.plan_update(&info, &aliases))
+    }
+}
+
+impl CollectionSpec {
+    fn plan_create(&self, aliases: &[AliasDescription]) -> CollectionPlan {
+        let mut actions = vec![PlanAction::CreateCollection(self.create.clone())];
+        actions.extend(self.indexes.iter().cloned().map(PlanAction::CreateIndex));
+        actions.extend(self.alias_actions(aliases));
+        CollectionPlan {
+            collection_name: self.create.collection_name.clone(),
+            actions,
+            conflicts: Vec::new(),
+        }
+    }
+
+    fn plan_update(&self, info: &CollectionInfo, aliases: &[AliasDescription]) -> CollectionPlan {
+        let mut diff = Diff::default();
+        let mut update = UpdateCollection {
+            collection_name: self.create.collection_name.clone(),
+            ..Default::default()
+        };
+        self.diff_collection(info, &mut update, &mut diff);
+
+        let mut actions = Vec::new();
+        if !diff.changed.is_empty() {
+            actions.push(PlanAction::UpdateCollection {
+                update,
+                changed: diff.changed,
+            });
+        }
+        actions.extend(self.index_actions(info));
+        actions.extend(self.alias_actions(aliases));
+        CollectionPlan {
+            collection_name: self.create.collection_name.clone(),
+            actions,
+            conflicts: diff.conflicts,
+        }
+    }
+
+    fn diff_collection(
+        &self,
+        info: &CollectionInfo,
+        update: &mut UpdateCollection,
+        diff: &mut Diff,
+    ) {
+        let spec = &self.create;
+        let config = info.config.clone().unwrap_or_default();
+        let params = config.params.clone().unwrap_or_default();
+
+        if let Some(shard_number) = spec.shard_number {
+            diff.immutable("shard_number", shard_number, params.shard_number);
+        }
+        if let Some(sharding_method) = spec.sharding_method {
+            diff.immutable(
+                "sharding_method",
+                sharding_method,
+                params.sharding_method.unwrap_or_default(),
+            );
+        }
+        if let Some(wal_config) = &spec.wal_config {
+            let current = config.wal_config.unwrap_or_default();
+            let merged = overlay(&current, wal_config);
+            if merged != current {
+                diff.conflict("wal_config", format!("{merged:?}"), format!("{current:?}"));
+            }
+        }
+
+        update.hnsw_config = diff.overlay("hnsw_config", &config.hnsw_config, &spec.hnsw_config);
+        update.optimizers_config = diff.overlay(
+            "optimizers_config",
+            &config.optimizer_config,
+            &spec.optimizers_config,
+        );
+        update.strict_mode_config = diff.overlay(
+            "strict_mode_config",
+            &config.strict_mode_config,
+            &spec.strict_mode_config,
+        );
+        update.quantization_config = diff
+            .overlay(
+                "quantization_config",
+                &config.quantization_config,
+                &spec.quantization_config,
+            )
+            .and_then(quantization_diff);
+
+        let params_diff = CollectionParamsDiff {
+            replication_factor: spec
+                .replication_factor
+                .filter(|&factor| Some(factor) != params.replication_factor),
+            write_consistency_factor: spec
+                .write_consistency_factor
+                .filter(|&factor| Some(factor) != params.write_consistency_factor),
+            on_disk_payload: spec
+                .on_disk_payload
+                .filter(|&on_disk| on_disk != params.on_disk_payload),
+            ..Default::default()
+        };
+        if params_diff != CollectionParamsDiff::default() {
+            update.params = Some(params_diff);
+            diff.changed.push("params".to_string());
+        }
+
+        if spec
+            .metadata
+            .iter()
+            .any(|(key, value)| config.metadata.get(key) != Some(value))
+        {
+            update.metadata = spec.metadata.clone();
+            diff.changed.push("metadata".to_string());
+        }
+
+        if let Some(vectors_config) = &spec.vectors_config {
+            update.vectors_config =
+                diff_vectors(vectors_config, params.vectors_config.as_ref(), diff);
+        }
+
+        if let Some(sparse) = &spec.sparse_vectors_config {
+            let current = params.sparse_vectors_config.unwrap_or_default().map;
+            let mut changed = HashMap::new();
+            for (name, spec_params) in &sparse.map {
+                let Some(current) = current.get(name) else {
+                    diff.conflict(
+                        format!("sparse_vectors_config.{name}"),
+                        "present",
+                        "missing",
+                    );
+                    continue;
+                };
+                if overlay(current, spec_params) != *current {
+                    diff.changed.push(format!("sparse_vectors_config.{name}"));
+                    changed.insert(name.clone(), *spec_params);
+                }
+            }
+            if !changed.is_empty() {
+                update.sparse_vectors_config = Some(changed.into());
+            }
+        }
+    }
+
+    fn index_actions(&self, info: &CollectionInfo) -> Vec<PlanAction> {
+        let mut actions = Vec::new();
+        for index in &self.indexes {
+            let expected = index.field_type.and_then(schema_type);
+            match info.payload_schema.get(&index.field_name) {
+                None => actions.push(PlanAction::CreateIndex(index.clone())),
+                Some(current) if expected.is_some_and(|expected| expected != current.data_type) => {
+                    actions.push(PlanAction::DeleteIndex(index.field_name.clone()));
+                    actions.push(PlanAction::CreateIndex(index.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+
+        if self.drop_stale_indexes {
+            let mut stale: Vec<_> = info
+                .payload_schema
+                .keys()
+                .filter(|field| !self.indexes.iter().any(|index| &index.field_name == *field))
+                .collect();
+            stale.sort();
+            actions.extend(
+                stale
+                    .into_iter()
+                    .map(|field| PlanAction::DeleteIndex(field.clone())),
+            );
+        }
+        actions
+    }
+
+    fn alias_actions(&self, aliases: &[AliasDescription]) -> Vec<PlanAction> {
+        self.aliases
+            .iter()
+            .filter_map(|alias| {
+                let current = aliases
+                    .iter()
+                    .find(|description| &description.alias_name == alias);
+                match current {
+                    Some(current) if current.collection_name == self.create.collection_name => None,
+                    current => Some(PlanAction::SetAlias {
+                        alias: alias.clone(),
+                        from: current.map(|current| current.collection_name.clone()),
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Changed settings and conflicts found while comparing a collection with its spec
+#[derive(Default)]
+struct Diff {
+    changed: Vec<String>,
+    conflicts: Vec<SpecConflict>,
+}
+
+impl Diff {
+    fn conflict(
+        &mut self,
+        setting: impl Into<String>,
+        expected: impl ToString,
+        actual: impl ToString,
+    ) {
+        self.conflicts.push(SpecConflict {
+            setting: setting.into(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        });
+    }
+
+    fn immutable<T: PartialEq + fmt::Debug>(&mut self, setting: &str, expected: T, actual: T) {
+        if expected != actual {
+            self.conflict(setting, format!("{expected:?}"), format!("{actual:?}"));
+        }
+    }
+
+    /// The spec if applying it changes the current settings
+    fn overlay<M: Message + Default + Clone + PartialEq>(
+        &mut self,
+        setting: &str,
+        current: &Option<M>,
+        spec: &Option<M>,
+    ) -> Option<M> {
+        let spec = spec.as_ref()?;
+        let current = current.clone().unwrap_or_default();
+        if overlay(&current, spec) == current {
+            return None;
+        }
+        self.changed.push(setting.to_string());
+        Some(spec.clone())
+    }
+}
+
+/// Settings with the fields set in `spec` applied on top of `current`
+fn overlay<M: Message + Default + Clone>(current: &M, spec: &M) -> M {
+    let mut merged = current.clone();
+    // Merging the encoded spec overwrites exactly the fields it sets
+    merged
+        .merge(spec.encode_to_vec().as_slice())
+        .expect("re-encoded message is valid");
+    merged
+}
+
+/// Vector params by name, the unnamed vector having an empty name
+fn vector_params(config: &VectorsConfig) -> (bool, BTreeMap<String, VectorParams>) {
+    match &config.config {
+        Some(vectors_config::Config::Params(params)) => {
+            (false, BTreeMap::from([(String::new(), *params)]))
+        }
+        Some(vectors_config::Config::ParamsMap(map)) => (
+            true,
+            map.map
+                .iter()
+                .map(|(name, params)| (name.clone(), *params))
+                .collect(),
+        ),
+        None => (true, BTreeMap::new()),
+    }
+}
+
+fn diff_vectors(
+    spec: &VectorsConfig,
+    current: Option<&VectorsConfig>,
+    diff: &mut Diff,
+) -> Option<VectorsConfigDiff> {
+    let (named, spec) = vector_params(spec);
+    let (current_named, current) = current.map(vector_params).unwrap_or_default();
+    if named != current_named {
+        let kind = |named| if named { "named" } else { "unnamed" };
+        diff.conflict("vectors_config", kind(named), kind(current_named));
+        return None;
+    }
+
+    // The unnamed vector has an empty name, and the vector itself an empty field
+    let setting = |name: &str, field: &str| {
+        ["vectors_config", name, field]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(".")
+    };
+    for name in current.keys().filter(|name| !spec.contains_key(*name)) {
+        diff.conflict(setting(name, ""), "missing", "present");
+    }
+
+    let mut diffs = HashMap::new();
+    for (name, spec) in &spec {
+        let Some(current) = current.get(name) else {
+            diff.conflict(setting(name, ""), "present", "missing");
+            continue;
+        };
+
+        diff.immutable(&setting(name, "size"), spec.size, current.size);
+        diff.immutable(&setting(name, "distance"), spec.distance, current.distance);
+        if let Some(datatype) = spec.datatype {
+            // Float32 is the default datatype
+            let normalize = |datatype| {
+                if datatype == Datatype::Default as i32 {
+                    Datatype::Float32 as i32
+                } else {
+                    datatype
+                }
+            };
+            diff.immutable(
+                &setting(name, "datatype"),
+                normalize(datatype),
+                normalize(current.datatype.unwrap_or_default()),
+            );
+        }
+        if spec.multivector_config.is_some() {
+            diff.immutable(
+                &setting(name, "multivector_config"),
+                spec.multivector_config,
+                current.multivector_config,
+            );
+        }
+
+        let vector_diff = VectorParamsDiff {
+            hnsw_config: diff.overlay(
+                &setting(name, "hnsw_config"),
+                &current.hnsw_config,
+                &spec.hnsw_config,
+            ),
+            quantization_config: diff
+                .overlay(
+                    &setting(name, "quantization_config"),
+                    &current.quantization_config,
+                    &spec.quantization_config,
+                )
+                .and_then(quantization_diff),
+            on_disk: spec
+                .on_disk
+                .filter(|&on_disk| on_disk != current.on_disk.unwrap_or_default()),
+        };
+        if vector_diff.on_disk.is_some() {
+            diff.changed.push(setting(name, "on_disk"));
+        }
+        if vector_diff != VectorParamsDiff::default() {
+            diffs.insert(name.clone(), vector_diff);
+        }
+    }
+
+    if diffs.is_empty() {
+        return None;
+    }
+    let config = if named {
+        vectors_config_diff::Config::ParamsMap(VectorParamsDiffMap { map: diffs })
+    } else {
+        vectors_config_diff::Config::Params(diffs.remove("")?)
+    };
+    Some(VectorsConfigDiff {
+        config: Some(config),
+    })
+}
+
+fn quantization_diff(config: QuantizationConfig) -> Option<QuantizationConfigDiff> {
+    let quantization = match config.quantization? {
+        Quantization::Scalar(scalar) => quantization_config_diff::Quantization::Scalar(scalar),
+        Quantization::Product(product) => quantization_config_diff::Quantization::Product(product),
+        Quantization::Binary(binary) => quantization_config_diff::Quantization::Binary(binary),
+    };
+    Some(quantization.into())
+}
+
+/// Payload schema type reported for an index of the given field type
+fn schema_type(field_type: i32) -> Option<i32> {
+    let field_type = FieldType::try_from(field_type).ok()?;
+    Some(PayloadSchemaType::from(field_type) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::{
+        CollectionConfig, CollectionParams, CreateCollectionBuilder,
+        CreateFieldIndexCollectionBuilder, Distance, HnswConfigDiff, HnswConfigDiffBuilder,
+        PayloadSchemaInfo, VectorParamsBuilder, VectorsConfigBuilder,
+    };
+
+    fn vectors(size: u64) -> VectorsConfig {
+        let mut config = VectorsConfigBuilder::default();
+        config.add_named_vector_params("text", VectorParamsBuilder::new(size, Distance::Cosine));
+        config.into()
+    }
+
+    fn existing() -> CollectionInfo {
+        CollectionInfo {
+            config: Some(CollectionConfig {
+                params: Some(CollectionParams {
+                    shard_number: 1,
+                    vectors_config: Some(vectors(384)),
+                    ..Default::default()
+                }),
+                hnsw_config: Some(HnswConfigDiff {
+                    m: Some(16),
+                    ef_construct: Some(100),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            payload_schema: HashMap::from([
+                (
+                    "author".to_string(),
+                    PayloadSchemaInfo {
+                        data_type: PayloadSchemaType::Keyword as i32,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "year".to_string(),
+                    PayloadSchemaInfo {
+                        data_type: PayloadSchemaType::Keyword as i32,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "legacy".to_string(),
+                    PayloadSchemaInfo {
+                        data_type: PayloadSchemaType::Text as i32,
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn spec_updates_mutable_settings() {
+        let spec = CollectionSpec::new(
+            CreateCollectionBuilder::new("articles")
+                .vectors_config(vectors(384))
+                .hnsw_config(HnswConfigDiffBuilder::default().m(32))
+                .shard_number(1),
+        )
+        .index(CreateFieldIndexCollectionBuilder::new(
+            "",
+            "author",
+            FieldType::Keyword,
+        ))
+        .index(CreateFieldIndexCollectionBuilder::new(
+            "",
+            "year",
+            FieldType::Integer,
+        ))
+        .index(CreateFieldIndexCollectionBuilder::new(
+            "",
+            "body",
+            FieldType::Text,
+        ))
+        .alias("articles_current")
+        .alias("articles_latest")
+        .drop_stale_indexes(true);
+
+        let aliases = [
+            AliasDescription {
+                alias_name: "articles_current".to_string(),
+                collection_name: "articles_v1".to_string(),
+            },
+            AliasDescription {
+                alias_name: "articles_latest".to_string(),
+                collection_name: "articles".to_string(),
+            },
+        ];
+        let plan = spec.plan_update(&existing(), &aliases);
+        assert!(plan.conflicts.is_empty());
+
+        let PlanAction::UpdateCollection { update, changed } = &plan.actions[0] else {
+            panic!("expected a collection update first");
+        };
+        assert_eq!(changed, &["hnsw_config"]);
+        assert_eq!(update.hnsw_config.unwrap().m, Some(32));
+        assert_eq!(update.vectors_config, None);
+
+        let actions: Vec<_> = plan.actions[1..].iter().map(ToString::to_string).collect();
+        assert_eq!(
+            actions,
+            [
+                "delete index on `year`",
+                "create index on `year`",
+                "create index on `body`",
+                "delete index on `legacy`",
+                "move alias `articles_current` from `articles_v1`",
+            ],
+        );
+
+        // Applying the spec once more changes nothing
+        let mut applied = existing();
+        let config = applied.config.as_mut().unwrap();
+        config.hnsw_config.as_mut().unwrap().m = Some(32);
+        applied.payload_schema.remove("legacy");
+        for (field, data_type) in [
+            ("year", PayloadSchemaType::Integer),
+            ("body", PayloadSchemaType::Text),
+        ] {
+            applied.payload_schema.insert(
+                field.to_string(),
+                PayloadSchemaInfo {
+                    data_type: data_type as i32,
+                    ..Default::default()
+                },
+            );
+        }
+        let aliases = [
+            AliasDescription {
+                alias_name: "articles_current".to_string(),
+                collection_name: "articles".to_string(),
+            },
+            aliases[1].clone(),
+        ];
+        assert!(spec.plan_update(&applied, &aliases).is_empty());
+    }
+
+    #[test]
+    fn spec_reports_immutable_conflicts() {
+        let mut vectors = vectors(768);
+        let Some(vectors_config::Config::ParamsMap(map)) = &mut vectors.config else {
+            unreachable!();
+        };
+        map.map.insert(
+            "image".to_string(),
+            VectorParamsBuilder::new(512, Distance::Dot).build(),
+        );
+        map.map.get_mut("text").unwrap().on_disk = Some(true);
+
+        let spec = CollectionSpec::new(
+            CreateCollectionBuilder::new("articles")
+                .vectors_config(vectors)
+                .shard_number(3),
+        );
+        let plan = spec.plan_update(&existing(), &[]);
+        let conflicts: Vec<_> = plan.conflicts.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            conflicts,
+            [
+                "shard_number is 1, spec requires 3",
+                "vectors_config.image is missing, spec requires present",
+                "vectors_config.text.size is 384, spec requires 768",
+            ],
+        );
+
+        // Mutable changes are still planned, but not applied with conflicts
+        let PlanAction::UpdateCollection { changed, .. } = &plan.actions[0] else {
+            panic!("expected a collection update");
+        };
+        assert_eq!(changed, &["vectors_config.text.on_disk"]);
+
+        let create = spec.plan_create(&[]);
+        assert!(matches!(
+            create.actions[..],
+            [PlanAction::CreateCollection(_)]
+        ));
+
+        // The unnamed vector is the vectors config itself
+        let mut diff = Diff::default();
+        let unnamed = VectorsConfig::from(VectorParamsBuilder::new(4, Distance::Cosine));
+        diff_vectors(&unnamed, None, &mut diff);
+        let conflicts: Vec<_> = diff.conflicts.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            conflicts,
+            ["vectors_config is missing, spec requires present"]
+        );
+    }
+}
@@ -0,0 +1,527 @@
+use std::collections::HashMap;
+
+use crate::qdrant::value::Kind;
+use crate::qdrant::vector;
+use crate::qdrant::vectors::VectorsOptions;
+use crate::qdrant::with_payload_selector::SelectorOptions;
+use crate::qdrant::{
+    GetPointsBuilder, PayloadIncludeSelector, PointId, PointStruct, PointsOperationResponse,
+    RetrievedPoint, UpsertPoints, Value, Vector, Vectors,
+};
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+/// Default payload field holding the content hash of a point
+pub const DEFAULT_CONTENT_HASH_FIELD: &str = "_content_hash";
+
+/// Configuration of content hash based upserts
+///
+/// ```rust
+/// use qdrant_client::content_hash::ContentHashConfig;
+///
+/// let config = ContentHashConfig::default()
+///     .field("_sync_hash")
+///     .chunk_size(500);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ContentHashConfig {
+    /// Payload field to store the content hash in
+    ///
+    /// The field is reserved, any value already present in a point's payload is overwritten.
+    pub field: String,
+
+    /// Number of points to look up and upsert per request
+    pub chunk_size: usize,
+}
+
+impl ContentHashConfig {
+    /// Set the payload field to store the content hash in
+    pub fn field(mut self, field: impl Into<String>) -> Self {
+        self.field = field.into();
+        self
+    }
+
+    /// Set the number of points to look up and upsert per request
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+}
+
+impl Default for ContentHashConfig {
+    fn default() -> Self {
+        Self {
+            field: DEFAULT_CONTENT_HASH_FIELD.to_string(),
+            chunk_size: 256,
+        }
+    }
+}
+
+/// Result of [`Qdrant::upsert_points_if_changed`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContentHashUpsertResponse {
+    /// Number of points that did not exist yet
+    pub inserted: usize,
+    /// Number of existing points whose vectors or payload changed
+    pub updated: usize,
+    /// Number of existing points that were skipped because nothing changed
+    pub unchanged: usize,
+    /// Response of the last upsert, `None` if no points had to be upserted
+    pub response: Option<PointsOperationResponse>,
+}
+
+/// # Content hash upserts
+///
+/// Upsert only points whose vectors or payload changed, making repeated synchronization of the
+/// same data cheap.
+impl Qdrant {
+    /// Insert or update points in a collection, skipping points that did not change.
+    ///
+    /// A hash of each point's vectors and payload is stored in a reserved payload field, see
+    /// [`ContentHashConfig::field`]. Existing hashes are fetched in bulk before upserting, and
+    /// points with an unchanged hash are left out. All points must have an ID.
+    ///
+    /// Points stored without going through this method have no hash, and are always updated
+    /// once.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::content_hash::ContentHashConfig;
+    /// use qdrant_client::qdrant::{PointStruct, UpsertPointsBuilder};
+    ///
+    ///# async fn upsert_points_if_changed(client: &Qdrant, points: Vec<PointStruct>)
+    ///# -> Result<(), QdrantError> {
+    /// let result = client
+    ///     .upsert_points_if_changed(
+    ///         UpsertPointsBuilder::new("my_collection", points).wait(true),
+    ///         &ContentHashConfig::default(),
+    ///     )
+    ///     .await?;
+    ///
+    /// println!(
+    ///     "inserted {}, updated {}, unchanged {}",
+    ///     result.inserted, result.updated, result.unchanged,
+    /// );
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn upsert_points_if_changed(
+        &self,
+        request: impl Into<UpsertPoints>,
+        config: &ContentHashConfig,
+    ) -> QdrantResult<ContentHashUpsertResponse> {
+        let mut request = request.into();
+        let mut points = std::mem::take(&mut request.points);
+
+        for point in &mut points {
+            if point.id.is_none() {
+                return Err(QdrantError::ConversionError(
+                    "Content hash upserts require all points to have an ID".to_string(),
+                ));
+            }
+            let hash = content_hash(point, &config.field);
+            point.payload.insert(config.field.clone(), hash.into());
+        }
+
+        let mut result = ContentHashUpsertResponse::default();
+        for chunk in points.chunks(config.chunk_size.max(1)) {
+            let ids: Vec<PointId> = chunk.iter().filter_map(|point| point.id.clone()).collect();
+            let mut lookup = GetPointsBuilder::new(&request.collection_name, ids)
+                .with_payload(SelectorOptions::Include(PayloadIncludeSelector::new(vec![
+                    config.field.clone(),
+                ])))
+                .with_vectors(false);
+            if let Some(shard_key_selector) = &request.shard_key_selector {
+                lookup = lookup.shard_key_selector(shard_key_selector.clone());
+            }
+            let existing = self.get_points(lookup).await?.result;
+            let changed = changed_points(chunk, existing, &config.field, &mut result);
+
+            if !changed.is_empty() {
+                let mut upsert = request.clone();
+                upsert.points = changed;
+                result.response = Some(self.upsert_points(upsert).await?);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Points of `chunk` whose hash differs from the stored hash of the `existing` points, counted
+/// into `result`
+fn changed_points(
+    chunk: &[PointStruct],
+    existing: Vec<RetrievedPoint>,
+    field: &str,
+    result: &mut ContentHashUpsertResponse,
+) -> Vec<PointStruct> {
+    // The server returns UUIDs normalized, key both sides by the normalized ID
+    let existing: HashMap<PointId, Option<Value>> = existing
+        .into_iter()
+        .filter_map(|point| {
+            let hash = point.payload.get(field).cloned();
+            Some((point.id?.normalized(), hash))
+        })
+        .collect();
+
+    let mut changed = Vec::with_capacity(chunk.len());
+    for point in chunk {
+        let hash = point.payload.get(field);
+        match point
+            .id
+            .as_ref()
+            .and_then(|id| existing.get(&id.normalized()))
+        {
+            None => result.inserted += 1,
+            Some(existing) if existing.as_ref() == hash => {
+                result.unchanged += 1;
+                continue;
+            }
+            Some(_) => result.updated += 1,
+        }
+        changed.push(point.clone());
+    }
+    changed
+}
+
+/// Hex encoded hash of the vectors and payload of a point, ignoring the hash field
+///
+/// Uses 64-bit FNV-1a over a canonical encoding, so hashes are stable across processes and
+/// versions regardless of map ordering. Vectors are hashed by their values, so the deprecated
+/// and current representation of the same vector hash the same, as do sparse vectors listing
+/// their indices in another order.
+pub(crate) fn content_hash(point: &PointStruct, field: &str) -> String {
+    let mut hasher = Fnv1a::default();
+
+    if let Some(Vectors {
+        vectors_options: Some(vectors),
+    }) = &point.vectors
+    {
+        match vectors {
+            VectorsOptions::Vector(vector) => {
+                hasher.write(&[0]);
+                hash_vector(&mut hasher, vector);
+            }
+            VectorsOptions::Vectors(named) => {
+                hasher.write(&[1]);
+                let mut names: Vec<_> = named.vectors.keys().collect();
+                names.sort();
+                for name in names {
+                    hasher.write_bytes(name.as_bytes());
+                    hash_vector(&mut hasher, &named.vectors[name]);
+                }
+            }
+        }
+    }
+
+    hasher.write(&[2]);
+    let mut keys: Vec<_> = point.payload.keys().filter(|key| *key != field).collect();
+    keys.sort();
+    for key in keys {
+        hasher.write_bytes(key.as_bytes());
+        hash_value(&mut hasher, &point.payload[key]);
+    }
+
+    format!("{:016x}", hasher.0)
+}
+
+#[expect(deprecated)]
+fn hash_vector(hasher: &mut Fnv1a, vector: &Vector) {
+    match &vector.vector {
+        Some(vector::Vector::Dense(dense)) => hash_dense(hasher, &dense.data),
+        Some(vector::Vector::Sparse(sparse)) => {
+            hash_sparse(hasher, &sparse.indices, &sparse.values)
+        }
+        Some(vector::Vector::MultiDense(multi)) => hash_multi(
+            hasher,
+            multi.vectors.iter().map(|dense| dense.data.as_slice()),
+        ),
+        Some(vector::Vector::Document(document)) => {
+            hasher.write(&[3]);
+            hasher.write_bytes(document.text.as_bytes());
+            hash_inference(hasher, &document.model, &document.options);
+        }
+        Some(vector::Vector::Image(image)) => {
+            hasher.write(&[4]);
+            hash_value(
+                hasher,
+                image.image.as_ref().unwrap_or(&Value { kind: None }),
+            );
+            hash_inference(hasher, &image.model, &image.options);
+        }
+        Some(vector::Vector::Object(object)) => {
+            hasher.write(&[5]);
+            hash_value(
+                hasher,
+                object.object.as_ref().unwrap_or(&Value { kind: None }),
+            );
+            hash_inference(hasher, &object.model, &object.options);
+        }
+        // Deprecated representation, hashed like the equivalent current one
+        None => match (&vector.indices, vector.vectors_count) {
+            (Some(indices), _) => hash_sparse(hasher, &indices.data, &vector.data),
+            (None, Some(count)) if count > 1 && vector.data.len() >= count as usize => {
+                let dim = vector.data.len() / count as usize;
+                hash_multi(hasher, vector.data.chunks(dim))
+            }
+            (None, _) => hash_dense(hasher, &vector.data),
+        },
+    }
+}
+
+fn hash_floats(hasher: &mut Fnv1a, values: &[f32]) {
+    hasher.write(&(values.len() as u64).to_le_bytes());
+    for value in values {
+        hasher.write(&value.to_bits().to_le_bytes());
+    }
+}
+
+fn hash_dense(hasher: &mut Fnv1a, data: &[f32]) {
+    hasher.write(&[0]);
+    hash_floats(hasher, data);
+}
+
+/// Sparse vectors are hashed ordered by index
+fn hash_sparse(hasher: &mut Fnv1a, indices: &[u32], values: &[f32]) {
+    let mut entries: Vec<_> = indices.iter().zip(values).collect();
+    entries.sort_by_key(|(index, _)| **index);
+    hasher.write(&[1]);
+    hasher.write(&(entries.len() as u64).to_le_bytes());
+    for (index, value) in entries {
+        hasher.write(&index.to_le_bytes());
+        hasher.write(&value.to_bits().to_le_bytes());
+    }
+}
+
+fn hash_multi<'a>(hasher: &mut Fnv1a, vectors: impl ExactSizeIterator<Item = &'a [f32]>) {
+    hasher.write(&[2]);
+    hasher.write(&(vectors.len() as u64).to_le_bytes());
+    for vector in vectors {
+        hash_floats(hasher, vector);
+    }
+}
+
+/// Model and options of an inference input, options are hashed ordered by key
+fn hash_inference(hasher: &mut Fnv1a, model: &str, options: &HashMap<String, Value>) {
+    hasher.write_bytes(model.as_bytes());
+    hash_fields(hasher, options);
+}
+
+/// Hash of a payload value, equal for equal values regardless of map ordering
+pub(crate) fn value_hash(value: &Value) -> u64 {
+    let mut hasher = Fnv1a::default();
+    hash_value(&mut hasher, value);
+    hasher.0
+}
+
+fn hash_value(hasher: &mut Fnv1a, value: &Value) {
+    match &value.kind {
+        None | Some(Kind::NullValue(_)) => hasher.write(&[0]),
+        Some(Kind::BoolValue(b)) => hasher.write(&[1, *b as u8]),
+        Some(Kind::IntegerValue(i)) => {
+            hasher.write(&[2]);
+            hasher.write(&i.to_le_bytes());
+        }
+        Some(Kind::DoubleValue(d)) => {
+            hasher.write(&[3]);
+            hasher.write(&d.to_bits().to_le_bytes());
+        }
+        Some(Kind::StringValue(s)) => {
+            hasher.write(&[4]);
+            hasher.write_bytes(s.as_bytes());
+        }
+        Some(Kind::ListValue(list)) => {
+            hasher.write(&[5]);
+            hasher.write(&(list.values.len() as u64).to_le_bytes());
+            for value in &list.values {
+                hash_value(hasher, value);
+            }
+        }
+        Some(Kind::StructValue(object)) => {
+            hasher.write(&[6]);
+            hash_fields(hasher, &object.fields);
+        }
+    }
+}
+
+/// Fields ordered by key
+fn hash_fields(hasher: &mut Fnv1a, fields: &HashMap<String, Value>) {
+    hasher.write(&(fields.len() as u64).to_le_bytes());
+    let mut keys: Vec<_> = fields.keys().collect();
+    keys.sort();
+    for key in keys {
+        hasher.write_bytes(key.as_bytes());
+        hash_value(hasher, &fields[key]);
+    }
+}
+
+/// 64-bit FNV-1a hasher
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    /// Write length prefixed bytes
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::{Document, NamedVectors, SparseIndices};
+    use crate::Payload;
+
+    fn point(payload: Payload) -> PointStruct {
+        PointStruct::new(1, vec![0.1, 0.2], payload)
+    }
+
+    #[test]
+    fn content_hash_is_canonical() {
+        let payload = Payload::from([
+            ("a", Value::from(1i64)),
+            ("b", Value::from(vec![Value::from("x"), Value::from(true)])),
+        ]);
+        let hash = content_hash(&point(payload.clone()), DEFAULT_CONTENT_HASH_FIELD);
+        assert_eq!(hash.len(), 16);
+
+        // Known FNV-1a value guards against accidental changes of the encoding
+        let mut hasher = Fnv1a::default();
+        hasher.write(b"a");
+        assert_eq!(hasher.0, 0xaf63dc4c8601ec8c);
+
+        // The hash field itself and payload order don't matter
+        let mut with_hash = point(Payload::from([
+            ("b", Value::from(vec![Value::from("x"), Value::from(true)])),
+            ("a", Value::from(1i64)),
+        ]));
+        with_hash
+            .payload
+            .insert(DEFAULT_CONTENT_HASH_FIELD.to_string(), "stale".into());
+        assert_eq!(content_hash(&with_hash, DEFAULT_CONTENT_HASH_FIELD), hash);
+
+        // Payload and vector changes do
+        let changed = point(Payload::from([("a", Value::from(1.0))]));
+        assert_ne!(content_hash(&changed, DEFAULT_CONTENT_HASH_FIELD), hash);
+        let mut moved = point(payload);
+        moved.vectors = Some(vec![0.2, 0.1].into());
+        assert_ne!(content_hash(&moved, DEFAULT_CONTENT_HASH_FIELD), hash);
+    }
+
+    #[test]
+    fn content_hash_named_vectors() {
+        let named = |names: [&str; 2]| {
+            let mut point = point(Payload::new());
+            point.vectors = Some(Vectors {
+                vectors_options: Some(VectorsOptions::Vectors(NamedVectors {
+                    vectors: names
+                        .iter()
+                        .map(|name| (name.to_string(), Vector::new_dense(vec![name.len() as f32])))
+                        .collect(),
+                })),
+            });
+            content_hash(&point, DEFAULT_CONTENT_HASH_FIELD)
+        };
+        assert_eq!(named(["image", "text"]), named(["text", "image"]));
+        assert_ne!(named(["image", "text"]), named(["image", "texts"]));
+    }
+
+    #[test]
+    fn changed_points_match_normalized_uuids() {
+        let uuid = "936DA01F-9ABD-4D9D-80C7-02AF85C822A8";
+        let mut unchanged = PointStruct::new(uuid, vec![0.1], Payload::new());
+        let hash = content_hash(&unchanged, DEFAULT_CONTENT_HASH_FIELD);
+        unchanged
+            .payload
+            .insert(DEFAULT_CONTENT_HASH_FIELD.to_string(), hash.clone().into());
+        let mut new = unchanged.clone();
+        new.id = Some(PointId::from(7));
+
+        // The server returns the UUID lower-cased
+        let existing = vec![RetrievedPoint {
+            id: Some(PointId::from(uuid.to_ascii_lowercase())),
+            payload: HashMap::from([(DEFAULT_CONTENT_HASH_FIELD.to_string(), hash.into())]),
+            ..Default::default()
+        }];
+        let mut result = ContentHashUpsertResponse::default();
+        let changed = changed_points(
+            &[unchanged, new.clone()],
+            existing,
+            DEFAULT_CONTENT_HASH_FIELD,
+            &mut result,
+        );
+        assert_eq!(changed, [new]);
+        assert_eq!(
+            (result.inserted, result.updated, result.unchanged),
+            (1, 0, 1)
+        );
+    }
+
+    #[test]
+    #[expect(deprecated)]
+    fn content_hash_of_equivalent_vectors() {
+        let hash = |vector: Vector| {
+            let mut point = point(Payload::new());
+            point.vectors = Some(vector.into());
+            content_hash(&point, DEFAULT_CONTENT_HASH_FIELD)
+        };
+        let legacy =
+            |data: Vec<f32>, indices: Option<Vec<u32>>, vectors_count: Option<u32>| Vector {
+                data,
+                indices: indices.map(|data| SparseIndices { data }),
+                vectors_count,
+                vector: None,
+            };
+
+        // Deprecated and current representations hash the same
+        assert_eq!(
+            hash(legacy(vec![0.1, 0.2], None, None)),
+            hash(Vector::new_dense(vec![0.1, 0.2])),
+        );
+        assert_eq!(
+            hash(legacy(vec![0.5, 0.7], Some(vec![3, 1]), None)),
+            hash(Vector::new_sparse(vec![1, 3], vec![0.7, 0.5])),
+        );
+        assert_eq!(
+            hash(legacy(vec![1.0, 2.0, 3.0, 4.0], None, Some(2))),
+            hash(Vector::new_multi(vec![vec![1.0, 2.0], vec![3.0, 4.0]])),
+        );
+        assert_ne!(
+            hash(Vector::new_dense(vec![1.0, 2.0, 3.0, 4.0])),
+            hash(Vector::new_multi(vec![vec![1.0, 2.0], vec![3.0, 4.0]])),
+        );
+
+        // Inference options are hashed ordered by key
+        let document = |options: &[(&str, i64)]| {
+            hash(Vector {
+                vector: Some(vector::Vector::Document(Document {
+                    text: "text".to_string(),
+                    model: "model".to_string(),
+                    options: options
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), Value::from(*value)))
+                        .collect(),
+                })),
+                ..Default::default()
+            })
+        };
+        let options = [("a", 1), ("b", 2), ("c", 3), ("d", 4)];
+        let reversed = [("d", 4), ("c", 3), ("b", 2), ("a", 1)];
+        assert_eq!(document(&options), document(&reversed));
+        assert_ne!(document(&options), document(&options[..3]));
+    }
+}
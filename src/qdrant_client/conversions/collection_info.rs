@@ -0,0 +1,298 @@
+use crate::qdrant::{
+    CollectionConfig, CollectionInfo, CollectionParamsDiff, CreateCollection,
+    CreateFieldIndexCollection, FieldType, PayloadSchemaType, UpdateCollection,
+};
+use crate::QdrantError;
+
+impl CollectionInfo {
+    /// Request to create a collection with the configuration of this one.
+    ///
+    /// Vectors, including named, sparse and multivectors with their datatype and on disk flags,
+    /// are copied as is, as well as the HNSW, optimizer, WAL, quantization and strict mode
+    /// configuration, sharding and metadata. Use [`to_field_indexes`](Self::to_field_indexes)
+    /// to copy the payload indexes as well.
+    ///
+    /// The read fan-out factor and delay cannot be set when creating a collection. Apply them
+    /// afterwards with [`to_update_collection`](Self::to_update_collection).
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    ///# async fn to_create_collection(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let info = client.collection_info("template").await?.result.unwrap();
+    ///
+    /// client
+    ///     .create_collection(info.to_create_collection("tenant_42"))
+    ///     .await?;
+    /// if let Some(update) = info.to_update_collection("tenant_42") {
+    ///     client.update_collection(update).await?;
+    /// }
+    /// for index in info.to_field_indexes("tenant_42") {
+    ///     client.create_field_index(index).await?;
+    /// }
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub fn to_create_collection(&self, collection_name: impl Into<String>) -> CreateCollection {
+        let config = self.config.clone().unwrap_or_default();
+        CreateCollection {
+            collection_name: collection_name.into(),
+            ..CreateCollection::from(config)
+        }
+    }
+
+    /// Request to apply the parameters of this collection that cannot be set on creation.
+    ///
+    /// Sets the read fan-out factor and delay, returns `None` if neither is configured.
+    pub fn to_update_collection(
+        &self,
+        collection_name: impl Into<String>,
+    ) -> Option<UpdateCollection> {
+        let params = self.config.as_ref()?.params.as_ref()?;
+        if params.read_fan_out_factor.is_none() && params.read_fan_out_delay_ms.is_none() {
+            return None;
+        }
+        Some(UpdateCollection {
+            collection_name: collection_name.into(),
+            params: Some(CollectionParamsDiff {
+                read_fan_out_factor: params.read_fan_out_factor,
+                read_fan_out_delay_ms: params.read_fan_out_delay_ms,
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    /// Requests to create the payload indexes of this collection, ordered by field name.
+    ///
+    /// Indexes of an unknown type are skipped.
+    pub fn to_field_indexes(
+        &self,
+        collection_name: impl Into<String>,
+    ) -> Vec<CreateFieldIndexCollection> {
+        let collection_name = collection_name.into();
+        let mut indexes: Vec<_> = self
+            .payload_schema
+            .iter()
+            .filter_map(|(field_name, schema)| {
+                let field_type = FieldType::try_from(schema.data_type()).ok()?;
+                Some(CreateFieldIndexCollection {
+                    collection_name: collection_name.clone(),
+                    wait: Some(true),
+                    field_name: field_name.clone(),
+                    field_type: Some(field_type as i32),
+                    field_index_params: schema.params.clone(),
+                    ordering: None,
+                    timeout: None,
+                })
+            })
+            .collect();
+        indexes.sort_by(|a, b| a.field_name.cmp(&b.field_name));
+        indexes
+    }
+}
+
+/// Create collection request with the given configuration, and an empty collection name
+///
+/// The read fan-out factor and delay are left out, [`CreateCollection`] has no fields for them.
+impl From<CollectionConfig> for CreateCollection {
+    fn from(config: CollectionConfig) -> Self {
+        let params = config.params.unwrap_or_default();
+        CreateCollection {
+            collection_name: String::new(),
+            hnsw_config: config.hnsw_config,
+            wal_config: config.wal_config,
+            optimizers_config: config.optimizer_config,
+            // Zero shards is not a valid configuration, let the server pick the default instead
+            shard_number: Some(params.shard_number).filter(|&shard_number| shard_number > 0),
+            on_disk_payload: Some(params.on_disk_payload),
+            timeout: None,
+            vectors_config: params.vectors_config,
+            replication_factor: params.replication_factor,
+            write_consistency_factor: params.write_consistency_factor,
+            quantization_config: config.quantization_config,
+            sharding_method: params.sharding_method,
+            sparse_vectors_config: params.sparse_vectors_config,
+            strict_mode_config: config.strict_mode_config,
+            metadata: config.metadata,
+        }
+    }
+}
+
+impl From<FieldType> for PayloadSchemaType {
+    fn from(field_type: FieldType) -> Self {
+        match field_type {
+            FieldType::Keyword => PayloadSchemaType::Keyword,
+            FieldType::Integer => PayloadSchemaType::Integer,
+            FieldType::Float => PayloadSchemaType::Float,
+            FieldType::Geo => PayloadSchemaType::Geo,
+            FieldType::Text => PayloadSchemaType::Text,
+            FieldType::Bool => PayloadSchemaType::Bool,
+            FieldType::Datetime => PayloadSchemaType::Datetime,
+            FieldType::Uuid => PayloadSchemaType::Uuid,
+        }
+    }
+}
+
+impl TryFrom<PayloadSchemaType> for FieldType {
+    type Error = QdrantError;
+
+    fn try_from(schema_type: PayloadSchemaType) -> Result<Self, Self::Error> {
+        match schema_type {
+            PayloadSchemaType::Keyword => Ok(FieldType::Keyword),
+            PayloadSchemaType::Integer => Ok(FieldType::Integer),
+            PayloadSchemaType::Float => Ok(FieldType::Float),
+            PayloadSchemaType::Geo => Ok(FieldType::Geo),
+            PayloadSchemaType::Text => Ok(FieldType::Text),
+            PayloadSchemaType::Bool => Ok(FieldType::Bool),
+            PayloadSchemaType::Datetime => Ok(FieldType::Datetime),
+            PayloadSchemaType::Uuid => Ok(FieldType::Uuid),
+            PayloadSchemaType::UnknownType => Err(QdrantError::ConversionError(
+                "Payload schema type is unknown".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::qdrant::{
+        payload_index_params, quantization_config, CollectionParams, Datatype, Distance,
+        HnswConfigDiff, KeywordIndexParams, Modifier, MultiVectorComparator, MultiVectorConfig,
+        PayloadIndexParams, PayloadSchemaInfo, QuantizationConfig, ScalarQuantizationBuilder,
+        SparseVectorConfig, SparseVectorParams, VectorParamsBuilder, VectorsConfig,
+        VectorsConfigBuilder, WalConfigDiff,
+    };
+
+    #[test]
+    fn collection_info_to_create_collection() {
+        let mut vectors = VectorsConfigBuilder::default();
+        vectors.add_named_vector_params(
+            "text",
+            VectorParamsBuilder::new(384, Distance::Cosine)
+                .datatype(Datatype::Float16)
+                .on_disk(true),
+        );
+        vectors.add_named_vector_params(
+            "colbert",
+            VectorParamsBuilder::new(128, Distance::Dot).multivector_config(MultiVectorConfig {
+                comparator: MultiVectorComparator::MaxSim as i32,
+            }),
+        );
+        let vectors_config = VectorsConfig::from(vectors);
+        let sparse_vectors_config = SparseVectorConfig {
+            map: HashMap::from([(
+                "bm25".to_string(),
+                SparseVectorParams {
+                    index: None,
+                    modifier: Some(Modifier::Idf as i32),
+                },
+            )]),
+        };
+
+        let config = CollectionConfig {
+            params: Some(CollectionParams {
+                shard_number: 2,
+                on_disk_payload: true,
+                vectors_config: Some(vectors_config.clone()),
+                replication_factor: Some(2),
+                sparse_vectors_config: Some(sparse_vectors_config.clone()),
+                read_fan_out_factor: Some(1),
+                read_fan_out_delay_ms: Some(50),
+                ..Default::default()
+            }),
+            hnsw_config: Some(HnswConfigDiff {
+                m: Some(32),
+                ..Default::default()
+            }),
+            wal_config: Some(WalConfigDiff {
+                wal_capacity_mb: Some(64),
+                ..Default::default()
+            }),
+            quantization_config: Some(QuantizationConfig::from(
+                quantization_config::Quantization::from(ScalarQuantizationBuilder::default()),
+            )),
+            ..Default::default()
+        };
+        let info = CollectionInfo {
+            config: Some(config.clone()),
+            ..Default::default()
+        };
+
+        let create = info.to_create_collection("copy");
+        assert_eq!(create.collection_name, "copy");
+        assert_eq!(create.shard_number, Some(2));
+        assert_eq!(create.on_disk_payload, Some(true));
+        assert_eq!(create.replication_factor, Some(2));
+        assert_eq!(create.vectors_config, Some(vectors_config));
+        assert_eq!(create.sparse_vectors_config, Some(sparse_vectors_config));
+        assert_eq!(create.hnsw_config, config.hnsw_config);
+        assert_eq!(create.wal_config, config.wal_config);
+        assert_eq!(create.quantization_config, config.quantization_config);
+
+        let update = info.to_update_collection("copy").unwrap();
+        assert_eq!(update.collection_name, "copy");
+        let params = update.params.unwrap();
+        assert_eq!(params.read_fan_out_factor, Some(1));
+        assert_eq!(params.read_fan_out_delay_ms, Some(50));
+        assert_eq!(params.replication_factor, None);
+
+        let create = CollectionInfo::default().to_create_collection("empty");
+        assert_eq!(create.shard_number, None);
+        assert_eq!(create.vectors_config, None);
+        assert_eq!(
+            CollectionInfo::default().to_update_collection("empty"),
+            None
+        );
+    }
+
+    #[test]
+    fn collection_info_to_field_indexes() {
+        let keyword_params = PayloadIndexParams {
+            index_params: Some(payload_index_params::IndexParams::KeywordIndexParams(
+                KeywordIndexParams {
+                    is_tenant: Some(true),
+                    on_disk: None,
+                    enable_hnsw: None,
+                },
+            )),
+        };
+        let schema =
+            |data_type: PayloadSchemaType, params: Option<PayloadIndexParams>| PayloadSchemaInfo {
+                data_type: data_type as i32,
+                params,
+                points: Some(10),
+            };
+        let info = CollectionInfo {
+            payload_schema: HashMap::from([
+                ("year".to_string(), schema(PayloadSchemaType::Integer, None)),
+                (
+                    "tenant".to_string(),
+                    schema(PayloadSchemaType::Keyword, Some(keyword_params.clone())),
+                ),
+                (
+                    "legacy".to_string(),
+                    schema(PayloadSchemaType::UnknownType, None),
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        let indexes = info.to_field_indexes("copy");
+        assert_eq!(indexes.len(), 2);
+        assert_eq!(indexes[0].collection_name, "copy");
+        assert_eq!(indexes[0].field_name, "tenant");
+        assert_eq!(indexes[0].field_type, Some(FieldType::Keyword as i32));
+        assert_eq!(indexes[0].field_index_params, Some(keyword_params));
+        assert_eq!(indexes[1].field_name, "year");
+        assert_eq!(indexes[1].field_type, Some(FieldType::Integer as i32));
+
+        for field_type in [FieldType::Keyword, FieldType::Datetime, FieldType::Uuid] {
+            let schema_type = PayloadSchemaType::from(field_type);
+            assert_eq!(FieldType::try_from(schema_type).unwrap(), field_type);
+        }
+    }
+}
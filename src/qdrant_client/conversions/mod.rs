@@ -1,3 +1,4 @@
+pub mod collection_info;
 pub mod expression;
 pub mod payloads;
 pub mod query;
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use crate::qdrant::vectors::VectorsOptions;
-use crate::qdrant::{vector, Document, Image, InferenceObject, NamedVectors, Vector, Vectors};
+use crate::qdrant::{
+    vector, vector_output, vectors_output, Document, Image, InferenceObject, NamedVectors, Vector,
+    VectorOutput, Vectors, VectorsOutput,
+};
 
 impl From<Vec<f32>> for Vector {
     fn from(vector: Vec<f32>) -> Self {
@@ -268,6 +271,39 @@ impl From<HashMap<String, InferenceObject>> for Vectors {
     }
 }
 
+/// Input vector with the data of a retrieved vector, to write it back as is
+impl From<VectorOutput> for Vector {
+    fn from(value: VectorOutput) -> Self {
+        match value.into_vector() {
+            vector_output::Vector::Dense(dense) => dense.into(),
+            vector_output::Vector::Sparse(sparse) => sparse.into(),
+            vector_output::Vector::MultiDense(multi) => multi.into(),
+        }
+    }
+}
+
+/// Input vectors with the data of retrieved vectors, to write them back as is
+impl From<VectorsOutput> for Vectors {
+    fn from(value: VectorsOutput) -> Self {
+        let options = match value.vectors_options {
+            Some(vectors_output::VectorsOptions::Vector(vector)) => {
+                VectorsOptions::Vector(vector.into())
+            }
+            Some(vectors_output::VectorsOptions::Vectors(named)) => {
+                VectorsOptions::Vectors(NamedVectors {
+                    vectors: named
+                        .vectors
+                        .into_iter()
+                        .map(|(name, vector)| (name, vector.into()))
+                        .collect(),
+                })
+            }
+            None => return Vectors::default(),
+        };
+        options.into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,6 +3,8 @@ use tonic::codegen::http::uri::InvalidUri;
 
 #[cfg(feature = "serde")]
 use crate::serde_deser::DeserPayloadError;
+#[cfg(feature = "serde")]
+use crate::serde_ser::SerPayloadError;
 
 /// Qdrant client error
 #[derive(Error, Debug)]
@@ -33,10 +35,58 @@ pub enum QdrantError {
     #[error("Invalid URI: {}", .0)]
     InvalidUri(#[source] InvalidUri),
 
-    /// Snapshot not found
+    /// Request would access points outside of a tenant
+    #[error("Tenant violation: {}", .0)]
+    TenantViolation(String),
+
+    /// Existing collection differs from its spec in settings that can't be changed
+    ///
+    /// Contains the plan with the conflicts, nothing of it was applied.
+    #[error("Collection conflicts with its spec: {}", .0)]
+    CollectionSpecConflict(Box<crate::qdrant_client::collection_spec::CollectionPlan>),
+
+    /// Reindex target is the collection the alias already points to
+    #[error("Alias {alias} already points to collection {collection_name}")]
+    AliasAlreadyPointsToTarget {
+        /// Alias being reindexed
+        alias: String,
+        /// Target collection of the reindex
+        collection_name: String,
+    },
+
+    /// Waiting for a condition took longer than allowed
+    #[error("Timed out: {}", .0)]
+    Timeout(String),
+
+    /// Collection did not meet the awaited conditions in time, with its last observed state
+    #[error("Timed out waiting for collection {collection_name}, last status {status:?}, optimizer status {optimizer_status:?}, update queue {update_queue:?}")]
+    CollectionWaitTimeout {
+        collection_name: String,
+        /// Last observed collection status
+        status: crate::qdrant::CollectionStatus,
+        /// Last observed optimizer status
+        optimizer_status: Option<crate::qdrant::OptimizerStatus>,
+        /// Last observed update queue, if reported by the server
+        update_queue: Option<crate::qdrant::UpdateQueueInfo>,
+    },
+
+    /// Shard transfer ended without an active replica on the target peer
+    #[error("Shard transfer failed: {}", .0)]
+    ShardTransferFailed(String),
+
+    /// Snapshot not found, with the collection name which is empty for full snapshots
     #[error("No snapshot found for collection: {}", .0)]
     NoSnapshotFound(String),
 
+    /// Snapshot file does not match the checksum reported by the server
+    #[error("Snapshot checksum mismatch, expected {expected} but got {actual}")]
+    SnapshotChecksumMismatch {
+        /// Expected SHA256 checksum
+        expected: String,
+        /// SHA256 checksum of the file
+        actual: String,
+    },
+
     /// Generic IO error
     #[error("IO error: {}", .0)]
     Io(#[from] std::io::Error),
@@ -46,6 +96,16 @@ pub enum QdrantError {
     #[error("Reqwest error: {}", .0)]
     Reqwest(#[from] reqwest::Error),
 
+    /// Qdrant REST API responded with an error
+    #[cfg(feature = "reqwest")]
+    #[error("Error in the REST response: {} {}", .status, .message)]
+    RestError {
+        /// HTTP status code
+        status: reqwest::StatusCode,
+        /// Error message of the server
+        message: String,
+    },
+
     /// JSON to payload conversion error, only JSON objects are supported
     #[cfg(feature = "serde")]
     #[error("JSON cannot be converted to payload, only JSON objects are supported")]
@@ -55,6 +115,11 @@ pub enum QdrantError {
     #[cfg(feature = "serde")]
     #[error("Error in payload deserialization")]
     PayloadDeserialization(#[from] DeserPayloadError),
+
+    /// Error when failing to serialize payload using `Payload::from_serializable()`.
+    #[cfg(feature = "serde")]
+    #[error("Error in payload serialization: {}", .0)]
+    PayloadSerialization(#[from] SerPayloadError),
 }
 
 impl QdrantError {
@@ -0,0 +1,631 @@
+use std::collections::HashMap;
+
+use crate::qdrant::{PointId, QueryPoints, ScoredPoint};
+use crate::qdrant_client::content_hash::value_hash;
+use crate::qdrant_client::{Qdrant, QdrantResult};
+
+/// Default `k` of reciprocal rank fusion, equal to the server default
+const DEFAULT_RRF_K: u32 = 2;
+
+/// Default number of results, equal to the server default
+const DEFAULT_LIMIT: u64 = 10;
+
+/// Method to fuse the results of a [`FederatedQuery`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum FederatedFusion {
+    /// Reciprocal rank fusion, scoring each hit with `weight / (k + rank)` per target, where the
+    /// best hit has rank 1
+    ///
+    /// Only uses the rank of hits, so it works for results of any distance metric.
+    Rrf {
+        /// Rank constant, higher values give lower ranked hits more importance
+        k: u32,
+    },
+    /// Distribution-based score fusion
+    ///
+    /// Normalizes the scores of each target using their mean and three standard deviations, and
+    /// sums the weighted normalized scores.
+    Dbsf,
+    /// Weighted score normalization
+    ///
+    /// Normalizes the scores of each target to `0..=1` using their minimum and maximum, and sums
+    /// the weighted normalized scores.
+    WeightedNormalized,
+}
+
+impl Default for FederatedFusion {
+    fn default() -> Self {
+        Self::Rrf { k: DEFAULT_RRF_K }
+    }
+}
+
+/// Key to detect the same hit returned by multiple targets
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum DedupeKey {
+    /// Hits with the same point ID are the same
+    ///
+    /// Only use this if the targets hold the same points, such as copies of a collection on
+    /// different clusters. Otherwise unrelated points that happen to share an ID are merged.
+    PointId,
+    /// Hits with the same value in this payload field are the same
+    ///
+    /// Hits without the field are never deduplicated.
+    PayloadField(String),
+    /// Never deduplicate, hits of different targets are always different
+    #[default]
+    None,
+}
+
+/// Order of the scores returned by the targets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScoreOrder {
+    /// Higher scores are better, as for cosine and dot product similarity
+    #[default]
+    Descending,
+    /// Lower scores are better, as for euclidean and manhattan distance
+    Ascending,
+}
+
+/// A collection to query, possibly on a different Qdrant instance
+#[derive(Clone)]
+pub struct FederatedTarget {
+    /// Client to query with, uses the client running the query if not set
+    pub client: Option<Qdrant>,
+    /// Collection to query
+    pub collection_name: String,
+    /// Weight of the results of this target in fusion
+    pub weight: f32,
+}
+
+/// Query to run against multiple collections, fusing the results client-side
+///
+/// ```rust
+/// use qdrant_client::federated::{DedupeKey, FederatedFusion, FederatedQuery, ScoreOrder};
+/// use qdrant_client::qdrant::QueryPointsBuilder;
+///
+/// let query = FederatedQuery::new(QueryPointsBuilder::new("").query(vec![0.1, 0.2]).limit(5))
+///     .target("tenant_a")
+///     .weighted_target("tenant_b", 0.5)
+///     .fusion(FederatedFusion::Dbsf)
+///     .score_order(ScoreOrder::Ascending)
+///     .dedupe_by(DedupeKey::PayloadField("document_id".to_string()));
+/// ```
+#[derive(Clone)]
+pub struct FederatedQuery {
+    /// Query to run against each target, its collection name is replaced by the target's
+    pub request: QueryPoints,
+    /// Targets to query
+    pub targets: Vec<FederatedTarget>,
+    /// Method to fuse the results of all targets
+    pub fusion: FederatedFusion,
+    /// Order of the scores returned by the targets
+    pub score_order: ScoreOrder,
+    /// Key to deduplicate hits of different targets
+    pub dedupe_by: DedupeKey,
+}
+
+impl FederatedQuery {
+    /// Create a federated query without targets
+    ///
+    /// The offset and limit of the request apply to the fused result. Each target is queried
+    /// for `offset + limit` hits.
+    pub fn new(request: impl Into<QueryPoints>) -> Self {
+        Self {
+            request: request.into(),
+            targets: Vec::new(),
+            fusion: FederatedFusion::default(),
+            score_order: ScoreOrder::default(),
+            dedupe_by: DedupeKey::default(),
+        }
+    }
+
+    /// Query a collection with the client running the query
+    pub fn target(self, collection_name: impl Into<String>) -> Self {
+        self.weighted_target(collection_name, 1.0)
+    }
+
+    /// Query a collection with the client running the query, weighing its results
+    pub fn weighted_target(mut self, collection_name: impl Into<String>, weight: f32) -> Self {
+        self.targets.push(FederatedTarget {
+            client: None,
+            collection_name: collection_name.into(),
+            weight,
+        });
+        self
+    }
+
+    /// Query a collection with a different client, weighing its results
+    pub fn remote_target(
+        mut self,
+        client: &Qdrant,
+        collection_name: impl Into<String>,
+        weight: f32,
+    ) -> Self {
+        self.targets.push(FederatedTarget {
+            client: Some(client.clone()),
+            collection_name: collection_name.into(),
+            weight,
+        });
+        self
+    }
+
+    /// Set the method to fuse results with, defaults to [`FederatedFusion::Rrf`]
+    pub fn fusion(mut self, fusion: FederatedFusion) -> Self {
+        self.fusion = fusion;
+        self
+    }
+
+    /// Set the order of the scores returned by the targets, defaults to
+    /// [`ScoreOrder::Descending`]
+    ///
+    /// Score based fusion inverts ascending scores, so that better hits score higher.
+    pub fn score_order(mut self, score_order: ScoreOrder) -> Self {
+        self.score_order = score_order;
+        self
+    }
+
+    /// Set the key to deduplicate hits with, defaults to [`DedupeKey::None`]
+    pub fn dedupe_by(mut self, dedupe_by: DedupeKey) -> Self {
+        self.dedupe_by = dedupe_by;
+        self
+    }
+}
+
+/// Where a [`FederatedHit`] was found
+#[derive(Clone, Debug, PartialEq)]
+pub struct HitSource {
+    /// Index of the target in [`FederatedQuery::targets`]
+    pub target: usize,
+    /// Collection the hit was found in
+    pub collection_name: String,
+    /// ID of the point in the collection
+    pub id: Option<PointId>,
+    /// Zero-based rank of the hit in the results of the target
+    pub rank: usize,
+    /// Original score of the hit
+    pub score: f32,
+}
+
+/// A fused hit of a [`FederatedQuery`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FederatedHit {
+    /// The best ranked point of all sources, with its score replaced by the fused score
+    pub point: ScoredPoint,
+    /// Sources the hit was found in, ordered by target
+    pub sources: Vec<HitSource>,
+}
+
+/// Response of a [`FederatedQuery`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FederatedResponse {
+    /// Fused hits, ordered by fused score
+    pub result: Vec<FederatedHit>,
+    /// Time spent to process the slowest target
+    pub time: f64,
+}
+
+/// # Federated query
+///
+/// Query multiple collections, possibly on different Qdrant instances, and fuse the results
+/// client-side.
+impl Qdrant {
+    /// Query multiple collections concurrently and fuse the results.
+    ///
+    /// Server-side fusion only fuses prefetches within one collection. This runs the same query
+    /// against each target of the [`FederatedQuery`], then fuses and deduplicates the hits
+    /// client-side. Each hit reports the targets it was found in. Fails if any target fails.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::federated::{FederatedFusion, FederatedQuery};
+    /// use qdrant_client::qdrant::QueryPointsBuilder;
+    ///
+    ///# async fn federated_query(client: &Qdrant, other_cluster: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let response = client
+    ///     .federated_query(
+    ///         FederatedQuery::new(QueryPointsBuilder::new("").query(vec![0.1, 0.2]).limit(10))
+    ///             .target("tenant_a")
+    ///             .target("tenant_b")
+    ///             .remote_target(other_cluster, "tenant_c", 0.5)
+    ///             .fusion(FederatedFusion::WeightedNormalized),
+    ///     )
+    ///     .await?;
+    ///
+    /// for hit in response.result {
+    ///     println!("{:?} found in {} collections", hit.point.id, hit.sources.len());
+    /// }
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn federated_query(&self, query: FederatedQuery) -> QdrantResult<FederatedResponse> {
+        let FederatedQuery {
+            request,
+            targets,
+            fusion,
+            score_order,
+            dedupe_by,
+        } = query;
+
+        let offset = request.offset.unwrap_or(0) as usize;
+        let limit = request.limit.unwrap_or(DEFAULT_LIMIT) as usize;
+
+        let responses = futures::future::try_join_all(targets.iter().map(|target| {
+            let client = target.client.as_ref().unwrap_or(self);
+            let request = QueryPoints {
+                collection_name: target.collection_name.clone(),
+                limit: Some((offset + limit) as u64),
+                offset: None,
+                ..request.clone()
+            };
+            client.query(request)
+        }))
+        .await?;
+
+        let time = responses
+            .iter()
+            .map(|response| response.time)
+            .fold(0.0, f64::max);
+        let results = responses
+            .into_iter()
+            .map(|response| response.result)
+            .collect();
+
+        let result = fuse(
+            &targets,
+            results,
+            &fusion,
+            score_order,
+            &dedupe_by,
+            offset,
+            limit,
+        );
+        Ok(FederatedResponse { result, time })
+    }
+}
+
+/// Identity of a hit for deduplication
+#[derive(PartialEq, Eq, Hash)]
+enum HitKey {
+    Point(PointId),
+    /// Canonical hash of the payload value, independent of map ordering
+    Payload(u64),
+    Unique(usize, usize),
+}
+
+fn hit_key(dedupe_by: &DedupeKey, target: usize, rank: usize, point: &ScoredPoint) -> HitKey {
+    let key = match dedupe_by {
+        DedupeKey::PointId => point.id.as_ref().map(|id| HitKey::Point(id.normalized())),
+        DedupeKey::PayloadField(field) => point
+            .payload
+            .get(field)
+            .map(|value| HitKey::Payload(value_hash(value))),
+        DedupeKey::None => None,
+    };
+    key.unwrap_or(HitKey::Unique(target, rank))
+}
+
+/// Fused score contribution of each hit in the results of one target
+fn fused_scores(
+    fusion: &FederatedFusion,
+    score_order: ScoreOrder,
+    weight: f32,
+    points: &[ScoredPoint],
+) -> Vec<f32> {
+    let ascending = score_order == ScoreOrder::Ascending;
+    let scores = points
+        .iter()
+        .map(|point| if ascending { -point.score } else { point.score });
+
+    match fusion {
+        FederatedFusion::Rrf { k } => (0..points.len())
+            .map(|rank| weight / (*k as f32 + rank as f32 + 1.0))
+            .collect(),
+        FederatedFusion::Dbsf => {
+            let count = points.len() as f32;
+            let mean = scores.clone().sum::<f32>() / count;
+            let variance = scores.clone().map(|s| (s - mean).powi(2)).sum::<f32>() / count;
+            let deviation = variance.sqrt();
+            let (low, high) = (mean - 3.0 * deviation, mean + 3.0 * deviation);
+            scores
+                .map(|score| weight * normalize(score, low, high))
+                .collect()
+        }
+        FederatedFusion::WeightedNormalized => {
+            let low = scores.clone().fold(f32::INFINITY, f32::min);
+            let high = scores.clone().fold(f32::NEG_INFINITY, f32::max);
+            scores
+                .map(|score| weight * normalize(score, low, high))
+                .collect()
+        }
+    }
+}
+
+/// Scale a score into `0..=1`, or `0.5` if all scores are equal
+fn normalize(score: f32, low: f32, high: f32) -> f32 {
+    if high > low {
+        ((score - low) / (high - low)).clamp(0.0, 1.0)
+    } else {
+        0.5
+    }
+}
+
+fn fuse(
+    targets: &[FederatedTarget],
+    results: Vec<Vec<ScoredPoint>>,
+    fusion: &FederatedFusion,
+    score_order: ScoreOrder,
+    dedupe_by: &DedupeKey,
+    offset: usize,
+    limit: usize,
+) -> Vec<FederatedHit> {
+    struct Fused {
+        score: f32,
+        /// Contribution of the source `point` was taken from
+        best_contribution: f32,
+        hit: FederatedHit,
+    }
+
+    let mut fused: Vec<Fused> = Vec::new();
+    let mut positions: HashMap<HitKey, usize> = HashMap::new();
+
+    for (target_index, (target, points)) in targets.iter().zip(results).enumerate() {
+        let contributions = fused_scores(fusion, score_order, target.weight, &points);
+        for (rank, (point, contribution)) in points.into_iter().zip(contributions).enumerate() {
+            let source = HitSource {
+                target: target_index,
+                collection_name: target.collection_name.clone(),
+                id: point.id.clone(),
+                rank,
+                score: point.score,
+            };
+
+            let key = hit_key(dedupe_by, target_index, rank, &point);
+            match positions.get(&key) {
+                Some(&position) => {
+                    let entry = &mut fused[position];
+                    entry.score += contribution;
+                    entry.hit.sources.push(source);
+                    if contribution > entry.best_contribution {
+                        entry.best_contribution = contribution;
+                        entry.hit.point = point;
+                    }
+                }
+                None => {
+                    positions.insert(key, fused.len());
+                    fused.push(Fused {
+                        score: contribution,
+                        best_contribution: contribution,
+                        hit: FederatedHit {
+                            point,
+                            sources: vec![source],
+                        },
+                    });
+                }
+            }
+        }
+    }
+
+    // Stable sort keeps earlier targets first on equal scores
+    fused.sort_by(|a, b| b.score.total_cmp(&a.score));
+    fused
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|Fused { score, mut hit, .. }| {
+            hit.point.score = score;
+            hit
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::Value;
+
+    fn target(collection_name: &str, weight: f32) -> FederatedTarget {
+        FederatedTarget {
+            client: None,
+            collection_name: collection_name.to_string(),
+            weight,
+        }
+    }
+
+    fn point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: Some(id.into()),
+            score,
+            ..Default::default()
+        }
+    }
+
+    fn ids(hits: &[FederatedHit]) -> Vec<PointId> {
+        hits.iter().filter_map(|hit| hit.point.id.clone()).collect()
+    }
+
+    #[test]
+    fn rrf_merges_duplicates_with_provenance() {
+        let targets = [target("a", 1.0), target("b", 1.0)];
+        let results = vec![
+            vec![point(1, 0.9), point(2, 0.8)],
+            vec![point(2, 0.7), point(3, 0.6)],
+        ];
+
+        let hits = fuse(
+            &targets,
+            results,
+            &FederatedFusion::default(),
+            ScoreOrder::Descending,
+            &DedupeKey::PointId,
+            0,
+            10,
+        );
+
+        assert_eq!(ids(&hits), vec![2.into(), 1.into(), 3.into()]);
+        // 1 / (2 + 2) + 1 / (2 + 1)
+        assert!((hits[0].point.score - (0.25 + 1.0 / 3.0)).abs() < 1e-6);
+        assert_eq!(
+            hits[0]
+                .sources
+                .iter()
+                .map(|source| (source.collection_name.as_str(), source.rank))
+                .collect::<Vec<_>>(),
+            vec![("a", 1), ("b", 0)],
+        );
+    }
+
+    #[test]
+    fn weighted_normalization_respects_weights_and_limit() {
+        let targets = [target("a", 1.0), target("b", 3.0)];
+        let results = vec![
+            vec![point(1, 100.0), point(2, 50.0)],
+            vec![point(3, 0.2), point(4, 0.1)],
+        ];
+
+        let hits = fuse(
+            &targets,
+            results,
+            &FederatedFusion::WeightedNormalized,
+            ScoreOrder::Descending,
+            &DedupeKey::PointId,
+            0,
+            2,
+        );
+
+        assert_eq!(ids(&hits), vec![3.into(), 1.into()]);
+        assert_eq!(hits[0].point.score, 3.0);
+        assert_eq!(hits[1].point.score, 1.0);
+    }
+
+    #[test]
+    fn ascending_scores_are_inverted() {
+        let targets = [target("a", 1.0)];
+        // Ascending scores, as returned for euclidean distance
+        let results = vec![vec![point(1, 0.1), point(2, 0.5), point(3, 0.9)]];
+
+        let hits = fuse(
+            &targets,
+            results,
+            &FederatedFusion::Dbsf,
+            ScoreOrder::Ascending,
+            &DedupeKey::PointId,
+            0,
+            10,
+        );
+
+        assert_eq!(ids(&hits), vec![1.into(), 2.into(), 3.into()]);
+        assert!(hits[0].point.score > hits[2].point.score);
+    }
+
+    #[test]
+    fn dedupe_by_payload_field() {
+        let with_document = |id: u64, score: f32, document: &str| ScoredPoint {
+            payload: HashMap::from([("document".to_string(), Value::from(document))]),
+            ..point(id, score)
+        };
+        let targets = [target("a", 1.0), target("b", 1.0)];
+        let results = vec![
+            vec![with_document(1, 0.9, "x"), point(2, 0.5)],
+            vec![with_document(7, 0.8, "x"), point(2, 0.4)],
+        ];
+
+        let hits = fuse(
+            &targets,
+            results.clone(),
+            &FederatedFusion::default(),
+            ScoreOrder::Descending,
+            &DedupeKey::PayloadField("document".to_string()),
+            0,
+            10,
+        );
+        // Both hits of document "x" merge, points without the field stay separate
+        assert_eq!(hits.len(), 3);
+        assert_eq!(hits[0].sources.len(), 2);
+        assert_eq!(hits[0].point.id, Some(1.into()));
+
+        let hits = fuse(
+            &targets,
+            results,
+            &FederatedFusion::default(),
+            ScoreOrder::Descending,
+            &DedupeKey::None,
+            0,
+            10,
+        );
+        assert_eq!(hits.len(), 4);
+    }
+
+    #[test]
+    fn dedupe_by_object_payload_field() {
+        // Equal objects built separately, their fields iterate in different orders
+        let with_source = |id: u64, score: f32| {
+            let source: HashMap<String, Value> = (0..16)
+                .map(|i| (format!("key_{i}"), Value::from(i as i64)))
+                .collect();
+            ScoredPoint {
+                payload: HashMap::from([(
+                    "source".to_string(),
+                    Value::from(crate::Payload::from(source)),
+                )]),
+                ..point(id, score)
+            }
+        };
+        let targets = [target("a", 1.0), target("b", 1.0)];
+        let results = vec![vec![with_source(1, 0.9)], vec![with_source(2, 0.8)]];
+
+        let hits = fuse(
+            &targets,
+            results,
+            &FederatedFusion::default(),
+            ScoreOrder::Descending,
+            &DedupeKey::PayloadField("source".to_string()),
+            0,
+            10,
+        );
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].sources.len(), 2);
+    }
+
+    #[test]
+    fn offset_applies_to_fused_result() {
+        let targets = [target("a", 1.0), target("b", 1.0)];
+        // Each target returns `offset + limit` hits
+        let results = vec![
+            vec![point(1, 0.9), point(2, 0.8), point(3, 0.7)],
+            vec![point(11, 0.9), point(12, 0.8), point(13, 0.7)],
+        ];
+
+        let hits = fuse(
+            &targets,
+            results,
+            &FederatedFusion::default(),
+            ScoreOrder::Descending,
+            &DedupeKey::None,
+            2,
+            1,
+        );
+
+        assert_eq!(ids(&hits), vec![2.into()]);
+    }
+
+    #[test]
+    fn same_ids_of_different_collections_are_kept_apart_by_default() {
+        let targets = [target("a", 1.0), target("b", 1.0)];
+        let results = vec![vec![point(1, 0.9)], vec![point(1, 0.8)]];
+
+        let hits = fuse(
+            &targets,
+            results,
+            &FederatedFusion::default(),
+            ScoreOrder::Descending,
+            &DedupeKey::default(),
+            0,
+            10,
+        );
+
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].sources[0].collection_name, "a");
+        assert_eq!(hits[1].sources[0].collection_name, "b");
+    }
+}
@@ -0,0 +1,528 @@
+use std::collections::HashMap;
+
+use prost::Message;
+
+use crate::qdrant::{
+    Condition, Filter, Fusion, PrefetchQuery, Query, QueryBatchPoints, QueryPoints, Rrf,
+    ScoredPoint, SearchParams, Usage, VectorInput, WithPayloadSelector, WithVectorsSelector,
+};
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+/// A branch of a [`HybridQuery`], searching a single named vector
+///
+/// ```rust
+/// use qdrant_client::hybrid::HybridBranch;
+/// use qdrant_client::qdrant::{Condition, Filter};
+///
+/// let dense = HybridBranch::dense("dense", vec![0.1, 0.2, 0.3]).limit(50);
+/// let sparse = HybridBranch::sparse("bm25", vec![12, 42], vec![0.5, 0.3])
+///     .limit(50)
+///     .filter(Filter::must([Condition::matches("lang", "en".to_string())]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct HybridBranch {
+    /// Name to annotate hits with, defaults to the vector name
+    pub name: String,
+    /// Name of the vector to search
+    pub using: String,
+    /// Input to search the nearest points for
+    pub input: VectorInput,
+    /// Number of candidates this branch contributes to fusion
+    pub limit: Option<u64>,
+    /// Filter applied to this branch only
+    pub filter: Option<Filter>,
+    /// Search params of this branch
+    pub params: Option<SearchParams>,
+    /// Minimal score of candidates of this branch
+    pub score_threshold: Option<f32>,
+}
+
+impl HybridBranch {
+    /// Search a named vector with any input, such as a document to embed server-side
+    pub fn new(using: impl Into<String>, input: impl Into<VectorInput>) -> Self {
+        let using = using.into();
+        Self {
+            name: using.clone(),
+            using,
+            input: input.into(),
+            limit: None,
+            filter: None,
+            params: None,
+            score_threshold: None,
+        }
+    }
+
+    /// Search a named dense vector
+    pub fn dense(using: impl Into<String>, vector: impl Into<Vec<f32>>) -> Self {
+        Self::new(using, VectorInput::new_dense(vector))
+    }
+
+    /// Search a named sparse vector
+    pub fn sparse(
+        using: impl Into<String>,
+        indices: impl Into<Vec<u32>>,
+        values: impl Into<Vec<f32>>,
+    ) -> Self {
+        Self::new(using, VectorInput::new_sparse(indices, values))
+    }
+
+    /// Set the name to annotate hits with
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the number of candidates this branch contributes to fusion
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set a filter applied to this branch only
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Set the search params of this branch
+    pub fn params(mut self, params: impl Into<SearchParams>) -> Self {
+        self.params = Some(params.into());
+        self
+    }
+
+    /// Set the minimal score of candidates of this branch
+    pub fn score_threshold(mut self, score_threshold: f32) -> Self {
+        self.score_threshold = Some(score_threshold);
+        self
+    }
+
+    fn prefetch(&self) -> PrefetchQuery {
+        PrefetchQuery {
+            query: Some(Query::new_nearest(self.input.clone())),
+            using: Some(self.using.clone()),
+            filter: self.filter.clone(),
+            params: self.params,
+            score_threshold: self.score_threshold,
+            limit: self.limit,
+            ..Default::default()
+        }
+    }
+}
+
+/// Late interaction rerank stage of a [`HybridQuery`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct HybridRerank {
+    /// Name of the multivector to rerank with
+    pub using: String,
+    /// Input to rerank with, usually a multivector
+    pub input: VectorInput,
+    /// Number of fused candidates to rerank
+    pub candidates: u64,
+}
+
+/// Hybrid search over dense, sparse and optionally late interaction vectors
+///
+/// Builds the nested [`QueryPoints`] for hybrid search. Each [branch](HybridBranch) becomes a
+/// prefetch, whose candidates are fused with RRF or DBSF. With a [rerank](Self::rerank) stage, the
+/// fused candidates are reranked with a multivector.
+///
+/// Run it with [`Qdrant::hybrid_query`] to get hits annotated with the branches they matched in,
+/// or convert it into [`QueryPoints`] to run it as a plain query.
+///
+/// ```rust
+/// use qdrant_client::hybrid::{HybridBranch, HybridQuery};
+/// use qdrant_client::qdrant::{Fusion, QueryPoints};
+///
+/// let query = HybridQuery::new("my_collection")
+///     .branch(HybridBranch::dense("dense", vec![0.1, 0.2, 0.3]).limit(50))
+///     .branch(HybridBranch::sparse("bm25", vec![12, 42], vec![0.5, 0.3]).limit(50))
+///     .fusion(Fusion::Dbsf)
+///     .rerank("colbert", vec![vec![0.1, 0.2], vec![0.3, 0.4]], 20)
+///     .limit(10)
+///     .with_payload(true);
+///
+/// let request: QueryPoints = query.into();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct HybridQuery {
+    /// Collection to query
+    pub collection_name: String,
+    /// Branches whose candidates are fused
+    pub branches: Vec<HybridBranch>,
+    /// Query fusing the branches, defaults to RRF
+    pub fusion: Query,
+    /// Optional rerank stage after fusion
+    pub rerank: Option<HybridRerank>,
+    /// Filter applied to all branches
+    pub filter: Option<Filter>,
+    /// Number of hits to return
+    pub limit: Option<u64>,
+    /// Options for specifying which payload to include or not
+    pub with_payload: Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include into response
+    pub with_vectors: Option<WithVectorsSelector>,
+}
+
+impl HybridQuery {
+    /// Create a hybrid query without branches
+    pub fn new(collection_name: impl Into<String>) -> Self {
+        Self {
+            collection_name: collection_name.into(),
+            branches: Vec::new(),
+            fusion: Query::new_fusion(Fusion::Rrf),
+            rerank: None,
+            filter: None,
+            limit: None,
+            with_payload: None,
+            with_vectors: None,
+        }
+    }
+
+    /// Add a branch whose candidates are fused
+    pub fn branch(mut self, branch: HybridBranch) -> Self {
+        self.branches.push(branch);
+        self
+    }
+
+    /// Fuse branches with the given fusion method
+    pub fn fusion(mut self, fusion: Fusion) -> Self {
+        self.fusion = Query::new_fusion(fusion);
+        self
+    }
+
+    /// Fuse branches with parameterized RRF, for example to weigh branches
+    pub fn rrf(mut self, rrf: impl Into<Rrf>) -> Self {
+        self.fusion = Query::new_rrf(rrf);
+        self
+    }
+
+    /// Rerank the best fused candidates with a late interaction multivector
+    pub fn rerank(
+        mut self,
+        using: impl Into<String>,
+        input: impl Into<VectorInput>,
+        candidates: u64,
+    ) -> Self {
+        self.rerank = Some(HybridRerank {
+            using: using.into(),
+            input: input.into(),
+            candidates,
+        });
+        self
+    }
+
+    /// Set a filter applied to all branches
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Set the number of hits to return
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set which payload to return with hits
+    pub fn with_payload(mut self, with_payload: impl Into<WithPayloadSelector>) -> Self {
+        self.with_payload = Some(with_payload.into());
+        self
+    }
+
+    /// Set which vectors to return with hits
+    pub fn with_vectors(mut self, with_vectors: impl Into<WithVectorsSelector>) -> Self {
+        self.with_vectors = Some(with_vectors.into());
+        self
+    }
+
+    /// Query returning the candidates of a single branch, as fused by the hybrid query
+    fn branch_query(&self, branch: &HybridBranch) -> QueryPoints {
+        let filter = match (&self.filter, &branch.filter) {
+            (Some(filter), Some(branch_filter)) => Some(Filter::must([
+                Condition::from(filter.clone()),
+                Condition::from(branch_filter.clone()),
+            ])),
+            (filter, branch_filter) => filter.clone().or_else(|| branch_filter.clone()),
+        };
+
+        QueryPoints {
+            collection_name: self.collection_name.clone(),
+            query: Some(Query::new_nearest(branch.input.clone())),
+            using: Some(branch.using.clone()),
+            filter,
+            params: branch.params,
+            score_threshold: branch.score_threshold,
+            limit: branch.limit,
+            with_payload: Some(false.into()),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<HybridQuery> for QueryPoints {
+    fn from(query: HybridQuery) -> Self {
+        let prefetch: Vec<_> = query.branches.iter().map(HybridBranch::prefetch).collect();
+
+        let (prefetch, query_input, using) = match query.rerank {
+            Some(rerank) => {
+                let fused = PrefetchQuery {
+                    prefetch,
+                    query: Some(query.fusion),
+                    limit: Some(rerank.candidates),
+                    ..Default::default()
+                };
+                (
+                    vec![fused],
+                    Query::new_nearest(rerank.input),
+                    Some(rerank.using),
+                )
+            }
+            None => (prefetch, query.fusion, None),
+        };
+
+        QueryPoints {
+            collection_name: query.collection_name,
+            prefetch,
+            query: Some(query_input),
+            using,
+            filter: query.filter,
+            limit: query.limit,
+            with_payload: query.with_payload,
+            with_vectors: query.with_vectors,
+            ..Default::default()
+        }
+    }
+}
+
+/// A branch a [`HybridHit`] was a candidate of
+#[derive(Clone, Debug, PartialEq)]
+pub struct BranchMatch {
+    /// Name of the branch
+    pub name: String,
+    /// Zero-based rank of the hit within the branch candidates
+    pub rank: usize,
+    /// Score of the hit within the branch
+    pub score: f32,
+}
+
+/// Hit of a [`HybridQuery`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct HybridHit {
+    /// The hit, scored by the fusion or rerank stage
+    pub point: ScoredPoint,
+    /// Branches the hit was a candidate of, in branch order
+    pub branches: Vec<BranchMatch>,
+}
+
+/// Response of a [`HybridQuery`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct HybridResponse {
+    /// Hits, best first
+    pub result: Vec<HybridHit>,
+    /// Time spent to process the query
+    pub time: f64,
+    /// Resources used by the query
+    pub usage: Option<Usage>,
+}
+
+/// # Hybrid query
+///
+/// Hybrid search over dense, sparse and late interaction vectors.
+impl Qdrant {
+    /// Run a hybrid query, annotating hits with the branches they matched in.
+    ///
+    /// The hybrid query and the candidates of each branch are retrieved in a single
+    /// [`query_batch`](Self::query_batch) call.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::hybrid::{HybridBranch, HybridQuery};
+    ///
+    ///# async fn hybrid_query(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let response = client
+    ///     .hybrid_query(
+    ///         HybridQuery::new("my_collection")
+    ///             .branch(HybridBranch::dense("dense", vec![0.1, 0.2, 0.3]).limit(50))
+    ///             .branch(HybridBranch::sparse("bm25", vec![12, 42], vec![0.5, 0.3]).limit(50))
+    ///             .limit(10),
+    ///     )
+    ///     .await?;
+    ///
+    /// for hit in response.result {
+    ///     let branches: Vec<_> = hit.branches.iter().map(|branch| &branch.name).collect();
+    ///     println!("{:?} matched in {branches:?}", hit.point.id);
+    /// }
+    ///# Ok(())
+    ///# }
+    /// ```
+    ///
+    /// Documentation: <https://qdrant.tech/documentation/concepts/hybrid-queries/>
+    pub async fn hybrid_query(&self, query: HybridQuery) -> QdrantResult<HybridResponse> {
+        let branch_names: Vec<_> = query.branches.iter().map(|b| b.name.clone()).collect();
+        let branch_queries: Vec<_> = query
+            .branches
+            .iter()
+            .map(|branch| query.branch_query(branch))
+            .collect();
+        let collection_name = query.collection_name.clone();
+
+        let mut query_points = vec![QueryPoints::from(query)];
+        query_points.extend(branch_queries);
+        let count = query_points.len();
+
+        let response = self
+            .query_batch(QueryBatchPoints {
+                collection_name,
+                query_points,
+                ..Default::default()
+            })
+            .await?;
+        if response.result.len() != count {
+            return Err(QdrantError::ConversionError(format!(
+                "expected {count} query results in batch, got {}",
+                response.result.len(),
+            )));
+        }
+
+        let mut results = response.result.into_iter().map(|batch| batch.result);
+        let hits = results.next().unwrap_or_default();
+        let result = annotate_hits(hits, &branch_names, results.collect());
+
+        Ok(HybridResponse {
+            result,
+            time: response.time,
+            usage: response.usage,
+        })
+    }
+}
+
+/// Annotate each hit with the branches it is a candidate of
+fn annotate_hits(
+    hits: Vec<ScoredPoint>,
+    branch_names: &[String],
+    branch_results: Vec<Vec<ScoredPoint>>,
+) -> Vec<HybridHit> {
+    let mut matches: HashMap<Vec<u8>, Vec<BranchMatch>> = HashMap::new();
+    for (name, candidates) in branch_names.iter().zip(branch_results) {
+        for (rank, candidate) in candidates.into_iter().enumerate() {
+            let Some(id) = &candidate.id else { continue };
+            matches
+                .entry(id.encode_to_vec())
+                .or_default()
+                .push(BranchMatch {
+                    name: name.clone(),
+                    rank,
+                    score: candidate.score,
+                });
+        }
+    }
+
+    hits.into_iter()
+        .map(|point| HybridHit {
+            branches: point
+                .id
+                .as_ref()
+                .and_then(|id| matches.remove(&id.encode_to_vec()))
+                .unwrap_or_default(),
+            point,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::{PrefetchQueryBuilder, QueryPointsBuilder};
+
+    #[test]
+    fn builds_nested_query_with_rerank() {
+        let filter = Filter::must([Condition::matches("city", "Berlin".to_string())]);
+        let query: QueryPoints = HybridQuery::new("collection")
+            .branch(HybridBranch::dense("dense", vec![0.1, 0.2]).limit(50))
+            .branch(
+                HybridBranch::sparse("bm25", vec![1], vec![0.5])
+                    .limit(40)
+                    .filter(filter.clone()),
+            )
+            .fusion(Fusion::Dbsf)
+            .rerank("colbert", VectorInput::new_multi(vec![vec![0.3, 0.4]]), 20)
+            .limit(5)
+            .into();
+
+        let expected = QueryPointsBuilder::new("collection")
+            .add_prefetch(
+                PrefetchQueryBuilder::default()
+                    .add_prefetch(
+                        PrefetchQueryBuilder::default()
+                            .query(Query::new_nearest(vec![0.1, 0.2]))
+                            .using("dense")
+                            .limit(50u64),
+                    )
+                    .add_prefetch(
+                        PrefetchQueryBuilder::default()
+                            .query(Query::new_nearest(VectorInput::new_sparse(
+                                vec![1],
+                                vec![0.5],
+                            )))
+                            .using("bm25")
+                            .filter(filter)
+                            .limit(40u64),
+                    )
+                    .query(Query::new_fusion(Fusion::Dbsf))
+                    .limit(20u64),
+            )
+            .query(Query::new_nearest(VectorInput::new_multi(vec![vec![
+                0.3, 0.4,
+            ]])))
+            .using("colbert")
+            .limit(5)
+            .build();
+
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn branch_query_combines_filters() {
+        let global = Filter::must([Condition::matches("tenant", 1)]);
+        let local = Filter::must([Condition::matches("lang", "en".to_string())]);
+        let query = HybridQuery::new("collection").filter(global.clone());
+        let branch = HybridBranch::dense("dense", vec![0.1]).filter(local.clone());
+
+        assert_eq!(
+            query.branch_query(&branch).filter,
+            Some(Filter::must([global.into(), local.into()])),
+        );
+    }
+
+    #[test]
+    fn hits_are_annotated_with_branches() {
+        let point = |id: u64, score: f32| ScoredPoint {
+            id: Some(id.into()),
+            score,
+            ..Default::default()
+        };
+        let names = ["dense".to_string(), "bm25".to_string()];
+
+        let hits = annotate_hits(
+            vec![point(1, 0.9), point(2, 0.5)],
+            &names,
+            vec![vec![point(2, 0.8), point(1, 0.7)], vec![point(1, 12.0)]],
+        );
+
+        let annotations: Vec<Vec<(&str, usize)>> = hits
+            .iter()
+            .map(|hit| {
+                hit.branches
+                    .iter()
+                    .map(|branch| (branch.name.as_str(), branch.rank))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            annotations,
+            vec![vec![("dense", 1), ("bm25", 0)], vec![("dense", 0)]],
+        );
+    }
+}
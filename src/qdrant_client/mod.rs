@@ -1,16 +1,34 @@
+pub mod batching;
 pub(crate) mod builders;
+#[cfg(all(feature = "download_snapshots", feature = "serde"))]
+pub mod cluster_snapshot;
 mod collection;
+pub mod collection_spec;
 pub mod config;
+pub mod content_hash;
 mod conversions;
 pub mod error;
+pub mod federated;
+pub mod hybrid;
 mod index;
 mod payload;
 mod points;
 mod query;
+pub mod rebalance;
+pub mod reindex;
+pub mod rerank;
+#[cfg(feature = "download_snapshots")]
+mod rest;
 mod search;
 mod sharding_keys;
 mod snapshot;
+pub mod snapshot_manager;
+pub mod tenant;
+#[cfg(feature = "serde")]
+pub mod typed;
 mod version_check;
+pub mod versioned;
+pub mod wait;
 
 use std::future::Future;
 use std::sync::Arc;
@@ -70,12 +88,26 @@ pub type QdrantBuilder = QdrantConfig;
 /// Categories:
 ///
 /// - [Collection operations](Self#collection-operations) - manage collections, aliases and cluster configuration
+/// - [Declarative collections](Self#declarative-collections) - create or reconcile collections from a spec
 /// - [Point operations](Self#point-operations) - manage points and vectors
 /// - [Payload operations](Self#payload-operations) - manage point payloads
 /// - [Query operations](Self#query-operations) - query (search) points using universal search
 /// - [Index operations](Self#index-operations) - manage field and payload indices
+/// - [Reindexing](Self#reindexing) - move the collection behind an alias to a new collection without downtime
 /// - [Snapshot operations](Self#snapshot-operations) - manage instance or collection snapshots
+/// - [Snapshot management](Self#snapshot-management) - create snapshots on a schedule with retention policies
+/// - [Cluster snapshots](Self#cluster-snapshots) - snapshot a collection on every node of a cluster
 /// - [Shard key operations](Self#sharding-key-operations) - manage shard keys
+/// - [Shard rebalancing](Self#shard-rebalancing) - balance shard replicas across the peers of a cluster
+/// - [Request batching](Self#request-batching) - automatically batch concurrent requests
+/// - [Federated query](Self#federated-query) - query multiple collections and fuse the results
+/// - [Reranked query](Self#reranked-query) - rerank query results client-side
+/// - [Content hash upserts](Self#content-hash-upserts) - upsert only points that changed
+/// - [Versioned upserts](Self#versioned-upserts) - write points with optimistic concurrency control
+/// - [Multitenancy](Self#multitenancy) - access a single tenant of a collection
+/// - [Typed points](Self#typed-point-operations) - query, scroll and retrieve points with deserialized payloads
+/// - [Hybrid query](Self#hybrid-query) - hybrid search over dense, sparse and late interaction vectors
+/// - [Waiting](Self#waiting) - wait for collections to become ready and for points to become visible
 ///
 /// Common operations include:
 ///
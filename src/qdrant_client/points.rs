@@ -6,6 +6,8 @@ use tonic::Status;
 
 use crate::auth::TokenInterceptor;
 use crate::qdrant::points_client::PointsClient;
+#[cfg(feature = "uuid")]
+use crate::qdrant::PointId;
 use crate::qdrant::{
     CountPoints, CountResponse, DeletePointVectors, DeletePoints, FacetCounts, FacetResponse,
     GetPoints, GetResponse, PointsOperationResponse, ScrollPoints, ScrollResponse,
@@ -156,6 +158,45 @@ impl Qdrant {
         .await
     }
 
+    /// Insert or update points in a collection, assigning random UUIDs to points without an ID.
+    ///
+    /// Returns the response along with the IDs of all points, in the order of the request.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::qdrant::{PointStruct, UpsertPointsBuilder};
+    ///
+    ///# async fn upsert_points_assign_ids(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let point = PointStruct {
+    ///     id: None,
+    ///     vectors: Some(vec![0.9, 0.1, 0.1].into()),
+    ///     payload: Default::default(),
+    /// };
+    ///
+    /// let (_response, ids) = client
+    ///     .upsert_points_assign_ids(UpsertPointsBuilder::new("my_collection", vec![point]).wait(true))
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    ///
+    /// <small><em>Requires `uuid` feature</em></small>
+    #[cfg(feature = "uuid")]
+    pub async fn upsert_points_assign_ids(
+        &self,
+        request: impl Into<UpsertPoints>,
+    ) -> QdrantResult<(PointsOperationResponse, Vec<PointId>)> {
+        let mut request = request.into();
+        let ids = request
+            .points
+            .iter_mut()
+            .map(|point| point.id.get_or_insert_with(PointId::random).clone())
+            .collect();
+        let response = self.upsert_points(request).await?;
+        Ok((response, ids))
+    }
+
     /// Retrieve specific points from a collection.
     ///
     /// Use [`with_vectors`](crate::qdrant::GetPointsBuilder::with_vectors) and
@@ -0,0 +1,747 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::time::Duration;
+
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use crate::qdrant::update_collection_cluster_setup_request::Operation;
+use crate::qdrant::{
+    AbortShardTransferBuilder, CollectionClusterInfoResponse, CollectionInfo, MoveShardBuilder,
+    ReplicaState, ReplicateShardBuilder, ShardKey, UpdateCollectionClusterSetupRequestBuilder,
+};
+use crate::qdrant_client::wait::Backoff;
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+/// Kind of shard transfer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShardMoveKind {
+    /// Move the replica, removing it from the source peer
+    Move,
+    /// Copy the replica, keeping it on the source peer
+    Replicate,
+}
+
+/// Transfer of a shard replica between peers
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShardMove {
+    pub collection_name: String,
+    pub shard_id: u32,
+    /// Shard key of the shard, for collections with custom sharding
+    pub shard_key: Option<ShardKey>,
+    pub from_peer_id: u64,
+    pub to_peer_id: u64,
+    pub kind: ShardMoveKind,
+}
+
+impl ShardMove {
+    /// Cluster operation performing this transfer
+    pub fn operation(&self) -> Operation {
+        match self.kind {
+            ShardMoveKind::Move => {
+                MoveShardBuilder::new(self.shard_id, self.from_peer_id, self.to_peer_id).into()
+            }
+            ShardMoveKind::Replicate => {
+                ReplicateShardBuilder::new(self.shard_id, self.from_peer_id, self.to_peer_id).into()
+            }
+        }
+    }
+}
+
+impl fmt::Display for ShardMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = match self.kind {
+            ShardMoveKind::Move => "move",
+            ShardMoveKind::Replicate => "replicate",
+        };
+        write!(
+            f,
+            "{verb} shard {} of `{}` from peer {} to peer {}",
+            self.shard_id, self.collection_name, self.from_peer_id, self.to_peer_id,
+        )
+    }
+}
+
+/// Shard transfers balancing replicas across peers, see [`RebalancePlanner`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RebalancePlan {
+    /// Transfers in the order they were planned
+    pub moves: Vec<ShardMove>,
+    /// Number of replicas per peer before the transfers
+    pub replicas_before: BTreeMap<u64, usize>,
+    /// Number of replicas per peer after the transfers
+    pub replicas_after: BTreeMap<u64, usize>,
+}
+
+impl RebalancePlan {
+    /// Whether the replicas are already balanced
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+}
+
+impl fmt::Display for RebalancePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            write!(f, "replicas are balanced")?;
+        } else {
+            write!(f, "{} shard transfers", self.moves.len())?;
+        }
+        for shard_move in &self.moves {
+            write!(f, "\n- {shard_move}")?;
+        }
+        for (peer_id, after) in &self.replicas_after {
+            let before = self
+                .replicas_before
+                .get(peer_id)
+                .copied()
+                .unwrap_or_default();
+            write!(f, "\npeer {peer_id}: {before} -> {after} replicas")?;
+        }
+        for (peer_id, before) in &self.replicas_before {
+            if !self.replicas_after.contains_key(peer_id) {
+                write!(f, "\npeer {peer_id}: {before} -> 0 replicas")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Planner of shard transfers balancing the number of replicas on each peer
+///
+/// Takes the cluster info of one or more collections, from
+/// [`collection_cluster_info`](Qdrant::collection_cluster_info), and the peers to place replicas
+/// on. The plan:
+///
+/// - moves all replicas off peers that are not listed, to drain them,
+/// - replicates shards with fewer replicas than the configured replication factor,
+/// - moves replicas from the most to the least loaded peers until replica counts differ by at
+///   most one, preferring shards of the collection and shard key most concentrated on the
+///   source peer. If no replica of the most loaded peer can move, the next most loaded peer is
+///   tried.
+///
+/// Every step is chosen greedily, so the plan is not guaranteed to be the shortest possible.
+/// A peer never holds two replicas of a shard, only active replicas are transferred, and shards
+/// with a transfer in progress are left alone. Replica counts may therefore remain unbalanced if
+/// no allowed move improves them.
+///
+/// ```rust
+/// use qdrant_client::qdrant::CollectionClusterInfoResponse;
+/// use qdrant_client::rebalance::RebalancePlanner;
+///
+///# fn plan(info: CollectionClusterInfoResponse) {
+/// let plan = RebalancePlanner::new([1, 2, 3])
+///     .collection("my_collection", info)
+///     .replication_factor("my_collection", 2)
+///     .plan();
+/// println!("{plan}");
+///# }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RebalancePlanner {
+    peers: BTreeSet<u64>,
+    collections: Vec<(String, CollectionClusterInfoResponse)>,
+    replication_factors: HashMap<String, usize>,
+}
+
+/// Replica of a shard on a peer, while planning
+#[derive(Clone, Debug)]
+struct PlacedReplica {
+    collection: usize,
+    shard_id: u32,
+    shard_key: Option<ShardKey>,
+    peer_id: u64,
+    active: bool,
+}
+
+impl RebalancePlanner {
+    /// Plan placing replicas on `peers`
+    pub fn new(peers: impl IntoIterator<Item = u64>) -> Self {
+        Self {
+            peers: peers.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a collection with its cluster info
+    pub fn collection(
+        mut self,
+        collection_name: impl Into<String>,
+        info: CollectionClusterInfoResponse,
+    ) -> Self {
+        self.collections.push((collection_name.into(), info));
+        self
+    }
+
+    /// Ensure each shard of a collection has at least `replication_factor` replicas
+    pub fn replication_factor(
+        mut self,
+        collection_name: impl Into<String>,
+        replication_factor: u32,
+    ) -> Self {
+        self.replication_factors
+            .insert(collection_name.into(), replication_factor as usize);
+        self
+    }
+
+    /// Add a collection with its cluster info and the replication factor of its config
+    ///
+    /// The replication factor defaults to 1, like on the server.
+    fn configured_collection(
+        self,
+        collection_name: String,
+        cluster_info: CollectionClusterInfoResponse,
+        info: &CollectionInfo,
+    ) -> Self {
+        let replication_factor = info
+            .config
+            .as_ref()
+            .and_then(|config| config.params.as_ref())
+            .and_then(|params| params.replication_factor)
+            .unwrap_or(1);
+        self.replication_factor(collection_name.clone(), replication_factor)
+            .collection(collection_name, cluster_info)
+    }
+
+    /// Compute the shard transfers
+    pub fn plan(&self) -> RebalancePlan {
+        let mut replicas = self.replicas();
+        let busy = self.busy_shards();
+        let replicas_before = self.load(&replicas, true);
+        let mut moves = Vec::new();
+
+        // Drain peers that are not listed
+        for index in 0..replicas.len() {
+            let replica = &replicas[index];
+            if self.peers.contains(&replica.peer_id)
+                || !replica.active
+                || busy.contains(&(replica.collection, replica.shard_id))
+            {
+                continue;
+            }
+            let Some(target) = self.least_loaded(&replicas, replica) else {
+                continue;
+            };
+            moves.push(self.transfer(replica, target, ShardMoveKind::Move));
+            replicas[index].peer_id = target;
+        }
+
+        // Add missing replicas
+        for (collection, (collection_name, _)) in self.collections.iter().enumerate() {
+            let Some(&factor) = self.replication_factors.get(collection_name) else {
+                continue;
+            };
+            let shard_ids: BTreeSet<u32> = replicas
+                .iter()
+                .filter(|replica| replica.collection == collection)
+                .map(|replica| replica.shard_id)
+                .collect();
+            for shard_id in shard_ids {
+                if busy.contains(&(collection, shard_id)) {
+                    continue;
+                }
+                loop {
+                    let holders: Vec<&PlacedReplica> = replicas
+                        .iter()
+                        .filter(|replica| {
+                            replica.collection == collection
+                                && replica.shard_id == shard_id
+                                && self.peers.contains(&replica.peer_id)
+                        })
+                        .collect();
+                    if holders.len() >= factor {
+                        break;
+                    }
+                    let Some(source) = holders.iter().find(|replica| replica.active) else {
+                        break;
+                    };
+                    let Some(target) = self.least_loaded(&replicas, source) else {
+                        break;
+                    };
+                    let shard_move = self.transfer(source, target, ShardMoveKind::Replicate);
+                    let replica = PlacedReplica {
+                        peer_id: target,
+                        ..(*source).clone()
+                    };
+                    moves.push(shard_move);
+                    replicas.push(replica);
+                }
+            }
+        }
+
+        // Balance replica counts, from the most loaded peer whose replicas can move
+        loop {
+            let load = self.load(&replicas, false);
+            let mut sources: Vec<(u64, usize)> = load
+                .iter()
+                .map(|(&peer_id, &count)| (peer_id, count))
+                .collect();
+            sources.sort_by_key(|&(peer_id, count)| (std::cmp::Reverse(count), peer_id));
+            let Some((index, target)) = sources.into_iter().find_map(|(source, count)| {
+                self.best_candidate(&replicas, &busy, &load, source, count)
+            }) else {
+                break;
+            };
+            moves.push(self.transfer(&replicas[index], target, ShardMoveKind::Move));
+            replicas[index].peer_id = target;
+        }
+
+        RebalancePlan {
+            moves,
+            replicas_before,
+            replicas_after: self.load(&replicas, false),
+        }
+    }
+
+    /// All replicas of all collections
+    fn replicas(&self) -> Vec<PlacedReplica> {
+        let mut replicas = Vec::new();
+        for (collection, (_, info)) in self.collections.iter().enumerate() {
+            let mut add = |shard_id, shard_key: &Option<ShardKey>, peer_id, state| {
+                replicas.push(PlacedReplica {
+                    collection,
+                    shard_id,
+                    shard_key: shard_key.clone(),
+                    peer_id,
+                    active: state == ReplicaState::Active as i32,
+                })
+            };
+            for shard in &info.local_shards {
+                add(shard.shard_id, &shard.shard_key, info.peer_id, shard.state);
+            }
+            for shard in &info.remote_shards {
+                add(shard.shard_id, &shard.shard_key, shard.peer_id, shard.state);
+            }
+        }
+        replicas.sort_by_key(|replica| (replica.collection, replica.shard_id, replica.peer_id));
+        replicas
+    }
+
+    /// Shards with a transfer in progress
+    fn busy_shards(&self) -> BTreeSet<(usize, u32)> {
+        self.collections
+            .iter()
+            .enumerate()
+            .flat_map(|(collection, (_, info))| {
+                info.shard_transfers
+                    .iter()
+                    .map(move |transfer| (collection, transfer.shard_id))
+            })
+            .collect()
+    }
+
+    /// Number of replicas per listed peer, and per unlisted peer if `all_peers`
+    fn load(&self, replicas: &[PlacedReplica], all_peers: bool) -> BTreeMap<u64, usize> {
+        let mut load: BTreeMap<u64, usize> = self.peers.iter().map(|&peer| (peer, 0)).collect();
+        for replica in replicas {
+            if all_peers || self.peers.contains(&replica.peer_id) {
+                *load.entry(replica.peer_id).or_default() += 1;
+            }
+        }
+        load
+    }
+
+    /// Whether `peer_id` holds a replica of the shard of `replica`
+    fn holds(replicas: &[PlacedReplica], replica: &PlacedReplica, peer_id: u64) -> bool {
+        replicas.iter().any(|other| {
+            other.collection == replica.collection
+                && other.shard_id == replica.shard_id
+                && other.peer_id == peer_id
+        })
+    }
+
+    /// Least loaded listed peer not holding the shard of `replica`
+    fn least_loaded(&self, replicas: &[PlacedReplica], replica: &PlacedReplica) -> Option<u64> {
+        let load = self.load(replicas, false);
+        load.into_iter()
+            .filter(|&(peer_id, _)| !Self::holds(replicas, replica, peer_id))
+            .min_by_key(|&(peer_id, load)| (load, peer_id))
+            .map(|(peer_id, _)| peer_id)
+    }
+
+    /// Replica on `source` and target peer of the move best reducing the imbalance
+    fn best_candidate(
+        &self,
+        replicas: &[PlacedReplica],
+        busy: &BTreeSet<(usize, u32)>,
+        load: &BTreeMap<u64, usize>,
+        source: u64,
+        max: usize,
+    ) -> Option<(usize, u64)> {
+        // Replicas of the same collection and shard key on a peer
+        let group_count = |replica: &PlacedReplica, peer_id: u64| {
+            replicas
+                .iter()
+                .filter(|other| {
+                    other.peer_id == peer_id
+                        && other.collection == replica.collection
+                        && other.shard_key == replica.shard_key
+                })
+                .count() as isize
+        };
+
+        let mut targets: Vec<(u64, usize)> = load
+            .iter()
+            .filter(|&(_, &count)| count + 2 <= max)
+            .map(|(&peer_id, &count)| (peer_id, count))
+            .collect();
+        targets.sort_by_key(|&(peer_id, count)| (count, peer_id));
+
+        for (target, _) in targets {
+            let best = replicas
+                .iter()
+                .enumerate()
+                .filter(|(_, replica)| {
+                    replica.peer_id == source
+                        && replica.active
+                        && !busy.contains(&(replica.collection, replica.shard_id))
+                        && !Self::holds(replicas, replica, target)
+                })
+                .max_by_key(|(_, replica)| {
+                    let spread = group_count(replica, source) - group_count(replica, target);
+                    // Prefer the lowest collection and shard ID among equal candidates
+                    (
+                        spread,
+                        std::cmp::Reverse((replica.collection, replica.shard_id)),
+                    )
+                })
+                .map(|(index, _)| index);
+            if let Some(index) = best {
+                return Some((index, target));
+            }
+        }
+        None
+    }
+
+    fn transfer(&self, replica: &PlacedReplica, to_peer_id: u64, kind: ShardMoveKind) -> ShardMove {
+        ShardMove {
+            collection_name: self.collections[replica.collection].0.clone(),
+            shard_id: replica.shard_id,
+            shard_key: replica.shard_key.clone(),
+            from_peer_id: replica.peer_id,
+            to_peer_id,
+            kind,
+        }
+    }
+}
+
+/// # Shard rebalancing
+///
+/// Plan and perform shard transfers balancing replicas across the peers of a cluster.
+///
+/// Documentation: <https://qdrant.tech/documentation/guides/distributed_deployment/#moving-shards>
+impl Qdrant {
+    /// Plan balancing the replicas of collections across `peers`, without transferring anything.
+    ///
+    /// Reads the cluster info and configured replication factor of each collection and plans with
+    /// a [`RebalancePlanner`].
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    ///# async fn plan_rebalance(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let plan = client
+    ///     .plan_rebalance(["articles", "images"], [5531049232473152, 2278839421957733])
+    ///     .await?;
+    /// println!("{plan}");
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn plan_rebalance(
+        &self,
+        collection_names: impl IntoIterator<Item = impl Into<String>>,
+        peers: impl IntoIterator<Item = u64>,
+    ) -> QdrantResult<RebalancePlan> {
+        let mut planner = RebalancePlanner::new(peers);
+        for collection_name in collection_names {
+            let collection_name = collection_name.into();
+            let cluster_info = self.collection_cluster_info(&collection_name).await?;
+            let info = self
+                .collection_info(&collection_name)
+                .await?
+                .result
+                .unwrap_or_default();
+            planner = planner.configured_collection(collection_name, cluster_info, &info);
+        }
+        Ok(planner.plan())
+    }
+
+    /// Perform the shard transfers of a plan.
+    ///
+    /// Runs up to `max_concurrent_transfers` transfers at once, transfers of the same shard one
+    /// after another in plan order. Each transfer is monitored through the `shard_transfers` of
+    /// the collection cluster info until it completes, and the new replica is checked to be
+    /// active. A transfer not completing within `transfer_timeout` is aborted. Stops at the first
+    /// failing transfer, letting running ones complete.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use std::time::Duration;
+    ///
+    ///# async fn execute_rebalance(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let plan = client
+    ///     .plan_rebalance(["articles"], [5531049232473152, 2278839421957733])
+    ///     .await?;
+    /// client
+    ///     .execute_rebalance(&plan, 2, Duration::from_secs(3600))
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn execute_rebalance(
+        &self,
+        plan: &RebalancePlan,
+        max_concurrent_transfers: usize,
+        transfer_timeout: Duration,
+    ) -> QdrantResult<()> {
+        // Transfers of a shard depend on the ones planned before
+        let mut per_shard: BTreeMap<(&str, u32), Vec<&ShardMove>> = BTreeMap::new();
+        for shard_move in &plan.moves {
+            per_shard
+                .entry((&shard_move.collection_name, shard_move.shard_id))
+                .or_default()
+                .push(shard_move);
+        }
+
+        stream::iter(per_shard.into_values())
+            .map(|moves| async move {
+                for shard_move in moves {
+                    self.transfer_shard(shard_move, transfer_timeout).await?;
+                }
+                Ok::<_, QdrantError>(())
+            })
+            .buffer_unordered(max_concurrent_transfers.max(1))
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(())
+    }
+
+    /// Start a shard transfer and wait for it to complete
+    async fn transfer_shard(&self, shard_move: &ShardMove, timeout: Duration) -> QdrantResult<()> {
+        let collection_name = &shard_move.collection_name;
+        self.update_collection_cluster_setup(UpdateCollectionClusterSetupRequestBuilder::new(
+            collection_name,
+            shard_move.operation(),
+        ))
+        .await?;
+
+        let mut backoff = Backoff::new(timeout);
+        loop {
+            let info = self.collection_cluster_info(collection_name).await?;
+            let running = info.shard_transfers.iter().any(|transfer| {
+                transfer.shard_id == shard_move.shard_id
+                    && transfer.from == shard_move.from_peer_id
+                    && transfer.to == shard_move.to_peer_id
+            });
+            if !running {
+                let active = RebalancePlanner::new([])
+                    .collection(collection_name, info)
+                    .replicas()
+                    .iter()
+                    .any(|replica| {
+                        replica.shard_id == shard_move.shard_id
+                            && replica.peer_id == shard_move.to_peer_id
+                            && replica.active
+                    });
+                if active {
+                    return Ok(());
+                }
+                return Err(QdrantError::ShardTransferFailed(shard_move.to_string()));
+            }
+
+            if !backoff.wait().await {
+                self.update_collection_cluster_setup(
+                    UpdateCollectionClusterSetupRequestBuilder::new(
+                        collection_name,
+                        AbortShardTransferBuilder::new(
+                            shard_move.shard_id,
+                            shard_move.from_peer_id,
+                            shard_move.to_peer_id,
+                        ),
+                    ),
+                )
+                .await?;
+                return Err(QdrantError::Timeout(format!(
+                    "aborted transfer to {shard_move} after {timeout:?}"
+                )));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::{
+        CollectionConfig, CollectionParams, LocalShardInfo, RemoteShardInfo, ShardTransferInfo,
+    };
+
+    /// Cluster info seen from peer 1, with shards placed on the given peers
+    fn cluster_info(placement: &[(u32, u64)]) -> CollectionClusterInfoResponse {
+        let state = ReplicaState::Active as i32;
+        CollectionClusterInfoResponse {
+            peer_id: 1,
+            local_shards: placement
+                .iter()
+                .filter(|(_, peer_id)| *peer_id == 1)
+                .map(|&(shard_id, _)| LocalShardInfo {
+                    shard_id,
+                    state,
+                    ..Default::default()
+                })
+                .collect(),
+            remote_shards: placement
+                .iter()
+                .filter(|(_, peer_id)| *peer_id != 1)
+                .map(|&(shard_id, peer_id)| RemoteShardInfo {
+                    shard_id,
+                    peer_id,
+                    state,
+                    shard_key: None,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn plan_balances_new_peer() {
+        // Six shards on two peers, a third peer was just added
+        let mut articles = cluster_info(&[(0, 1), (1, 1), (2, 1), (3, 2), (4, 2), (5, 2)]);
+        let images = cluster_info(&[(0, 1), (1, 2)]);
+        // Shard 2 is being transferred and must stay where it is
+        articles.shard_transfers.push(ShardTransferInfo {
+            shard_id: 2,
+            from: 1,
+            to: 2,
+            ..Default::default()
+        });
+
+        let plan = RebalancePlanner::new([1, 2, 3])
+            .collection("articles", articles)
+            .collection("images", images)
+            .plan();
+        let moves: Vec<_> = plan.moves.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            moves,
+            [
+                "move shard 0 of `articles` from peer 1 to peer 3",
+                "move shard 3 of `articles` from peer 2 to peer 3",
+            ],
+        );
+        assert_eq!(
+            plan.replicas_after,
+            BTreeMap::from([(1, 3), (2, 3), (3, 2)])
+        );
+
+        let balanced = RebalancePlanner::new([1, 2])
+            .collection("images", cluster_info(&[(0, 1), (1, 2)]))
+            .plan();
+        assert!(balanced.is_empty());
+    }
+
+    #[test]
+    fn plan_drains_peers_and_adds_replicas() {
+        // Peer 4 is removed, shard 1 has a single replica
+        let info = cluster_info(&[(0, 1), (0, 4), (1, 2), (2, 3), (2, 4)]);
+        let plan = RebalancePlanner::new([1, 2, 3])
+            .collection("articles", info)
+            .replication_factor("articles", 2)
+            .plan();
+
+        assert!(plan
+            .moves
+            .iter()
+            .all(|shard_move| shard_move.to_peer_id != 4));
+        assert_eq!(
+            plan.moves[..2],
+            [
+                ShardMove {
+                    collection_name: "articles".to_string(),
+                    shard_id: 0,
+                    shard_key: None,
+                    from_peer_id: 4,
+                    to_peer_id: 2,
+                    kind: ShardMoveKind::Move,
+                },
+                ShardMove {
+                    collection_name: "articles".to_string(),
+                    shard_id: 2,
+                    shard_key: None,
+                    from_peer_id: 4,
+                    to_peer_id: 1,
+                    kind: ShardMoveKind::Move,
+                },
+            ],
+        );
+        assert_eq!(plan.moves[2].kind, ShardMoveKind::Replicate);
+        assert_eq!(plan.moves[2].shard_id, 1);
+        assert_eq!(plan.moves.len(), 3);
+        assert_eq!(plan.replicas_before[&4], 2);
+        assert_eq!(
+            plan.replicas_after,
+            BTreeMap::from([(1, 2), (2, 2), (3, 2)])
+        );
+        assert!(matches!(
+            plan.moves[2].operation(),
+            Operation::ReplicateShard(_)
+        ));
+    }
+
+    #[test]
+    fn plan_uses_configured_replication_factor() {
+        let info = CollectionInfo {
+            config: Some(CollectionConfig {
+                params: Some(CollectionParams {
+                    replication_factor: Some(2),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let plan = RebalancePlanner::new([1, 2])
+            .configured_collection("articles".to_string(), cluster_info(&[(0, 1)]), &info)
+            .plan();
+        let moves: Vec<_> = plan.moves.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            moves,
+            ["replicate shard 0 of `articles` from peer 1 to peer 2"]
+        );
+
+        // Without a configured factor a single replica is enough
+        let plan = RebalancePlanner::new([1, 2])
+            .configured_collection(
+                "articles".to_string(),
+                cluster_info(&[(0, 1)]),
+                &CollectionInfo::default(),
+            )
+            .plan();
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn plan_skips_blocked_most_loaded_peer() {
+        // Peers 1 and 2 hold three replicas each, but all shards of peer 1 are being transferred
+        let mut articles = cluster_info(&[(0, 1), (1, 1), (2, 1), (3, 2), (4, 2), (5, 2), (6, 3)]);
+        for shard_id in 0..3 {
+            articles.shard_transfers.push(ShardTransferInfo {
+                shard_id,
+                from: 1,
+                to: 3,
+                ..Default::default()
+            });
+        }
+
+        let plan = RebalancePlanner::new([1, 2, 3])
+            .collection("articles", articles)
+            .plan();
+        let moves: Vec<_> = plan.moves.iter().map(ToString::to_string).collect();
+        assert_eq!(moves, ["move shard 3 of `articles` from peer 2 to peer 3"]);
+        assert_eq!(
+            plan.replicas_after,
+            BTreeMap::from([(1, 3), (2, 2), (3, 2)]),
+        );
+    }
+}
@@ -0,0 +1,387 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+
+use crate::qdrant::{
+    AliasDescription, AliasTransaction, CreateShardKeyBuilder, CreateShardKeyRequestBuilder,
+    PointStruct, RetrievedPoint, ScrollPointsBuilder, ShardKey, UpsertPointsBuilder,
+};
+use crate::qdrant_client::collection_spec::{CollectionPlan, CollectionSpec};
+use crate::qdrant_client::wait::WaitCondition;
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+/// Default number of points copied per request
+const DEFAULT_BATCH_SIZE: u32 = 256;
+
+/// Default time to wait for the target collection to become green
+const DEFAULT_GREEN_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Transformation of points copied to the target collection, like computing new embeddings
+pub trait PointTransform: Send + Sync {
+    /// Points to write into the target collection, for a batch read from the source collection
+    ///
+    /// Points are read with their payload and vectors. For collections with custom sharding, all
+    /// points of a batch share a shard key, and are written to the same shard key.
+    fn transform<'a>(
+        &'a self,
+        points: Vec<RetrievedPoint>,
+    ) -> BoxFuture<'a, QdrantResult<Vec<PointStruct>>>;
+}
+
+/// Reindex of the collection behind an alias into a new collection, see [`Qdrant::reindex`]
+///
+/// ```rust
+/// use qdrant_client::collection_spec::CollectionSpec;
+/// use qdrant_client::qdrant::{CreateCollectionBuilder, Distance, VectorParamsBuilder};
+/// use qdrant_client::reindex::Reindex;
+///
+/// let spec = CollectionSpec::new(
+///     CreateCollectionBuilder::new("articles_v2")
+///         .vectors_config(VectorParamsBuilder::new(768, Distance::Cosine)),
+/// );
+/// let request = Reindex::new("articles", spec).batch_size(512);
+/// ```
+#[derive(Clone)]
+pub struct Reindex {
+    alias: String,
+    spec: CollectionSpec,
+    batch_size: u32,
+    transform: Option<Arc<dyn PointTransform>>,
+    green_timeout: Duration,
+}
+
+impl Reindex {
+    /// Reindex the collection behind `alias` into the collection described by `spec`
+    pub fn new(alias: impl Into<String>, spec: CollectionSpec) -> Self {
+        Self {
+            alias: alias.into(),
+            spec,
+            batch_size: DEFAULT_BATCH_SIZE,
+            transform: None,
+            green_timeout: DEFAULT_GREEN_TIMEOUT,
+        }
+    }
+
+    /// Number of points copied per request, defaults to 256
+    pub fn batch_size(mut self, batch_size: u32) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Transform points before writing them, by default points are copied as is
+    pub fn transform(mut self, transform: impl PointTransform + 'static) -> Self {
+        self.transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Maximum time to wait for the target collection to become green, defaults to 10 minutes
+    pub fn green_timeout(mut self, timeout: Duration) -> Self {
+        self.green_timeout = timeout;
+        self
+    }
+}
+
+/// Result of [`Qdrant::reindex`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReindexReport {
+    /// Collection the alias pointed to before, if it existed
+    ///
+    /// It is kept, delete it once requests started before the alias switch have finished.
+    pub source: Option<String>,
+    /// Collection the alias points to now
+    pub target: String,
+    /// Changes applied to create the target collection
+    pub plan: CollectionPlan,
+    /// Number of points written to the target collection
+    pub points_copied: u64,
+}
+
+/// # Reindexing
+///
+/// Move the collection behind an alias to a new collection without downtime.
+///
+/// Documentation: <https://qdrant.tech/documentation/concepts/collections/#collection-aliases>
+impl Qdrant {
+    /// Reindex the collection behind an alias into a new collection, and switch the alias.
+    ///
+    /// Creates the target collection with [`ensure_collection`](Self::ensure_collection), copies
+    /// all points from the collection the alias points to, optionally through a
+    /// [`PointTransform`], and waits for the target to become green. The alias, and the aliases of
+    /// the spec, are then moved to the target in a single atomic request, so queries through the
+    /// alias never fail or see a partially filled collection. Points written to the source during
+    /// the copy may be missed.
+    ///
+    /// An existing target collection is reused, which allows resuming an interrupted reindex. If
+    /// the alias does not exist yet, it is created. If it already points to the target, this fails
+    /// with [`QdrantError::AliasAlreadyPointsToTarget`]. Shard keys of collections with custom
+    /// sharding are kept.
+    ///
+    /// The source collection is not deleted, it is returned in the report so it can be deleted
+    /// after a grace period.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::collection_spec::CollectionSpec;
+    /// use qdrant_client::qdrant::{CreateCollectionBuilder, Distance, VectorParamsBuilder};
+    /// use qdrant_client::reindex::Reindex;
+    ///
+    ///# async fn reindex(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let spec = CollectionSpec::new(
+    ///     CreateCollectionBuilder::new("articles_v2")
+    ///         .vectors_config(VectorParamsBuilder::new(384, Distance::Dot)),
+    /// );
+    /// let report = client.reindex(Reindex::new("articles", spec)).await?;
+    /// println!("copied {} points", report.points_copied);
+    ///
+    /// // Later, once requests to the old collection have finished
+    /// if let Some(source) = report.source {
+    ///     client.delete_collection(source).await?;
+    /// }
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn reindex(&self, request: Reindex) -> QdrantResult<ReindexReport> {
+        let target = request.spec.collection_name().to_string();
+        let source = self
+            .list_aliases()
+            .await?
+            .aliases
+            .into_iter()
+            .find(|alias| alias.alias_name == request.alias)
+            .map(|alias| alias.collection_name);
+        if source.as_deref() == Some(target.as_str()) {
+            return Err(QdrantError::AliasAlreadyPointsToTarget {
+                alias: request.alias,
+                collection_name: target,
+            });
+        }
+
+        let plan = self
+            .ensure_collection(&request.spec.without_aliases())
+            .await?;
+
+        let mut points_copied = 0;
+        if let Some(source) = &source {
+            points_copied = self.copy_points(source, &target, &request).await?;
+        }
+        self.wait_for_collection(&target, [WaitCondition::Green], request.green_timeout)
+            .await?;
+
+        // Aliases may have changed during the copy
+        let existing = self.list_aliases().await?.aliases;
+        let aliases = std::iter::once(&request.alias).chain(request.spec.aliases());
+        self.change_aliases(alias_switch(aliases, &existing, &target))
+            .await?;
+
+        Ok(ReindexReport {
+            source,
+            target,
+            plan,
+            points_copied,
+        })
+    }
+
+    /// Copy all points of `source` into `target`, returning the number of points written
+    ///
+    /// Points of collections with custom sharding are written to the same shard key, which is
+    /// created in `target` if missing.
+    async fn copy_points(
+        &self,
+        source: &str,
+        target: &str,
+        request: &Reindex,
+    ) -> QdrantResult<u64> {
+        let mut copied = 0;
+        let mut offset = None;
+        let mut target_shard_keys = None;
+        loop {
+            let mut scroll = ScrollPointsBuilder::new(source)
+                .limit(request.batch_size)
+                .with_payload(true)
+                .with_vectors(true);
+            if let Some(offset) = offset.take() {
+                scroll = scroll.offset(offset);
+            }
+            let response = self.scroll(scroll).await?;
+
+            for (shard_key, points) in group_by_shard_key(response.result) {
+                let points = match &request.transform {
+                    Some(transform) => transform.transform(points).await?,
+                    None => points.into_iter().map(point_struct).collect(),
+                };
+                if points.is_empty() {
+                    continue;
+                }
+                copied += points.len() as u64;
+                let mut upsert = UpsertPointsBuilder::new(target, points).wait(true);
+                if let Some(shard_key) = shard_key {
+                    self.ensure_shard_key(target, &shard_key, &mut target_shard_keys)
+                        .await?;
+                    upsert = upsert.shard_key_selector(vec![shard_key]);
+                }
+                self.upsert_points(upsert).await?;
+            }
+
+            match response.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => return Ok(copied),
+            }
+        }
+    }
+
+    /// Create `shard_key` in `collection_name` unless it exists
+    ///
+    /// `known` caches the shard keys of the collection, it is fetched on first use.
+    async fn ensure_shard_key(
+        &self,
+        collection_name: &str,
+        shard_key: &ShardKey,
+        known: &mut Option<Vec<ShardKey>>,
+    ) -> QdrantResult<()> {
+        let known = match known {
+            Some(known) => known,
+            None => known.insert(
+                self.list_shard_keys(collection_name)
+                    .await?
+                    .shard_keys
+                    .into_iter()
+                    .filter_map(|description| description.key)
+                    .collect(),
+            ),
+        };
+        if !known.contains(shard_key) {
+            self.create_shard_key(
+                CreateShardKeyRequestBuilder::new(collection_name)
+                    .request(CreateShardKeyBuilder::default().shard_key(shard_key.clone())),
+            )
+            .await?;
+            known.push(shard_key.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Points grouped by their shard key, in order of appearance
+fn group_by_shard_key(points: Vec<RetrievedPoint>) -> Vec<(Option<ShardKey>, Vec<RetrievedPoint>)> {
+    let mut groups: Vec<(Option<ShardKey>, Vec<RetrievedPoint>)> = Vec::new();
+    for point in points {
+        match groups
+            .iter_mut()
+            .find(|(shard_key, _)| *shard_key == point.shard_key)
+        {
+            Some((_, group)) => group.push(point),
+            None => groups.push((point.shard_key.clone(), vec![point])),
+        }
+    }
+    groups
+}
+
+/// Point to write back with the ID, payload and vectors of a retrieved point
+fn point_struct(point: RetrievedPoint) -> PointStruct {
+    PointStruct {
+        id: point.id,
+        payload: point.payload,
+        vectors: point.vectors.map(Into::into),
+    }
+}
+
+/// Transaction pointing all `aliases` to `collection_name`, given the `existing` aliases
+fn alias_switch<'a>(
+    aliases: impl IntoIterator<Item = &'a String>,
+    existing: &[AliasDescription],
+    collection_name: &str,
+) -> AliasTransaction {
+    aliases
+        .into_iter()
+        .fold(AliasTransaction::new(), |transaction, alias| {
+            transaction.point_alias(alias, collection_name, existing)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::qdrant::{vectors, vectors_output, NamedVectorsOutput, PointId, VectorOutput};
+
+    #[test]
+    fn alias_switch_is_atomic() {
+        let aliases = ["articles".to_string(), "articles_latest".to_string()];
+        let existing = [AliasDescription {
+            alias_name: "articles".to_string(),
+            collection_name: "articles_v1".to_string(),
+        }];
+        assert_eq!(
+            alias_switch(&aliases, &existing, "articles_v2"),
+            AliasTransaction::new()
+                .delete_alias("articles")
+                .create_alias("articles_v2", "articles")
+                .create_alias("articles_v2", "articles_latest"),
+        );
+    }
+
+    #[test]
+    fn retrieved_point_copied_as_is() {
+        let point = RetrievedPoint {
+            id: Some(PointId::from(7)),
+            payload: HashMap::from([("title".to_string(), "Hello".into())]),
+            vectors: Some(crate::qdrant::VectorsOutput {
+                vectors_options: Some(vectors_output::VectorsOptions::Vectors(
+                    NamedVectorsOutput {
+                        vectors: HashMap::from([(
+                            "text".to_string(),
+                            VectorOutput {
+                                vector: Some(crate::qdrant::vector_output::Vector::Dense(
+                                    crate::qdrant::DenseVector {
+                                        data: vec![0.5, 0.25],
+                                    },
+                                )),
+                                ..Default::default()
+                            },
+                        )]),
+                    },
+                )),
+            }),
+            ..Default::default()
+        };
+
+        let copied = point_struct(point);
+        assert_eq!(copied.id, Some(PointId::from(7)));
+        assert_eq!(copied.payload["title"], "Hello".into());
+        let Some(vectors::VectorsOptions::Vectors(named)) = copied.vectors.unwrap().vectors_options
+        else {
+            panic!("expected named vectors");
+        };
+        assert_eq!(named.vectors["text"], vec![0.5, 0.25].into());
+    }
+
+    #[test]
+    fn points_grouped_by_shard_key() {
+        let point = |id: u64, shard_key: Option<&str>| RetrievedPoint {
+            id: Some(PointId::from(id)),
+            shard_key: shard_key.map(ShardKey::from),
+            ..Default::default()
+        };
+        let groups = group_by_shard_key(vec![
+            point(1, Some("eu")),
+            point(2, Some("us")),
+            point(3, Some("eu")),
+            point(4, None),
+        ]);
+        assert_eq!(
+            groups,
+            vec![
+                (
+                    Some(ShardKey::from("eu")),
+                    vec![point(1, Some("eu")), point(3, Some("eu"))],
+                ),
+                (Some(ShardKey::from("us")), vec![point(2, Some("us"))]),
+                (None, vec![point(4, None)]),
+            ],
+        );
+    }
+}
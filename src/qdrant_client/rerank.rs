@@ -0,0 +1,656 @@
+use futures::future::BoxFuture;
+
+use crate::qdrant::value::Kind;
+use crate::qdrant::vector_output::Vector;
+use crate::qdrant::with_payload_selector::SelectorOptions as PayloadSelectorOptions;
+use crate::qdrant::with_vectors_selector::SelectorOptions as VectorsSelectorOptions;
+use crate::qdrant::{
+    PayloadExcludeSelector, PayloadIncludeSelector, QueryPoints, QueryResponse, ScoredPoint, Value,
+    VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+};
+use crate::qdrant_client::{Qdrant, QdrantResult};
+
+/// Default number of results, equal to the server default
+const DEFAULT_LIMIT: u64 = 10;
+
+/// Context of the query whose results are reranked
+#[derive(Clone, Debug)]
+pub struct RerankContext<'a> {
+    /// The query as given by the caller
+    pub request: &'a QueryPoints,
+    /// Number of results that will be returned after reranking, excluding the offset
+    pub limit: usize,
+}
+
+/// Client-side reranking stage for query results
+///
+/// Attach a reranker to a query with [`Qdrant::query_reranked`]. The query over-fetches
+/// candidates, the reranker reorders them and the result is truncated to the requested limit.
+///
+/// Rerankers may call external services, such as a cross-encoder:
+///
+/// ```rust
+/// use futures::future::BoxFuture;
+/// use qdrant_client::qdrant::ScoredPoint;
+/// use qdrant_client::rerank::{RerankContext, Reranker};
+/// use qdrant_client::QdrantError;
+///
+/// struct CrossEncoder;
+///
+/// impl Reranker for CrossEncoder {
+///     fn rerank<'a>(
+///         &'a self,
+///         _context: &'a RerankContext<'a>,
+///         mut points: Vec<ScoredPoint>,
+///     ) -> BoxFuture<'a, Result<Vec<ScoredPoint>, QdrantError>> {
+///         Box::pin(async move {
+///             // Score the `text` payload of each point with the cross-encoder
+///             for point in &mut points {
+///                 point.score = 0.0;
+///             }
+///             points.sort_by(|a, b| b.score.total_cmp(&a.score));
+///             Ok(points)
+///         })
+///     }
+///
+///     fn payload_fields(&self) -> Vec<String> {
+///         vec!["text".to_string()]
+///     }
+/// }
+/// ```
+pub trait Reranker: Send + Sync {
+    /// Reorder the candidates, best first
+    ///
+    /// May change scores and drop candidates.
+    fn rerank<'a>(
+        &'a self,
+        context: &'a RerankContext<'a>,
+        points: Vec<ScoredPoint>,
+    ) -> BoxFuture<'a, QdrantResult<Vec<ScoredPoint>>>;
+
+    /// Top level payload fields the reranker reads
+    ///
+    /// These are fetched with the candidates, and removed again from the results if the caller
+    /// did not ask for them.
+    fn payload_fields(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Names of the vectors the reranker reads, an empty name is the default vector
+    ///
+    /// These are fetched with the candidates, and removed again from the results if the caller
+    /// did not ask for them.
+    fn vectors(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// # Reranked query
+///
+/// Rerank query results client-side.
+impl Qdrant {
+    /// Query points and rerank the results client-side.
+    ///
+    /// Fetches `candidates` points (at least the requested offset plus limit), reranks them with
+    /// the [`Reranker`] and returns the requested page of the reranked points. Payload fields and
+    /// vectors the reranker needs are fetched as well, and removed again if not requested.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::qdrant::QueryPointsBuilder;
+    /// use qdrant_client::rerank::MmrReranker;
+    ///
+    ///# async fn query_reranked(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let response = client
+    ///     .query_reranked(
+    ///         QueryPointsBuilder::new("my_collection")
+    ///             .query(vec![0.1, 0.2, 0.3])
+    ///             .using("dense")
+    ///             .limit(10)
+    ///             .with_payload(true),
+    ///         &MmrReranker::new("dense", 0.5),
+    ///         100,
+    ///     )
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn query_reranked(
+        &self,
+        request: impl Into<QueryPoints>,
+        reranker: &dyn Reranker,
+        candidates: u64,
+    ) -> QdrantResult<QueryResponse> {
+        let request = request.into();
+        let limit = request.limit.unwrap_or(DEFAULT_LIMIT);
+        let offset = request.offset.unwrap_or(0);
+        let payload_fields = reranker.payload_fields();
+        let vectors = reranker.vectors();
+
+        let fetch = QueryPoints {
+            limit: Some(candidates.max(offset + limit)),
+            offset: None,
+            with_payload: fetch_payload(&request.with_payload, &payload_fields),
+            with_vectors: fetch_vectors(&request.with_vectors, &vectors),
+            ..request.clone()
+        };
+        let response = self.query(fetch).await?;
+
+        let context = RerankContext {
+            request: &request,
+            limit: limit as usize,
+        };
+        let points = reranker.rerank(&context, response.result).await?;
+
+        let result = points
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|mut point| {
+                strip_payload(&mut point, &request.with_payload, &payload_fields);
+                strip_vectors(&mut point, &request.with_vectors, &vectors);
+                point
+            })
+            .collect();
+
+        Ok(QueryResponse {
+            result,
+            time: response.time,
+            usage: response.usage,
+        })
+    }
+}
+
+/// Payload selector including the fields a reranker needs
+fn fetch_payload(
+    selector: &Option<WithPayloadSelector>,
+    needed: &[String],
+) -> Option<WithPayloadSelector> {
+    if needed.is_empty() {
+        return selector.clone();
+    }
+
+    let options = match selector.as_ref().and_then(|s| s.selector_options.as_ref()) {
+        None | Some(PayloadSelectorOptions::Enable(false)) => {
+            PayloadSelectorOptions::Include(PayloadIncludeSelector::new(needed.to_vec()))
+        }
+        Some(PayloadSelectorOptions::Enable(true)) => PayloadSelectorOptions::Enable(true),
+        Some(PayloadSelectorOptions::Include(PayloadIncludeSelector { fields })) => {
+            let mut fields = fields.clone();
+            for field in needed {
+                if !fields.contains(field) {
+                    fields.push(field.clone());
+                }
+            }
+            PayloadSelectorOptions::Include(PayloadIncludeSelector { fields })
+        }
+        Some(PayloadSelectorOptions::Exclude(PayloadExcludeSelector { fields })) => {
+            let fields = fields.iter().filter(|f| !needed.contains(f)).cloned();
+            PayloadSelectorOptions::Exclude(PayloadExcludeSelector::new(fields.collect::<Vec<_>>()))
+        }
+    };
+    Some(options.into())
+}
+
+/// Remove payload fields only fetched for the reranker
+fn strip_payload(
+    point: &mut ScoredPoint,
+    selector: &Option<WithPayloadSelector>,
+    needed: &[String],
+) {
+    if needed.is_empty() {
+        return;
+    }
+
+    match selector.as_ref().and_then(|s| s.selector_options.as_ref()) {
+        None | Some(PayloadSelectorOptions::Enable(false)) => point.payload.clear(),
+        Some(PayloadSelectorOptions::Enable(true)) => {}
+        Some(PayloadSelectorOptions::Include(PayloadIncludeSelector { fields })) => {
+            point.payload.retain(|key, _| {
+                fields
+                    .iter()
+                    .any(|field| field == key || field.starts_with(&format!("{key}.")))
+            });
+        }
+        Some(PayloadSelectorOptions::Exclude(PayloadExcludeSelector { fields })) => {
+            point.payload.retain(|key, _| !fields.contains(key));
+        }
+    }
+}
+
+/// Vectors selector including the vectors a reranker needs
+fn fetch_vectors(
+    selector: &Option<WithVectorsSelector>,
+    needed: &[String],
+) -> Option<WithVectorsSelector> {
+    if needed.is_empty() {
+        return selector.clone();
+    }
+
+    let options = match selector.as_ref().and_then(|s| s.selector_options.as_ref()) {
+        // The default vector can only be selected by enabling all vectors
+        _ if needed.iter().any(String::is_empty) => VectorsSelectorOptions::Enable(true),
+        None | Some(VectorsSelectorOptions::Enable(false)) => {
+            VectorsSelectorOptions::Include(VectorsSelector::new(needed.to_vec()))
+        }
+        Some(VectorsSelectorOptions::Enable(true)) => VectorsSelectorOptions::Enable(true),
+        Some(VectorsSelectorOptions::Include(VectorsSelector { names })) => {
+            let mut names = names.clone();
+            for name in needed {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+            VectorsSelectorOptions::Include(VectorsSelector { names })
+        }
+    };
+    Some(WithVectorsSelector {
+        selector_options: Some(options),
+    })
+}
+
+/// Remove vectors only fetched for the reranker
+fn strip_vectors(
+    point: &mut ScoredPoint,
+    selector: &Option<WithVectorsSelector>,
+    needed: &[String],
+) {
+    use crate::qdrant::vectors_output::VectorsOptions;
+
+    if needed.is_empty() {
+        return;
+    }
+
+    match selector.as_ref().and_then(|s| s.selector_options.as_ref()) {
+        None | Some(VectorsSelectorOptions::Enable(false)) => point.vectors = None,
+        Some(VectorsSelectorOptions::Enable(true)) => {}
+        Some(VectorsSelectorOptions::Include(VectorsSelector { names })) => {
+            if let Some(VectorsOptions::Vectors(vectors)) = point
+                .vectors
+                .as_mut()
+                .and_then(|vectors| vectors.vectors_options.as_mut())
+            {
+                vectors.vectors.retain(|name, _| names.contains(name));
+            }
+        }
+    }
+}
+
+/// Min-max normalized scores, best is `1.0`
+///
+/// Results are ordered best first, which is ascending for distance based scores.
+fn normalized_scores(points: &[ScoredPoint]) -> Vec<f32> {
+    let ascending = matches!(points, [first, .., last] if first.score < last.score);
+    let scores: Vec<f32> = points
+        .iter()
+        .map(|point| if ascending { -point.score } else { point.score })
+        .collect();
+    let low = scores.iter().copied().fold(f32::INFINITY, f32::min);
+    let high = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    scores
+        .into_iter()
+        .map(|score| {
+            if high > low {
+                (score - low) / (high - low)
+            } else {
+                1.0
+            }
+        })
+        .collect()
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(a, b)| a * b).sum();
+    let norm_a = a.iter().map(|a| a * a).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|b| b * b).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Client-side maximal marginal relevance
+///
+/// Reorders candidates to balance relevance and diversity, based on the cosine similarity of a
+/// dense vector. Candidates without that vector are treated as dissimilar to all others. Scores
+/// are not changed.
+///
+/// Documentation: <https://qdrant.tech/documentation/concepts/hybrid-queries/#maximal-marginal-relevance-mmr>
+#[derive(Clone, Debug, PartialEq)]
+pub struct MmrReranker {
+    /// Name of the dense vector to compare candidates with, empty for the default vector
+    pub using: String,
+    /// Preference of diversity over relevance, from `0.0` (relevance only) to `1.0`
+    pub diversity: f32,
+}
+
+impl MmrReranker {
+    /// Rerank by the dense vector `using`, preferring diversity by `diversity` in `0.0..=1.0`
+    pub fn new(using: impl Into<String>, diversity: f32) -> Self {
+        Self {
+            using: using.into(),
+            diversity: diversity.clamp(0.0, 1.0),
+        }
+    }
+
+    fn rerank_sync(&self, points: Vec<ScoredPoint>) -> Vec<ScoredPoint> {
+        let relevance = normalized_scores(&points);
+        let vectors: Vec<Option<Vec<f32>>> = points
+            .iter()
+            .map(
+                |point| match point.vectors.as_ref()?.get_vector_by_name(&self.using)? {
+                    Vector::Dense(dense) => Some(dense.data),
+                    _ => None,
+                },
+            )
+            .collect();
+
+        let mut remaining: Vec<usize> = (0..points.len()).collect();
+        let mut selected: Vec<usize> = Vec::with_capacity(points.len());
+        // Highest similarity of each candidate to any selected candidate
+        let mut max_similarity = vec![0.0f32; points.len()];
+
+        while !remaining.is_empty() {
+            let (position, &best) = remaining
+                .iter()
+                .enumerate()
+                .max_by(|(_, &a), (_, &b)| {
+                    let mmr = |i: usize| {
+                        (1.0 - self.diversity) * relevance[i] - self.diversity * max_similarity[i]
+                    };
+                    // Prefer the earlier candidate on ties
+                    mmr(a).total_cmp(&mmr(b)).then(b.cmp(&a))
+                })
+                .expect("remaining candidates are not empty");
+            remaining.remove(position);
+            selected.push(best);
+
+            if let Some(best_vector) = &vectors[best] {
+                for &candidate in &remaining {
+                    if let Some(vector) = &vectors[candidate] {
+                        let similarity = cosine_similarity(best_vector, vector);
+                        max_similarity[candidate] = max_similarity[candidate].max(similarity);
+                    }
+                }
+            }
+        }
+
+        let mut points: Vec<Option<ScoredPoint>> = points.into_iter().map(Some).collect();
+        selected
+            .into_iter()
+            .filter_map(|index| points[index].take())
+            .collect()
+    }
+}
+
+impl Reranker for MmrReranker {
+    fn rerank<'a>(
+        &'a self,
+        _context: &'a RerankContext<'a>,
+        points: Vec<ScoredPoint>,
+    ) -> BoxFuture<'a, QdrantResult<Vec<ScoredPoint>>> {
+        Box::pin(async move { Ok(self.rerank_sync(points)) })
+    }
+
+    fn vectors(&self) -> Vec<String> {
+        vec![self.using.clone()]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum BoostRule {
+    Match {
+        key: String,
+        value: Value,
+        boost: f32,
+    },
+    Numeric {
+        key: String,
+        factor: f32,
+    },
+}
+
+/// Client-side score boost based on payload fields
+///
+/// Adds to the score of each candidate and reorders them by the boosted score. Assumes higher
+/// scores are better.
+///
+/// ```rust
+/// use qdrant_client::rerank::PayloadBoost;
+///
+/// let boost = PayloadBoost::new()
+///     .boost_match("category", "premium", 0.2)
+///     .boost_numeric("popularity", 0.001);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PayloadBoost {
+    rules: Vec<BoostRule>,
+}
+
+impl PayloadBoost {
+    /// Create a boost without rules, which keeps the order of candidates
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `boost` to the score if the top level payload field `key` is, or contains, `value`
+    pub fn boost_match(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<Value>,
+        boost: f32,
+    ) -> Self {
+        self.rules.push(BoostRule::Match {
+            key: key.into(),
+            value: value.into(),
+            boost,
+        });
+        self
+    }
+
+    /// Add the numeric top level payload field `key` multiplied by `factor` to the score
+    pub fn boost_numeric(mut self, key: impl Into<String>, factor: f32) -> Self {
+        self.rules.push(BoostRule::Numeric {
+            key: key.into(),
+            factor,
+        });
+        self
+    }
+
+    fn boost(&self, point: &ScoredPoint) -> f32 {
+        self.rules
+            .iter()
+            .map(|rule| match rule {
+                BoostRule::Match { key, value, boost } => match point.payload.get(key) {
+                    Some(Value {
+                        kind: Some(Kind::ListValue(list)),
+                    }) if list.values.contains(value) => *boost,
+                    Some(field) if field == value => *boost,
+                    _ => 0.0,
+                },
+                BoostRule::Numeric { key, factor } => {
+                    match point.payload.get(key).and_then(|value| value.kind.as_ref()) {
+                        Some(Kind::IntegerValue(integer)) => *integer as f32 * factor,
+                        Some(Kind::DoubleValue(double)) => *double as f32 * factor,
+                        _ => 0.0,
+                    }
+                }
+            })
+            .sum()
+    }
+
+    fn rerank_sync(&self, mut points: Vec<ScoredPoint>) -> Vec<ScoredPoint> {
+        for point in &mut points {
+            point.score += self.boost(point);
+        }
+        // Stable sort keeps the original order on equal scores
+        points.sort_by(|a, b| b.score.total_cmp(&a.score));
+        points
+    }
+}
+
+impl Reranker for PayloadBoost {
+    fn rerank<'a>(
+        &'a self,
+        _context: &'a RerankContext<'a>,
+        points: Vec<ScoredPoint>,
+    ) -> BoxFuture<'a, QdrantResult<Vec<ScoredPoint>>> {
+        Box::pin(async move { Ok(self.rerank_sync(points)) })
+    }
+
+    fn payload_fields(&self) -> Vec<String> {
+        let mut fields: Vec<String> = Vec::new();
+        for rule in &self.rules {
+            let (BoostRule::Match { key, .. } | BoostRule::Numeric { key, .. }) = rule;
+            if !fields.contains(key) {
+                fields.push(key.clone());
+            }
+        }
+        fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::qdrant::vectors_output::VectorsOptions;
+    use crate::qdrant::{NamedVectorsOutput, PointId, VectorOutput, VectorsOutput};
+
+    fn point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: Some(id.into()),
+            score,
+            ..Default::default()
+        }
+    }
+
+    fn with_vector(point: ScoredPoint, vector: Vec<f32>) -> ScoredPoint {
+        let vector = VectorOutput {
+            vector: Some(Vector::Dense(crate::qdrant::DenseVector { data: vector })),
+            ..Default::default()
+        };
+        ScoredPoint {
+            vectors: Some(VectorsOutput {
+                vectors_options: Some(VectorsOptions::Vectors(NamedVectorsOutput {
+                    vectors: HashMap::from([("dense".to_string(), vector)]),
+                })),
+            }),
+            ..point
+        }
+    }
+
+    fn include(fields: Vec<String>) -> WithPayloadSelector {
+        PayloadSelectorOptions::Include(PayloadIncludeSelector::new(fields)).into()
+    }
+
+    fn ids(points: &[ScoredPoint]) -> Vec<PointId> {
+        points.iter().filter_map(|point| point.id.clone()).collect()
+    }
+
+    #[test]
+    fn mmr_prefers_diverse_candidates() {
+        let points = vec![
+            with_vector(point(1, 0.9), vec![1.0, 0.0]),
+            with_vector(point(2, 0.85), vec![0.99, 0.01]),
+            with_vector(point(3, 0.7), vec![0.0, 1.0]),
+        ];
+
+        let relevance_only = MmrReranker::new("dense", 0.0).rerank_sync(points.clone());
+        assert_eq!(ids(&relevance_only), vec![1.into(), 2.into(), 3.into()]);
+
+        let diverse = MmrReranker::new("dense", 0.5).rerank_sync(points);
+        assert_eq!(ids(&diverse), vec![1.into(), 3.into(), 2.into()]);
+        // Scores are kept
+        assert_eq!(diverse[1].score, 0.7);
+    }
+
+    #[test]
+    fn payload_boost_reorders() {
+        let payload = |pairs: &[(&str, Value)]| -> HashMap<String, Value> {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect()
+        };
+        let points = vec![
+            point(1, 0.9),
+            ScoredPoint {
+                payload: payload(&[("tags", vec!["premium", "new"].into())]),
+                ..point(2, 0.8)
+            },
+            ScoredPoint {
+                payload: payload(&[("popularity", 300.into())]),
+                ..point(3, 0.5)
+            },
+        ];
+
+        let boost = PayloadBoost::new()
+            .boost_match("tags", "premium", 0.2)
+            .boost_numeric("popularity", 0.001);
+        let reranked = boost.rerank_sync(points);
+
+        assert_eq!(ids(&reranked), vec![2.into(), 1.into(), 3.into()]);
+        assert!((reranked[0].score - 1.0).abs() < 1e-6);
+        assert!((reranked[2].score - 0.8).abs() < 1e-6);
+        assert_eq!(boost.payload_fields(), vec!["tags", "popularity"]);
+    }
+
+    #[test]
+    fn needed_payload_is_fetched_and_stripped() {
+        let needed = vec!["tags".to_string()];
+
+        // Payload not requested
+        let fetch = fetch_payload(&None, &needed);
+        assert_eq!(fetch, Some(include(vec!["tags".to_string()])));
+        let mut hit = ScoredPoint {
+            payload: HashMap::from([("tags".to_string(), Value::from("a"))]),
+            ..point(1, 1.0)
+        };
+        strip_payload(&mut hit, &None, &needed);
+        assert!(hit.payload.is_empty());
+
+        // Other payload requested
+        let requested = Some(include(vec!["title".to_string()]));
+        let fetch = fetch_payload(&requested, &needed);
+        assert_eq!(
+            fetch,
+            Some(include(vec!["title".to_string(), "tags".to_string()]))
+        );
+        let mut hit = ScoredPoint {
+            payload: HashMap::from([
+                ("tags".to_string(), Value::from("a")),
+                ("title".to_string(), Value::from("b")),
+            ]),
+            ..point(1, 1.0)
+        };
+        strip_payload(&mut hit, &requested, &needed);
+        assert_eq!(hit.payload.keys().collect::<Vec<_>>(), vec!["title"]);
+
+        // Full payload requested
+        let requested = Some(true.into());
+        assert_eq!(fetch_payload(&requested, &needed), requested);
+    }
+
+    #[test]
+    fn needed_vectors_are_fetched_and_stripped() {
+        let needed = vec!["dense".to_string()];
+        assert_eq!(
+            fetch_vectors(&None, &needed),
+            Some(WithVectorsSelector {
+                selector_options: Some(VectorsSelectorOptions::Include(VectorsSelector::new(
+                    needed.clone()
+                ))),
+            })
+        );
+
+        let mut hit = with_vector(point(1, 1.0), vec![1.0]);
+        strip_vectors(&mut hit, &Some(true.into()), &needed);
+        assert!(hit.vectors.is_some());
+        strip_vectors(&mut hit, &None, &needed);
+        assert!(hit.vectors.is_none());
+    }
+}
@@ -0,0 +1,157 @@
+use reqwest::header::{HeaderMap, HeaderValue};
+use tonic::transport::Uri;
+
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+/// Default port of the Qdrant REST API
+const DEFAULT_REST_PORT: u16 = 6333;
+
+/// Access to the REST API, for transferring snapshot files which is not possible over gRPC
+impl Qdrant {
+    /// HTTP client authenticating with the API key of this client
+    ///
+    /// Only the `api-key` header and the connect timeout are taken from the client
+    /// configuration. The request timeout is not applied, as snapshot transfers may take long.
+    /// Server certificates are verified against the native root certificates, like the gRPC
+    /// channel does, and the bundled web PKI roots.
+    pub(crate) fn rest_client(&self) -> QdrantResult<reqwest::Client> {
+        let client = reqwest::Client::builder()
+            .default_headers(self.rest_headers()?)
+            .connect_timeout(self.config.connect_timeout)
+            .build()?;
+        Ok(client)
+    }
+
+    /// Headers sent with every REST request
+    fn rest_headers(&self) -> QdrantResult<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        if let Some(api_key) = &self.config.api_key {
+            let mut value = HeaderValue::from_str(api_key).map_err(|_| {
+                QdrantError::ConversionError("Malformed API key or token".to_string())
+            })?;
+            value.set_sensitive(true);
+            headers.insert("api-key", value);
+        }
+        Ok(headers)
+    }
+
+    /// REST API URI of the node, `rest_api_uri` if given
+    ///
+    /// Defaults to the host of the gRPC URI on the default REST port.
+    pub(crate) fn rest_api_uri(&self, rest_api_uri: Option<&str>) -> QdrantResult<String> {
+        if let Some(uri) = rest_api_uri {
+            return Ok(uri.trim_end_matches('/').to_string());
+        }
+
+        let uri = self.config.uri.parse::<Uri>()?;
+        let host = uri.host().ok_or_else(|| {
+            QdrantError::ConversionError(format!("No host in URI {}", self.config.uri))
+        })?;
+        Ok(format!(
+            "{}://{host}:{DEFAULT_REST_PORT}",
+            uri.scheme_str().unwrap_or("http"),
+        ))
+    }
+}
+
+/// Result of a REST API response, or its error message
+pub(crate) async fn rest_result(response: reqwest::Response) -> QdrantResult<serde_json::Value> {
+    let status = response.status();
+    let body = response.bytes().await?;
+    let json = serde_json::from_slice::<serde_json::Value>(&body).ok();
+    if status.is_success() {
+        return Ok(json
+            .and_then(|mut json| json.get_mut("result").map(serde_json::Value::take))
+            .unwrap_or_default());
+    }
+
+    let message = json
+        .as_ref()
+        .and_then(|json| json.pointer("/status/error")?.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| String::from_utf8_lossy(&body).into_owned());
+    Err(QdrantError::RestError { status, message })
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::codegen::http;
+
+    use super::*;
+
+    #[test]
+    fn rest_api_uri() {
+        let client = Qdrant::from_url("https://node-1.example.com:6334")
+            .skip_compatibility_check()
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.rest_api_uri(None).unwrap(),
+            "https://node-1.example.com:6333",
+        );
+        assert_eq!(
+            client.rest_api_uri(Some("http://other:1234/")).unwrap(),
+            "http://other:1234",
+        );
+
+        let client = Qdrant::from_url("http://[::1]:6334")
+            .skip_compatibility_check()
+            .build()
+            .unwrap();
+        assert_eq!(client.rest_api_uri(None).unwrap(), "http://[::1]:6333");
+    }
+
+    #[test]
+    fn rest_headers() {
+        let client = Qdrant::from_url("http://localhost:6334")
+            .api_key("secret")
+            .skip_compatibility_check()
+            .build()
+            .unwrap();
+        let headers = client.rest_headers().unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers["api-key"], "secret");
+        assert!(headers["api-key"].is_sensitive());
+
+        let anonymous = Qdrant::from_url("http://localhost:6334")
+            .skip_compatibility_check()
+            .build()
+            .unwrap();
+        assert!(anonymous.rest_headers().unwrap().is_empty());
+
+        let malformed = Qdrant::from_url("http://localhost:6334")
+            .api_key("line\nbreak")
+            .skip_compatibility_check()
+            .build()
+            .unwrap();
+        assert!(malformed.rest_headers().is_err());
+    }
+
+    #[tokio::test]
+    async fn rest_response_errors() {
+        let response = |status: u16, body: &'static str| {
+            reqwest::Response::from(http::Response::builder().status(status).body(body).unwrap())
+        };
+
+        let result = rest_result(response(200, r#"{"result":true,"status":"ok"}"#)).await;
+        assert_eq!(result.unwrap(), serde_json::Value::Bool(true));
+
+        let result = rest_result(response(
+            400,
+            r#"{"status":{"error":"Checksum mismatch"},"time":0.1}"#,
+        ))
+        .await;
+        assert!(matches!(
+            result,
+            Err(QdrantError::RestError { status, message })
+                if status == 400 && message == "Checksum mismatch",
+        ));
+
+        let result = rest_result(response(502, "Bad gateway")).await;
+        assert!(matches!(
+            result,
+            Err(QdrantError::RestError { message, .. }) if message == "Bad gateway",
+        ));
+    }
+}
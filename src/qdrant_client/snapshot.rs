@@ -1,3 +1,6 @@
+#[cfg(feature = "download_snapshots")]
+mod download;
+
 use std::future::Future;
 
 use tonic::codegen::InterceptedService;
@@ -6,12 +9,18 @@ use tonic::Status;
 
 use crate::auth::TokenInterceptor;
 use crate::qdrant::snapshots_client::SnapshotsClient;
+#[cfg(feature = "download_snapshots")]
+use crate::qdrant::SnapshotDescription;
 use crate::qdrant::{
     CreateFullSnapshotRequest, CreateSnapshotRequest, CreateSnapshotResponse,
     DeleteFullSnapshotRequest, DeleteSnapshotRequest, DeleteSnapshotResponse,
     ListFullSnapshotsRequest, ListSnapshotsRequest, ListSnapshotsResponse,
 };
+#[cfg(feature = "download_snapshots")]
+use crate::qdrant_client::rest::rest_result;
 use crate::qdrant_client::{Qdrant, QdrantResult};
+#[cfg(feature = "download_snapshots")]
+use crate::QdrantError;
 
 /// # Snapshot operations
 ///
@@ -99,7 +108,16 @@ impl Qdrant {
         .await
     }
 
-    /// Download a collection snapshot on this node.
+    /// Download a collection or full snapshot of a node.
+    ///
+    /// Downloads the newest snapshot unless a snapshot name is given. The download uses the
+    /// REST API of the node with the API key of this client, see
+    /// [`rest_api_uri`](crate::qdrant::SnapshotDownloadBuilder::rest_api_uri).
+    ///
+    /// The snapshot is streamed into `<out_path>.part`, which is renamed to the output path once
+    /// complete and verified against the SHA256 checksum reported by the server. A `.part` file
+    /// left by an interrupted download is continued with an HTTP range request. If the checksum
+    /// does not match, the partial file is removed and an error is returned.
     ///
     /// ```no_run
     ///# use std::fs::File;
@@ -112,8 +130,14 @@ impl Qdrant {
     ///     SnapshotDownloadBuilder::new("./target_path.snapshot", "my_collection")
     ///         .snapshot_name("snapshot_name")
     ///         .rest_api_uri("http://localhost:6333")
+    ///         .progress(|progress| {
+    ///             println!("{} of {:?} bytes", progress.downloaded, progress.total);
+    ///         })
     /// ).await?;
     ///
+    /// // Newest full snapshot of the node
+    /// client.download_snapshot(SnapshotDownloadBuilder::new_full("./full.snapshot")).await?;
+    ///
     /// let snapshot_file = File::open("./target_path.snapshot")?;
     ///# Ok(snapshot_file)
     ///# }
@@ -129,56 +153,190 @@ impl Qdrant {
         &self,
         download: impl Into<crate::qdrant::SnapshotDownload>,
     ) -> QdrantResult<()> {
-        use std::io::Write;
+        let options = download.into();
+        let (snapshot_name, snapshot) = self.snapshot_to_download(&options).await?;
 
-        use futures_util::StreamExt;
+        let rest_api_uri = self.rest_api_uri(options.rest_api_uri.as_deref())?;
+        let url = if options.full_snapshot {
+            format!("{rest_api_uri}/snapshots/{snapshot_name}")
+        } else {
+            format!(
+                "{rest_api_uri}/collections/{}/snapshots/{snapshot_name}",
+                options.collection_name,
+            )
+        };
 
-        use crate::qdrant_client::error::QdrantError;
+        download::download_snapshot_file(&self.rest_client()?, &url, &options, snapshot.as_ref())
+            .await
+    }
 
-        let options = download.into();
+    /// Upload a local snapshot file and recover a collection from it on this node.
+    ///
+    /// Streams the file to the REST API of the node with the API key of this client, and waits
+    /// for the recovery to finish. Unless disabled, the SHA256 checksum of the file is computed
+    /// first so the server can reject a corrupted upload.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::qdrant::{SnapshotPriority, SnapshotUploadBuilder};
+    ///
+    ///# async fn upload_snapshot(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// client
+    ///     .upload_snapshot(
+    ///         SnapshotUploadBuilder::new("./my_collection.snapshot", "my_collection")
+    ///             .priority(SnapshotPriority::Snapshot),
+    ///     )
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    ///
+    /// Documentation: <https://qdrant.tech/documentation/concepts/snapshots/#upload-snapshot-file>
+    #[cfg(feature = "download_snapshots")]
+    pub async fn upload_snapshot(
+        &self,
+        upload: impl Into<crate::qdrant::SnapshotUpload>,
+    ) -> QdrantResult<()> {
+        use reqwest::multipart::{Form, Part};
+        use tokio_util::io::ReaderStream;
 
-        let snapshot_name = match &options.snapshot_name {
-            Some(sn) => sn.to_string(),
-            _ => match self
-                .list_snapshots(options.collection_name.clone())
-                .await?
-                .snapshot_descriptions
-                .first()
-            {
-                Some(sn) => sn.name.clone(),
-                _ => {
-                    return Err(QdrantError::NoSnapshotFound(
-                        options.collection_name.clone(),
-                    ))
-                }
-            },
+        let upload = upload.into();
+        let checksum = match upload.checksum {
+            Some(checksum) => Some(checksum),
+            None if upload.compute_checksum => {
+                Some(download::file_checksum(&upload.file_path).await?)
+            }
+            None => None,
         };
 
-        let mut stream = reqwest::get(format!(
-            "{}/collections/{}/snapshots/{snapshot_name}",
-            options
-                .rest_api_uri
-                .as_ref()
-                .map(|uri| uri.to_string())
-                .unwrap_or_else(|| String::from("http://localhost:6333")),
-            options.collection_name,
-        ))
-        .await?
-        .bytes_stream();
+        let file = tokio::fs::File::open(&upload.file_path).await?;
+        let length = file.metadata().await?.len();
+        let file_name = upload.file_path.file_name().map_or_else(
+            || "snapshot".to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let part =
+            Part::stream_with_length(reqwest::Body::wrap_stream(ReaderStream::new(file)), length)
+                .file_name(file_name);
 
-        let _ = std::fs::remove_file(&options.out_path);
-        let mut file = std::fs::OpenOptions::new()
-            .write(true)
-            .create_new(true)
-            .open(&options.out_path)?;
+        let mut query = vec![("wait", "true".to_string())];
+        if let Some(priority) = upload.priority {
+            query.push(("priority", priority.as_str().to_string()));
+        }
+        if let Some(checksum) = checksum {
+            query.push(("checksum", checksum));
+        }
+
+        let rest_api_uri = self.rest_api_uri(upload.rest_api_uri.as_deref())?;
+        let response = self
+            .rest_client()?
+            .post(format!(
+                "{rest_api_uri}/collections/{}/snapshots/upload",
+                upload.collection_name,
+            ))
+            .query(&query)
+            .multipart(Form::new().part("snapshot", part))
+            .send()
+            .await?;
+        rest_result(response).await?;
+        Ok(())
+    }
 
-        while let Some(chunk) = stream.next().await {
-            let _written = file.write(&chunk?)?;
+    /// Recover a collection on this node from a snapshot URL.
+    ///
+    /// The node fetches the snapshot itself, so the location must be reachable from the node. The
+    /// request uses the REST API with the API key of this client, and waits for the recovery to
+    /// finish.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::qdrant::{SnapshotPriority, SnapshotRecoverBuilder};
+    ///
+    ///# async fn recover_snapshot_from_url(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// client
+    ///     .recover_snapshot_from_url(
+    ///         SnapshotRecoverBuilder::new(
+    ///             "my_collection",
+    ///             "https://backups.example.com/my_collection.snapshot",
+    ///         )
+    ///         .priority(SnapshotPriority::Snapshot)
+    ///         .checksum("a1b2c3..."),
+    ///     )
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    ///
+    /// Documentation: <https://qdrant.tech/documentation/concepts/snapshots/#recover-from-a-url-or-local-file>
+    #[cfg(feature = "download_snapshots")]
+    pub async fn recover_snapshot_from_url(
+        &self,
+        recover: impl Into<crate::qdrant::SnapshotRecover>,
+    ) -> QdrantResult<()> {
+        let recover = recover.into();
+        let mut body = serde_json::Map::new();
+        body.insert("location".to_string(), recover.location.into());
+        if let Some(priority) = recover.priority {
+            body.insert("priority".to_string(), priority.as_str().into());
+        }
+        if let Some(checksum) = recover.checksum {
+            body.insert("checksum".to_string(), checksum.into());
+        }
+        if let Some(api_key) = recover.location_api_key {
+            body.insert("api_key".to_string(), api_key.into());
         }
 
+        let rest_api_uri = self.rest_api_uri(recover.rest_api_uri.as_deref())?;
+        let response = self
+            .rest_client()?
+            .put(format!(
+                "{rest_api_uri}/collections/{}/snapshots/recover",
+                recover.collection_name,
+            ))
+            .query(&[("wait", "true")])
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::Value::Object(body).to_string())
+            .send()
+            .await?;
+        rest_result(response).await?;
         Ok(())
     }
 
+    /// Name and description of the snapshot to download
+    ///
+    /// The description is `None` if a named snapshot is not listed on this node, or if it is not
+    /// needed to verify the checksum.
+    #[cfg(feature = "download_snapshots")]
+    async fn snapshot_to_download(
+        &self,
+        options: &crate::qdrant::SnapshotDownload,
+    ) -> QdrantResult<(String, Option<SnapshotDescription>)> {
+        if let (Some(name), false) = (&options.snapshot_name, options.verify_checksum) {
+            return Ok((name.clone(), None));
+        }
+
+        let snapshots = if options.full_snapshot {
+            self.list_full_snapshots().await?
+        } else {
+            self.list_snapshots(options.collection_name.clone()).await?
+        }
+        .snapshot_descriptions;
+
+        match &options.snapshot_name {
+            Some(name) => Ok((
+                name.clone(),
+                snapshots
+                    .into_iter()
+                    .find(|snapshot| &snapshot.name == name),
+            )),
+            None => download::newest_snapshot(snapshots)
+                .map(|snapshot| (snapshot.name.clone(), Some(snapshot)))
+                .ok_or_else(|| QdrantError::NoSnapshotFound(options.collection_name.clone())),
+        }
+    }
+
     /// Delete a collection snapshot on this node.
     ///
     /// ```no_run
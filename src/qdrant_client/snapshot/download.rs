@@ -0,0 +1,294 @@
+use std::path::{Path, PathBuf};
+
+use futures_util::StreamExt;
+use reqwest::header::RANGE;
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufWriter};
+
+use crate::qdrant::{SnapshotDescription, SnapshotDownload, SnapshotDownloadProgress};
+use crate::qdrant_client::QdrantResult;
+use crate::QdrantError;
+
+/// Newest snapshot by creation time, snapshots without one are considered oldest
+pub(super) fn newest_snapshot(
+    snapshots: impl IntoIterator<Item = SnapshotDescription>,
+) -> Option<SnapshotDescription> {
+    snapshots.into_iter().max_by(|a, b| {
+        let created = |snapshot: &SnapshotDescription| {
+            snapshot
+                .creation_time
+                .as_ref()
+                .map(|time| (time.seconds, time.nanos))
+        };
+        created(a)
+            .cmp(&created(b))
+            .then_with(|| a.name.cmp(&b.name))
+    })
+}
+
+/// Path of the partial file a snapshot is downloaded into
+fn partial_path(out_path: &Path) -> PathBuf {
+    let mut path = out_path.as_os_str().to_owned();
+    path.push(".part");
+    PathBuf::from(path)
+}
+
+/// Download `url` to the output path through a partial file, resuming and verifying it
+pub(super) async fn download_snapshot_file(
+    client: &reqwest::Client,
+    url: &str,
+    options: &SnapshotDownload,
+    snapshot: Option<&SnapshotDescription>,
+) -> QdrantResult<()> {
+    let part_path = partial_path(&options.out_path);
+    let size = snapshot
+        .and_then(|snapshot| u64::try_from(snapshot.size).ok())
+        .filter(|&size| size > 0);
+    let checksum = snapshot
+        .and_then(|snapshot| snapshot.checksum.as_deref())
+        .filter(|_| options.verify_checksum);
+
+    let mut offset = if options.resume {
+        tokio::fs::metadata(&part_path)
+            .await
+            .map_or(0, |metadata| metadata.len())
+    } else {
+        0
+    };
+    // A partial file larger than the snapshot belongs to a different one
+    if size.is_some_and(|size| offset > size) {
+        offset = 0;
+    }
+
+    let response = if offset > 0 && size == Some(offset) {
+        // Interrupted after the last byte was written, only verification is left
+        None
+    } else {
+        let mut request = client.get(url);
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={offset}-"));
+        }
+        let mut response = request.send().await?;
+        if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            // The partial file does not fit this snapshot, start over
+            response = client.get(url).send().await?;
+        }
+        let response = response.error_for_status()?;
+        if response.status() != StatusCode::PARTIAL_CONTENT {
+            offset = 0;
+        }
+        Some(response)
+    };
+
+    let mut hasher = checksum.map(|_| Sha256::new());
+    if let (Some(hasher), true) = (&mut hasher, offset > 0) {
+        hash_file(&part_path, hasher).await?;
+    }
+
+    let file = if offset > 0 {
+        OpenOptions::new().append(true).open(&part_path).await?
+    } else {
+        File::create(&part_path).await?
+    };
+    let mut writer = BufWriter::new(file);
+    let mut downloaded = offset;
+    let total = response
+        .as_ref()
+        .and_then(|response| response.content_length())
+        .map(|length| length + offset)
+        .or(size);
+    let report = |downloaded| {
+        if let Some(progress) = &options.progress {
+            progress(SnapshotDownloadProgress { downloaded, total });
+        }
+    };
+
+    match response {
+        Some(response) => {
+            let mut stream = response.bytes_stream();
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk?;
+                writer.write_all(&chunk).await?;
+                if let Some(hasher) = &mut hasher {
+                    hasher.update(&chunk);
+                }
+                downloaded += chunk.len() as u64;
+                report(downloaded);
+            }
+        }
+        None => report(downloaded),
+    }
+    writer.flush().await?;
+    writer.into_inner().sync_all().await?;
+
+    if let (Some(expected), Some(hasher)) = (checksum, hasher) {
+        let actual = format!("{:x}", hasher.finalize());
+        if !actual.eq_ignore_ascii_case(expected) {
+            let _ = tokio::fs::remove_file(&part_path).await;
+            return Err(QdrantError::SnapshotChecksumMismatch {
+                expected: expected.to_string(),
+                actual,
+            });
+        }
+    }
+
+    tokio::fs::rename(&part_path, &options.out_path).await?;
+    Ok(())
+}
+
+/// Hex encoded SHA256 checksum of a file
+pub(super) async fn file_checksum(path: &Path) -> QdrantResult<String> {
+    let mut hasher = Sha256::new();
+    hash_file(path, &mut hasher).await?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+async fn hash_file(path: &Path, hasher: &mut Sha256) -> QdrantResult<()> {
+    let mut file = File::open(path).await?;
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::qdrant::{SnapshotDownloadBuilder, Timestamp};
+
+    const SNAPSHOT: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    /// Serve the snapshot over HTTP with range support, recording requested range starts
+    async fn serve(ranges: Arc<Mutex<Vec<Option<usize>>>>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    let read = socket.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                let request = String::from_utf8_lossy(&request).to_lowercase();
+                let start = request.lines().find_map(|line| {
+                    line.trim()
+                        .strip_prefix("range: bytes=")?
+                        .trim_end_matches('-')
+                        .parse::<usize>()
+                        .ok()
+                });
+                ranges.lock().push(start);
+
+                let head = match start {
+                    Some(start) => format!(
+                        "HTTP/1.1 206 Partial Content\r\ncontent-range: bytes {start}-{}/{}\r\ncontent-length: {}\r\n",
+                        SNAPSHOT.len() - 1,
+                        SNAPSHOT.len(),
+                        SNAPSHOT.len() - start,
+                    ),
+                    None => format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\n", SNAPSHOT.len()),
+                };
+                let body = &SNAPSHOT[start.unwrap_or(0)..];
+                let response =
+                    [format!("{head}connection: close\r\n\r\n").as_bytes(), body].concat();
+                socket.write_all(&response).await.unwrap();
+            }
+        });
+        addr
+    }
+
+    fn description(checksum: &str) -> SnapshotDescription {
+        SnapshotDescription {
+            name: "test.snapshot".to_string(),
+            creation_time: None,
+            size: SNAPSHOT.len() as i64,
+            checksum: Some(checksum.to_string()),
+        }
+    }
+
+    #[test]
+    fn newest_snapshot_by_creation_time() {
+        let snapshot = |name: &str, seconds: Option<i64>| SnapshotDescription {
+            name: name.to_string(),
+            creation_time: seconds.map(|seconds| Timestamp { seconds, nanos: 0 }),
+            ..Default::default()
+        };
+        let newest = newest_snapshot([
+            snapshot("b", Some(20)),
+            snapshot("c", Some(30)),
+            snapshot("a", Some(10)),
+            snapshot("d", None),
+        ]);
+        assert_eq!(newest.unwrap().name, "c");
+        assert!(newest_snapshot([]).is_none());
+    }
+
+    #[tokio::test]
+    async fn download_resumes_and_verifies() {
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let url = format!("http://{}/snapshot", serve(ranges.clone()).await);
+        let dir = std::env::temp_dir().join(format!("qdrant-snapshot-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let out_path = dir.join("test.snapshot");
+        let checksum = format!("{:x}", Sha256::digest(SNAPSHOT));
+
+        // Continue an interrupted download
+        std::fs::write(partial_path(&out_path), &SNAPSHOT[..10]).unwrap();
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let progress = reports.clone();
+        let options = SnapshotDownloadBuilder::new(&out_path, "test")
+            .progress(move |report| progress.lock().push(report))
+            .build();
+        download_snapshot_file(
+            &reqwest::Client::new(),
+            &url,
+            &options,
+            Some(&description(&checksum)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(std::fs::read(&out_path).unwrap(), SNAPSHOT);
+        assert!(!partial_path(&out_path).exists());
+        assert_eq!(ranges.lock().as_slice(), [Some(10)]);
+        assert_eq!(
+            reports.lock().last(),
+            Some(&SnapshotDownloadProgress {
+                downloaded: SNAPSHOT.len() as u64,
+                total: Some(SNAPSHOT.len() as u64),
+            }),
+        );
+
+        // A checksum mismatch keeps the previous file and removes the partial one
+        let options = SnapshotDownloadBuilder::new(&out_path, "test").build();
+        let result = download_snapshot_file(
+            &reqwest::Client::new(),
+            &url,
+            &options,
+            Some(&description(&"0".repeat(64))),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(QdrantError::SnapshotChecksumMismatch { actual, .. }) if actual == checksum,
+        ));
+        assert_eq!(ranges.lock().as_slice(), [Some(10), None]);
+        assert!(!partial_path(&out_path).exists());
+        assert_eq!(std::fs::read(&out_path).unwrap(), SNAPSHOT);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,520 @@
+use std::collections::HashSet;
+#[cfg(feature = "download_snapshots")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "download_snapshots")]
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "download_snapshots")]
+use futures::future::BoxFuture;
+
+#[cfg(feature = "download_snapshots")]
+use crate::qdrant::SnapshotDownloadBuilder;
+use crate::qdrant::{DeleteSnapshotRequestBuilder, SnapshotDescription};
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Rules deciding which snapshots to keep
+///
+/// Without [`keep_last`](Self::keep_last) and [`keep_daily`](Self::keep_daily), all snapshots
+/// are kept before applying the byte cap. The newest snapshot is always kept.
+///
+/// ```rust
+/// use qdrant_client::snapshot_manager::RetentionPolicy;
+///
+/// // The last 3 snapshots, plus the newest one of each of the last 7 days, up to 10 GiB
+/// let policy = RetentionPolicy::default()
+///     .keep_last(3)
+///     .keep_daily(7)
+///     .max_total_bytes(10 << 30);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Number of newest snapshots to keep
+    pub keep_last: Option<usize>,
+    /// Number of days, counted in UTC and including today, to keep the newest snapshot of
+    pub keep_daily: Option<u32>,
+    /// Maximum total size of the kept snapshots, dropping the oldest ones first
+    pub max_total_bytes: Option<u64>,
+}
+
+impl RetentionPolicy {
+    /// Keep the newest `count` snapshots
+    pub fn keep_last(mut self, count: usize) -> Self {
+        self.keep_last = Some(count);
+        self
+    }
+
+    /// Keep the newest snapshot of each of the last `days` days
+    pub fn keep_daily(mut self, days: u32) -> Self {
+        self.keep_daily = Some(days);
+        self
+    }
+
+    /// Delete the oldest kept snapshots while their total size exceeds `bytes`
+    ///
+    /// The newest snapshot is always kept, even if it is larger than `bytes`.
+    pub fn max_total_bytes(mut self, bytes: u64) -> Self {
+        self.max_total_bytes = Some(bytes);
+        self
+    }
+
+    /// Snapshots to delete, newest first, at unix time `now` in seconds
+    pub fn prune(&self, snapshots: &[SnapshotDescription], now: i64) -> Vec<SnapshotDescription> {
+        let mut sorted: Vec<&SnapshotDescription> = snapshots.iter().collect();
+        sorted.sort_by(|a, b| {
+            created(b)
+                .cmp(&created(a))
+                .then_with(|| b.name.cmp(&a.name))
+        });
+
+        let mut keep = vec![self.keep_last.is_none() && self.keep_daily.is_none(); sorted.len()];
+        if let Some(count) = self.keep_last {
+            keep.iter_mut().take(count).for_each(|keep| *keep = true);
+        }
+        if let Some(days) = self.keep_daily {
+            let today = now.div_euclid(SECONDS_PER_DAY);
+            let mut seen = HashSet::new();
+            for (keep, snapshot) in keep.iter_mut().zip(&sorted) {
+                let Some((seconds, _)) = created(snapshot) else {
+                    continue;
+                };
+                let day = seconds.div_euclid(SECONDS_PER_DAY);
+                if day > today - i64::from(days) && seen.insert(day) {
+                    *keep = true;
+                }
+            }
+        }
+        if let Some(first) = keep.first_mut() {
+            *first = true;
+        }
+
+        if let Some(max_total_bytes) = self.max_total_bytes {
+            // Once a snapshot does not fit, it and all older ones are dropped
+            let mut total = 0u64;
+            let mut full = false;
+            for (index, (keep, snapshot)) in keep.iter_mut().zip(&sorted).enumerate() {
+                if !*keep {
+                    continue;
+                }
+                let size = u64::try_from(snapshot.size).unwrap_or_default();
+                full |= index > 0 && total + size > max_total_bytes;
+                if full {
+                    *keep = false;
+                } else {
+                    total += size;
+                }
+            }
+        }
+
+        sorted
+            .into_iter()
+            .zip(keep)
+            .filter(|(_, keep)| !keep)
+            .map(|(snapshot, _)| snapshot.clone())
+            .collect()
+    }
+}
+
+fn created(snapshot: &SnapshotDescription) -> Option<(i64, i32)> {
+    snapshot
+        .creation_time
+        .as_ref()
+        .map(|time| (time.seconds, time.nanos))
+}
+
+/// Destination for downloaded snapshots, like an object store bucket
+///
+/// Keys are `collections/<collection>/<snapshot>` for collection snapshots, and
+/// `full/<snapshot>` for full snapshots.
+///
+/// <small><em>Requires `download_snapshots` feature</em></small>
+#[cfg(feature = "download_snapshots")]
+pub trait SnapshotSink: Send + Sync {
+    /// Store the snapshot file at `path` under `key`
+    ///
+    /// The file is removed after this returns, it may be moved instead of copied.
+    fn put<'a>(&'a self, key: &'a str, path: &'a Path) -> BoxFuture<'a, QdrantResult<()>>;
+}
+
+/// Sink storing snapshots in a local directory, keys being relative paths
+///
+/// <small><em>Requires `download_snapshots` feature</em></small>
+#[cfg(feature = "download_snapshots")]
+#[derive(Clone, Debug)]
+pub struct LocalDirSink {
+    dir: PathBuf,
+}
+
+#[cfg(feature = "download_snapshots")]
+impl LocalDirSink {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[cfg(feature = "download_snapshots")]
+impl SnapshotSink for LocalDirSink {
+    fn put<'a>(&'a self, key: &'a str, path: &'a Path) -> BoxFuture<'a, QdrantResult<()>> {
+        Box::pin(async move {
+            let target = self.dir.join(key);
+            if let Some(parent) = target.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            // Renaming fails across file systems, copy instead
+            if tokio::fs::rename(path, &target).await.is_err() {
+                tokio::fs::copy(path, &target).await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Snapshot created or deleted by a [`SnapshotManager`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManagedSnapshot {
+    /// Collection of the snapshot, `None` for full snapshots
+    pub collection_name: Option<String>,
+    pub snapshot: SnapshotDescription,
+    /// Key the snapshot was stored under in the sink, if downloaded
+    pub sink_key: Option<String>,
+}
+
+/// Result of [`SnapshotManager::run`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SnapshotReport {
+    /// Newly created snapshots
+    pub created: Vec<ManagedSnapshot>,
+    /// Snapshots deleted by the retention policy
+    pub pruned: Vec<ManagedSnapshot>,
+}
+
+/// Snapshots of collections and the full node with retention, see [`Qdrant::snapshot_manager`]
+///
+/// Each [`run`](Self::run) creates a snapshot of every selected collection, and of the full
+/// node if enabled, optionally stores it in a [`SnapshotSink`], then deletes snapshots on the
+/// node according to the [`RetentionPolicy`]. Retention applies to each collection separately,
+/// and to snapshots on the node only, not to stored copies.
+#[derive(Clone)]
+pub struct SnapshotManager {
+    client: Qdrant,
+    collections: Option<Vec<String>>,
+    full_snapshot: bool,
+    retention: RetentionPolicy,
+    #[cfg(feature = "download_snapshots")]
+    sink: Option<Arc<dyn SnapshotSink>>,
+    #[cfg(feature = "download_snapshots")]
+    staging_dir: PathBuf,
+    #[cfg(feature = "download_snapshots")]
+    rest_api_uri: Option<String>,
+}
+
+impl SnapshotManager {
+    /// Snapshot only these collections, instead of all collections
+    pub fn collections(mut self, collections: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.collections = Some(collections.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Also create a full snapshot of the node
+    pub fn full_snapshot(mut self, full_snapshot: bool) -> Self {
+        self.full_snapshot = full_snapshot;
+        self
+    }
+
+    /// Set the rules deciding which snapshots to keep, by default all are kept
+    pub fn retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Download each created snapshot and store it in `sink`
+    ///
+    /// <small><em>Requires `download_snapshots` feature</em></small>
+    #[cfg(feature = "download_snapshots")]
+    pub fn sink(mut self, sink: impl SnapshotSink + 'static) -> Self {
+        self.sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// Set the directory snapshots are downloaded to before storing them in the sink
+    ///
+    /// Defaults to the system temporary directory.
+    ///
+    /// <small><em>Requires `download_snapshots` feature</em></small>
+    #[cfg(feature = "download_snapshots")]
+    pub fn staging_dir(mut self, staging_dir: impl Into<PathBuf>) -> Self {
+        self.staging_dir = staging_dir.into();
+        self
+    }
+
+    /// Set the REST API URI snapshots are downloaded from
+    ///
+    /// <small><em>Requires `download_snapshots` feature</em></small>
+    #[cfg(feature = "download_snapshots")]
+    pub fn rest_api_uri(mut self, rest_api_uri: impl Into<String>) -> Self {
+        self.rest_api_uri = Some(rest_api_uri.into());
+        self
+    }
+
+    /// Create, store and prune snapshots
+    ///
+    /// Stops at the first failing request, snapshots created up to then are pruned by a later
+    /// run.
+    pub async fn run(&self) -> QdrantResult<SnapshotReport> {
+        let collections = match &self.collections {
+            Some(collections) => collections.clone(),
+            None => self
+                .client
+                .list_collections()
+                .await?
+                .collections
+                .into_iter()
+                .map(|collection| collection.name)
+                .collect(),
+        };
+
+        let mut report = SnapshotReport::default();
+        for collection_name in collections {
+            self.run_for(Some(collection_name), &mut report).await?;
+        }
+        if self.full_snapshot {
+            self.run_for(None, &mut report).await?;
+        }
+        Ok(report)
+    }
+
+    /// Snapshot a collection, or the full node if `None`
+    async fn run_for(
+        &self,
+        collection_name: Option<String>,
+        report: &mut SnapshotReport,
+    ) -> QdrantResult<()> {
+        let created = match &collection_name {
+            Some(collection_name) => self.client.create_snapshot(collection_name).await?,
+            None => self.client.create_full_snapshot().await?,
+        }
+        .snapshot_description
+        .ok_or_else(|| {
+            QdrantError::ConversionError("Server did not describe the created snapshot".to_string())
+        })?;
+
+        #[cfg(feature = "download_snapshots")]
+        let sink_key = self.store(collection_name.as_deref(), &created).await?;
+        #[cfg(not(feature = "download_snapshots"))]
+        let sink_key = None;
+
+        report.created.push(ManagedSnapshot {
+            collection_name: collection_name.clone(),
+            snapshot: created,
+            sink_key,
+        });
+
+        let snapshots = match &collection_name {
+            Some(collection_name) => self.client.list_snapshots(collection_name).await?,
+            None => self.client.list_full_snapshots().await?,
+        }
+        .snapshot_descriptions;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs() as i64);
+
+        for snapshot in self.retention.prune(&snapshots, now) {
+            match &collection_name {
+                Some(collection_name) => {
+                    self.client
+                        .delete_snapshot(DeleteSnapshotRequestBuilder::new(
+                            collection_name,
+                            &snapshot.name,
+                        ))
+                        .await?
+                }
+                None => self.client.delete_full_snapshot(&snapshot.name).await?,
+            };
+            report.pruned.push(ManagedSnapshot {
+                collection_name: collection_name.clone(),
+                snapshot,
+                sink_key: None,
+            });
+        }
+        Ok(())
+    }
+
+    /// Download a snapshot into the sink, returning its key
+    #[cfg(feature = "download_snapshots")]
+    async fn store(
+        &self,
+        collection_name: Option<&str>,
+        snapshot: &SnapshotDescription,
+    ) -> QdrantResult<Option<String>> {
+        let Some(sink) = &self.sink else {
+            return Ok(None);
+        };
+
+        let (key, mut download) = match collection_name {
+            Some(collection_name) => (
+                format!("collections/{collection_name}/{}", snapshot.name),
+                SnapshotDownloadBuilder::new(
+                    self.staging_dir.join(&snapshot.name),
+                    collection_name,
+                ),
+            ),
+            None => (
+                format!("full/{}", snapshot.name),
+                SnapshotDownloadBuilder::new_full(self.staging_dir.join(&snapshot.name)),
+            ),
+        };
+        download = download.snapshot_name(&snapshot.name);
+        if let Some(rest_api_uri) = &self.rest_api_uri {
+            download = download.rest_api_uri(rest_api_uri);
+        }
+        let download = download.build();
+        let staged = download.out_path.clone();
+
+        self.client.download_snapshot(download).await?;
+        let result = sink.put(&key, &staged).await;
+        let _ = tokio::fs::remove_file(&staged).await;
+        result.map(|()| Some(key))
+    }
+}
+
+/// # Snapshot management
+///
+/// Scheduled snapshots with retention policies, and optional off-node storage.
+impl Qdrant {
+    /// Manager creating and pruning snapshots of collections and the full node.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::snapshot_manager::{LocalDirSink, RetentionPolicy};
+    ///
+    ///# async fn snapshot_manager(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let report = client
+    ///     .snapshot_manager()
+    ///     .collections(["my_collection"])
+    ///     .full_snapshot(true)
+    ///     .retention(RetentionPolicy::default().keep_last(3).keep_daily(7))
+    ///     .sink(LocalDirSink::new("/var/backups/qdrant"))
+    ///     .run()
+    ///     .await?;
+    ///
+    /// for pruned in report.pruned {
+    ///     println!("deleted {}", pruned.snapshot.name);
+    /// }
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub fn snapshot_manager(&self) -> SnapshotManager {
+        SnapshotManager {
+            client: self.clone(),
+            collections: None,
+            full_snapshot: false,
+            retention: RetentionPolicy::default(),
+            #[cfg(feature = "download_snapshots")]
+            sink: None,
+            #[cfg(feature = "download_snapshots")]
+            staging_dir: std::env::temp_dir(),
+            #[cfg(feature = "download_snapshots")]
+            rest_api_uri: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::Timestamp;
+
+    const NOW: i64 = 1_700_000_000;
+
+    /// Snapshot created `hours` ago
+    fn snapshot(name: &str, hours: i64, size: i64) -> SnapshotDescription {
+        SnapshotDescription {
+            name: name.to_string(),
+            creation_time: Some(Timestamp {
+                seconds: NOW - hours * 60 * 60,
+                nanos: 0,
+            }),
+            size,
+            checksum: None,
+        }
+    }
+
+    fn names(snapshots: Vec<SnapshotDescription>) -> Vec<String> {
+        snapshots
+            .into_iter()
+            .map(|snapshot| snapshot.name)
+            .collect()
+    }
+
+    #[test]
+    fn retention_keeps_last_and_dailies() {
+        // One snapshot every 12 hours over 5 days, newest first
+        let snapshots: Vec<_> = (0..10)
+            .map(|index| snapshot(&format!("s{index}"), index * 12, 1))
+            .collect();
+
+        assert!(RetentionPolicy::default().prune(&snapshots, NOW).is_empty());
+        assert_eq!(
+            names(
+                RetentionPolicy::default()
+                    .keep_last(3)
+                    .prune(&snapshots, NOW)
+            ),
+            ["s3", "s4", "s5", "s6", "s7", "s8", "s9"],
+        );
+
+        // `NOW` is 22:13 UTC, so s0 and s1 are today, s2 and s3 yesterday, and so on
+        assert_eq!(
+            names(
+                RetentionPolicy::default()
+                    .keep_last(1)
+                    .keep_daily(3)
+                    .prune(&snapshots, NOW)
+            ),
+            ["s1", "s3", "s5", "s6", "s7", "s8", "s9"],
+        );
+
+        // The newest snapshot is kept even when nothing else would be
+        assert_eq!(
+            RetentionPolicy::default()
+                .keep_last(0)
+                .prune(&snapshots, NOW)
+                .len(),
+            9,
+        );
+    }
+
+    #[test]
+    fn retention_caps_total_bytes() {
+        let mut snapshots = [
+            snapshot("old", 30, 40),
+            snapshot("new", 1, 50),
+            snapshot("mid", 10, 30),
+            snapshot("undated", 0, 1),
+        ];
+        snapshots[3].creation_time = None;
+
+        assert_eq!(
+            names(
+                RetentionPolicy::default()
+                    .max_total_bytes(85)
+                    .prune(&snapshots, NOW)
+            ),
+            // The undated snapshot would still fit, but it is older than "old" which does not
+            ["old", "undated"],
+        );
+        // Too small for the newest snapshot alone, which is still kept
+        assert_eq!(
+            names(
+                RetentionPolicy::default()
+                    .max_total_bytes(10)
+                    .prune(&snapshots, NOW)
+            ),
+            ["mid", "old", "undated"],
+        );
+    }
+}
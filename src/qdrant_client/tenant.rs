@@ -0,0 +1,543 @@
+use std::collections::HashMap;
+
+use crate::json_path::{JsonPath, JsonPathItem};
+use crate::qdrant::points_selector::PointsSelectorOneOf;
+use crate::qdrant::query::Variant;
+use crate::qdrant::{
+    vector_input, Condition, ContextInput, CountPoints, CountResponse, DeletePoints, FacetCounts,
+    FacetResponse, Filter, LookupLocation, PointsOperationResponse, PointsSelector, PrefetchQuery,
+    Query, QueryPoints, QueryResponse, ScrollPoints, ScrollResponse, SetPayloadPoints, ShardKey,
+    ShardKeySelector, UpsertPoints, Value, VectorInput,
+};
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::{Payload, QdrantError};
+
+/// Default payload field holding the tenant of a point
+pub const DEFAULT_TENANT_FIELD: &str = "tenant_id";
+
+/// Handle scoped to a single tenant of a multitenant collection
+///
+/// Created with [`Qdrant::tenant`]. Every request is restricted to the points of the tenant:
+///
+/// - reads and filter based writes get a condition on the tenant field added to their filter,
+///   including all prefetches of a query
+/// - upserted points get the tenant field set in their payload, and only update existing points
+///   of the same tenant
+/// - all requests get the shard key of the tenant, if configured
+///
+/// Requests that could access points of other tenants are refused with
+/// [`QdrantError::TenantViolation`]. This includes requests for another collection or shard key,
+/// payloads assigning another tenant, queries looking up vectors from another collection or
+/// using the vector of a point by ID, and updates selecting points by ID; use a filter with
+/// [`Condition::has_id`] instead.
+///
+/// Collection names of requests may be left empty, they are set to the collection of the
+/// handle.
+#[derive(Clone)]
+pub struct TenantClient {
+    client: Qdrant,
+    collection_name: String,
+    tenant_id: String,
+    tenant_field: String,
+    shard_key: Option<ShardKey>,
+}
+
+impl TenantClient {
+    /// Set the payload field holding the tenant, defaults to [`DEFAULT_TENANT_FIELD`]
+    ///
+    /// Nested fields like `meta.tenant` are supported, objects along the path are created in
+    /// upserted payloads. Array indices are not allowed in the path.
+    pub fn tenant_field(mut self, tenant_field: impl Into<String>) -> Self {
+        self.tenant_field = tenant_field.into();
+        self
+    }
+
+    /// Set the shard key the tenant's points are stored in
+    pub fn shard_key(mut self, shard_key: impl Into<ShardKey>) -> Self {
+        self.shard_key = Some(shard_key.into());
+        self
+    }
+
+    /// Collection of this tenant
+    pub fn collection_name(&self) -> &str {
+        &self.collection_name
+    }
+
+    /// ID of this tenant
+    pub fn tenant_id(&self) -> &str {
+        &self.tenant_id
+    }
+
+    /// Condition matching the points of this tenant
+    pub fn condition(&self) -> Condition {
+        Condition::matches(self.tenant_field.clone(), self.tenant_id.clone())
+    }
+
+    /// Query points of this tenant, see [`Qdrant::query`]
+    pub async fn query(&self, request: impl Into<QueryPoints>) -> QdrantResult<QueryResponse> {
+        let mut request = request.into();
+        self.scope_collection(&mut request.collection_name)?;
+        self.scope_lookup(&request.lookup_from)?;
+        self.scope_query(&request.query)?;
+        self.scope_prefetch(&mut request.prefetch)?;
+        self.scope_filter(&mut request.filter);
+        self.scope_shard_key(&mut request.shard_key_selector)?;
+        self.client.query(request).await
+    }
+
+    /// Scroll points of this tenant, see [`Qdrant::scroll`]
+    pub async fn scroll(&self, request: impl Into<ScrollPoints>) -> QdrantResult<ScrollResponse> {
+        let mut request = request.into();
+        self.scope_collection(&mut request.collection_name)?;
+        self.scope_filter(&mut request.filter);
+        self.scope_shard_key(&mut request.shard_key_selector)?;
+        self.client.scroll(request).await
+    }
+
+    /// Count points of this tenant, see [`Qdrant::count`]
+    pub async fn count(&self, request: impl Into<CountPoints>) -> QdrantResult<CountResponse> {
+        let mut request = request.into();
+        self.scope_collection(&mut request.collection_name)?;
+        self.scope_filter(&mut request.filter);
+        self.scope_shard_key(&mut request.shard_key_selector)?;
+        self.client.count(request).await
+    }
+
+    /// Count payload values of points of this tenant, see [`Qdrant::facet`]
+    pub async fn facet(&self, request: impl Into<FacetCounts>) -> QdrantResult<FacetResponse> {
+        let mut request = request.into();
+        self.scope_collection(&mut request.collection_name)?;
+        self.scope_filter(&mut request.filter);
+        self.scope_shard_key(&mut request.shard_key_selector)?;
+        self.client.facet(request).await
+    }
+
+    /// Insert or update points of this tenant, see [`Qdrant::upsert_points`]
+    ///
+    /// Existing points of other tenants with the same ID are left untouched.
+    pub async fn upsert_points(
+        &self,
+        request: impl Into<UpsertPoints>,
+    ) -> QdrantResult<PointsOperationResponse> {
+        let mut request = request.into();
+        self.scope_collection(&mut request.collection_name)?;
+        for point in &mut request.points {
+            self.scope_payload(&mut point.payload)?;
+        }
+        self.scope_filter(&mut request.update_filter);
+        self.scope_shard_key(&mut request.shard_key_selector)?;
+        self.client.upsert_points(request).await
+    }
+
+    /// Set payload of points of this tenant selected by a filter, see [`Qdrant::set_payload`]
+    pub async fn set_payload(
+        &self,
+        request: impl Into<SetPayloadPoints>,
+    ) -> QdrantResult<PointsOperationResponse> {
+        let mut request = request.into();
+        self.scope_collection(&mut request.collection_name)?;
+        self.scope_set_payload(&request.key, &request.payload)?;
+        self.scope_selector(&mut request.points_selector)?;
+        self.scope_shard_key(&mut request.shard_key_selector)?;
+        self.client.set_payload(request).await
+    }
+
+    /// Delete points of this tenant selected by a filter, see [`Qdrant::delete_points`]
+    pub async fn delete_points(
+        &self,
+        request: impl Into<DeletePoints>,
+    ) -> QdrantResult<PointsOperationResponse> {
+        let mut request = request.into();
+        self.scope_collection(&mut request.collection_name)?;
+        self.scope_selector(&mut request.points)?;
+        self.scope_shard_key(&mut request.shard_key_selector)?;
+        self.client.delete_points(request).await
+    }
+
+    fn violation(&self, reason: &str) -> QdrantError {
+        QdrantError::TenantViolation(format!(
+            "{reason} for tenant {:?} of collection {:?}",
+            self.tenant_id, self.collection_name,
+        ))
+    }
+
+    fn scope_collection(&self, collection_name: &mut String) -> QdrantResult<()> {
+        if collection_name.is_empty() {
+            collection_name.clone_from(&self.collection_name);
+        } else if *collection_name != self.collection_name {
+            return Err(self.violation(&format!("request for collection {collection_name:?}")));
+        }
+        Ok(())
+    }
+
+    fn scope_filter(&self, filter: &mut Option<Filter>) {
+        filter
+            .get_or_insert_with(Filter::default)
+            .must
+            .push(self.condition());
+    }
+
+    fn scope_shard_key(&self, selector: &mut Option<ShardKeySelector>) -> QdrantResult<()> {
+        let Some(shard_key) = &self.shard_key else {
+            return Ok(());
+        };
+        let tenant_selector = ShardKeySelector {
+            shard_keys: vec![shard_key.clone()],
+            fallback: None,
+        };
+        match selector {
+            None => *selector = Some(tenant_selector),
+            Some(selector) if *selector == tenant_selector => {}
+            Some(_) => return Err(self.violation("request for another shard key")),
+        }
+        Ok(())
+    }
+
+    fn scope_lookup(&self, lookup: &Option<LookupLocation>) -> QdrantResult<()> {
+        match lookup {
+            Some(lookup) if lookup.collection_name != self.collection_name => Err(self.violation(
+                &format!("lookup from collection {:?}", lookup.collection_name),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Refuse queries using the vector of a point, which may belong to another tenant
+    fn scope_query(&self, query: &Option<Query>) -> QdrantResult<()> {
+        let Some(variant) = query.as_ref().and_then(|query| query.variant.as_ref()) else {
+            return Ok(());
+        };
+        let inputs: Vec<&VectorInput> = match variant {
+            Variant::Nearest(input) => vec![input],
+            Variant::Recommend(recommend) => recommend
+                .positive
+                .iter()
+                .chain(&recommend.negative)
+                .collect(),
+            Variant::Discover(discover) => discover
+                .target
+                .iter()
+                .chain(discover.context.iter().flat_map(context_inputs))
+                .collect(),
+            Variant::Context(context) => context_inputs(context).collect(),
+            Variant::NearestWithMmr(nearest) => nearest.nearest.iter().collect(),
+            Variant::RelevanceFeedback(feedback) => feedback
+                .target
+                .iter()
+                .chain(
+                    feedback
+                        .feedback
+                        .iter()
+                        .filter_map(|item| item.example.as_ref()),
+                )
+                .collect(),
+            Variant::OrderBy(_)
+            | Variant::Fusion(_)
+            | Variant::Sample(_)
+            | Variant::Formula(_)
+            | Variant::Rrf(_) => Vec::new(),
+        };
+        let by_id = inputs
+            .iter()
+            .any(|input| matches!(input.variant, Some(vector_input::Variant::Id(_))));
+        if by_id {
+            return Err(self.violation("querying by point ID is not allowed, query by vector"));
+        }
+        Ok(())
+    }
+
+    fn scope_prefetch(&self, prefetch: &mut [PrefetchQuery]) -> QdrantResult<()> {
+        for prefetch in prefetch {
+            self.scope_lookup(&prefetch.lookup_from)?;
+            self.scope_query(&prefetch.query)?;
+            self.scope_prefetch(&mut prefetch.prefetch)?;
+            self.scope_filter(&mut prefetch.filter);
+        }
+        Ok(())
+    }
+
+    fn scope_selector(&self, selector: &mut Option<PointsSelector>) -> QdrantResult<()> {
+        match selector {
+            Some(PointsSelector {
+                points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter)),
+            }) => {
+                filter.must.push(self.condition());
+                Ok(())
+            }
+            Some(PointsSelector {
+                points_selector_one_of: Some(PointsSelectorOneOf::Points(_)),
+            }) => Err(self.violation(
+                "selecting points by ID is not allowed, use a filter with `Condition::has_id`",
+            )),
+            _ => Err(self.violation("updates without a filter are not allowed")),
+        }
+    }
+
+    fn tenant_path(&self) -> QdrantResult<JsonPath> {
+        let path = JsonPath::parse(&self.tenant_field)?;
+        if path
+            .rest
+            .iter()
+            .any(|item| !matches!(item, JsonPathItem::Key(_)))
+        {
+            return Err(QdrantError::ConversionError(format!(
+                "Tenant field {:?} must not contain array indices",
+                self.tenant_field,
+            )));
+        }
+        Ok(path)
+    }
+
+    fn scope_payload(&self, payload: &mut HashMap<String, Value>) -> QdrantResult<()> {
+        let path = self.tenant_path()?;
+        let tenant = Value::from(self.tenant_id.clone());
+        let mut scoped = Payload(std::mem::take(payload));
+        let result = match scoped.get_path(&self.tenant_field) {
+            Some(value) if *value != tenant => {
+                Err(self.violation(&format!("point assigned to tenant {value}")))
+            }
+            _ => {
+                scoped.set_json_path(&path, &tenant);
+                Ok(())
+            }
+        };
+        *payload = scoped.0;
+        result
+    }
+
+    /// Refuse setting payload at the tenant field, or at an object containing it
+    fn scope_set_payload(
+        &self,
+        key: &Option<String>,
+        payload: &HashMap<String, Value>,
+    ) -> QdrantResult<()> {
+        let tenant_path = self.tenant_path()?.items();
+        let key = key.as_deref().map(JsonPath::parse).transpose()?;
+        for field in payload.keys() {
+            let path = match &key {
+                Some(key) => key.key(field.clone()),
+                None => JsonPath::new(field.clone()),
+            }
+            .items();
+            let common = path.len().min(tenant_path.len());
+            if path[..common] == tenant_path[..common] {
+                return Err(self.violation("setting the tenant field is not allowed"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Vector inputs of the pairs of a context query
+fn context_inputs(context: &ContextInput) -> impl Iterator<Item = &VectorInput> {
+    context
+        .pairs
+        .iter()
+        .flat_map(|pair| pair.positive.iter().chain(&pair.negative))
+}
+
+/// # Multitenancy
+///
+/// Access a single tenant of a collection partitioned by a payload field, and optionally by
+/// shard key.
+///
+/// Documentation: <https://qdrant.tech/documentation/guides/multiple-partitions/>
+impl Qdrant {
+    /// Handle scoped to a single tenant of a collection.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::qdrant::{
+    ///     Condition, DeletePointsBuilder, Filter, PointStruct, QueryPointsBuilder, UpsertPointsBuilder,
+    /// };
+    /// use qdrant_client::Payload;
+    ///
+    ///# async fn tenant(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let tenant = client.tenant("my_collection", "acme").tenant_field("group_id");
+    ///
+    /// // Points get `"group_id": "acme"` in their payload
+    /// tenant
+    ///     .upsert_points(UpsertPointsBuilder::new(
+    ///         "",
+    ///         vec![PointStruct::new(1, vec![0.1, 0.2], Payload::new())],
+    ///     ))
+    ///     .await?;
+    ///
+    /// // Only returns points of the tenant
+    /// tenant
+    ///     .query(QueryPointsBuilder::new("").query(vec![0.1, 0.2]))
+    ///     .await?;
+    ///
+    /// // Deleting by ID requires a filter
+    /// tenant
+    ///     .delete_points(DeletePointsBuilder::new("").points(Filter::must([Condition::has_id([1])])))
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub fn tenant(
+        &self,
+        collection_name: impl Into<String>,
+        tenant_id: impl Into<String>,
+    ) -> TenantClient {
+        TenantClient {
+            client: self.clone(),
+            collection_name: collection_name.into(),
+            tenant_id: tenant_id.into(),
+            tenant_field: DEFAULT_TENANT_FIELD.to_string(),
+            shard_key: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::{
+        PointId, PointStruct, PrefetchQueryBuilder, QueryPointsBuilder, RecommendInputBuilder,
+        SetPayloadPointsBuilder,
+    };
+
+    fn tenant() -> TenantClient {
+        Qdrant::from_url("http://localhost:6334")
+            .build()
+            .unwrap()
+            .tenant("docs", "acme")
+            .shard_key("eu".to_string())
+    }
+
+    #[test]
+    fn tenant_scopes_reads() {
+        let tenant = tenant();
+        let mut request: QueryPoints = QueryPointsBuilder::new("")
+            .add_prefetch(PrefetchQuery::default())
+            .filter(Filter::must([Condition::matches(
+                "kind",
+                "pdf".to_string(),
+            )]))
+            .into();
+        tenant
+            .scope_collection(&mut request.collection_name)
+            .unwrap();
+        tenant.scope_prefetch(&mut request.prefetch).unwrap();
+        tenant.scope_filter(&mut request.filter);
+        tenant
+            .scope_shard_key(&mut request.shard_key_selector)
+            .unwrap();
+
+        assert_eq!(request.collection_name, "docs");
+        assert_eq!(request.filter.unwrap().must.len(), 2);
+        assert_eq!(
+            request.prefetch[0].filter,
+            Some(Filter::must([tenant.condition()])),
+        );
+        assert_eq!(
+            request.shard_key_selector,
+            Some(ShardKeySelector::from("eu".to_string())),
+        );
+
+        assert!(tenant.scope_collection(&mut "other".to_string()).is_err());
+        let mut other_shard = Some(ShardKeySelector::from("us".to_string()));
+        assert!(tenant.scope_shard_key(&mut other_shard).is_err());
+        let lookup = Some(LookupLocation {
+            collection_name: "other".to_string(),
+            ..Default::default()
+        });
+        assert!(tenant.scope_lookup(&lookup).is_err());
+    }
+
+    #[test]
+    fn tenant_refuses_queries_by_id() {
+        let tenant = tenant();
+        let by_id = |query: Query| {
+            matches!(
+                tenant.scope_query(&Some(query)),
+                Err(QdrantError::TenantViolation(_)),
+            )
+        };
+        assert!(by_id(Query::new_nearest(PointId::from(1))));
+        assert!(by_id(Query::new_recommend(
+            RecommendInputBuilder::default()
+                .add_positive(vec![0.1, 0.2])
+                .add_negative(PointId::from(2)),
+        )));
+        assert!(!by_id(Query::new_nearest(vec![0.1, 0.2])));
+        assert!(tenant.scope_query(&None).is_ok());
+
+        let mut prefetch = vec![PrefetchQueryBuilder::default()
+            .query(Query::new_nearest(PointId::from(1)))
+            .build()];
+        assert!(tenant.scope_prefetch(&mut prefetch).is_err());
+    }
+
+    #[test]
+    fn tenant_scopes_writes() {
+        let tenant = tenant();
+
+        let mut point = PointStruct::new(1, vec![0.1], Payload::new());
+        tenant.scope_payload(&mut point.payload).unwrap();
+        assert_eq!(point.payload[DEFAULT_TENANT_FIELD], Value::from("acme"));
+        let mut foreign = Payload::from([(DEFAULT_TENANT_FIELD, Value::from("other"))]).into();
+        assert!(matches!(
+            tenant.scope_payload(&mut foreign),
+            Err(QdrantError::TenantViolation(_)),
+        ));
+
+        let by_filter: SetPayloadPoints = SetPayloadPointsBuilder::new("docs", Payload::new())
+            .points_selector(Filter::must([Condition::has_id([1])]))
+            .into();
+        let mut selector = by_filter.points_selector;
+        tenant.scope_selector(&mut selector).unwrap();
+        let Some(PointsSelectorOneOf::Filter(filter)) = selector.unwrap().points_selector_one_of
+        else {
+            panic!("expected a filter");
+        };
+        assert_eq!(filter.must[1], tenant.condition());
+
+        let by_id: SetPayloadPoints = SetPayloadPointsBuilder::new("docs", Payload::new())
+            .points_selector(vec![PointId::from(1)])
+            .into();
+        assert!(tenant
+            .scope_selector(&mut by_id.points_selector.clone())
+            .is_err());
+        assert!(tenant.scope_selector(&mut None).is_err());
+    }
+
+    #[test]
+    fn tenant_nested_field() {
+        let tenant = tenant().tenant_field("meta.tenant");
+
+        let mut point = PointStruct::new(
+            1,
+            vec![0.1],
+            Payload::try_from(serde_json::json!({"meta": {"source": "web"}})).unwrap(),
+        );
+        tenant.scope_payload(&mut point.payload).unwrap();
+        assert_eq!(
+            serde_json::Value::from(Payload::from(point.payload)),
+            serde_json::json!({"meta": {"source": "web", "tenant": "acme"}}),
+        );
+
+        let mut foreign: HashMap<_, _> =
+            Payload::try_from(serde_json::json!({"meta": {"tenant": "other"}}))
+                .unwrap()
+                .into();
+        assert!(tenant.scope_payload(&mut foreign).is_err());
+        // The payload is left intact on errors
+        assert_eq!(foreign["meta"].get_path("tenant"), Some(&"other".into()));
+
+        let set = |key: Option<&str>, field: &str| {
+            let payload = HashMap::from([(field.to_string(), Value::from(1))]);
+            tenant.scope_set_payload(&key.map(str::to_string), &payload)
+        };
+        assert!(set(None, "meta").is_err());
+        assert!(set(Some("meta"), "tenant").is_err());
+        assert!(set(Some("meta.tenant"), "id").is_err());
+        assert!(set(Some("meta"), "source").is_ok());
+        assert!(set(None, "tenant").is_ok());
+
+        let indexed = tenant.clone().tenant_field("tenants[0]");
+        assert!(indexed.scope_payload(&mut HashMap::new()).is_err());
+    }
+}
@@ -0,0 +1,343 @@
+use std::cell::RefCell;
+
+use serde::de::{DeserializeOwned, Visitor};
+use serde::Deserializer;
+
+use crate::qdrant::with_payload_selector::SelectorOptions;
+use crate::qdrant::{
+    GetPoints, PayloadIncludeSelector, PointId, QueryPoints, RetrievedPoint, ScoredPoint,
+    ScrollPoints, ShardKey, VectorsOutput, WithPayloadSelector,
+};
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::serde_deser::DeserPayloadError;
+use crate::Payload;
+
+/// Point with its payload deserialized into `T`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypedHit<T> {
+    /// Point ID
+    pub id: Option<PointId>,
+    /// Similarity score, only set for query results
+    pub score: Option<f32>,
+    /// Point version
+    pub version: Option<u64>,
+    /// Vectors, if requested
+    pub vectors: Option<VectorsOutput>,
+    /// Shard key of the point
+    pub shard_key: Option<ShardKey>,
+    /// Deserialized payload
+    pub payload: T,
+}
+
+impl<T: DeserializeOwned> TryFrom<ScoredPoint> for TypedHit<T> {
+    type Error = crate::QdrantError;
+
+    fn try_from(point: ScoredPoint) -> Result<Self, Self::Error> {
+        Ok(Self {
+            payload: Payload::from(point.payload).deserialize()?,
+            id: point.id,
+            score: Some(point.score),
+            version: Some(point.version),
+            vectors: point.vectors,
+            shard_key: point.shard_key,
+        })
+    }
+}
+
+impl<T: DeserializeOwned> TryFrom<RetrievedPoint> for TypedHit<T> {
+    type Error = crate::QdrantError;
+
+    fn try_from(point: RetrievedPoint) -> Result<Self, Self::Error> {
+        Ok(Self {
+            payload: Payload::from(point.payload).deserialize()?,
+            id: point.id,
+            score: None,
+            version: None,
+            vectors: point.vectors,
+            shard_key: point.shard_key,
+        })
+    }
+}
+
+/// Page of scrolled points with deserialized payloads
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypedScrollResponse<T> {
+    /// Points of this page
+    pub result: Vec<TypedHit<T>>,
+    /// Offset of the next page, `None` on the last page
+    pub next_page_offset: Option<PointId>,
+}
+
+/// # Typed point operations
+///
+/// Query, scroll and retrieve points with their payload deserialized into a type.
+///
+/// If the request does not select a payload, only the fields of the type are fetched. This
+/// works for structs deriving [`Deserialize`](serde::Deserialize); other types, such as maps
+/// and structs with flattened fields, fetch the full payload.
+///
+/// <small><em>Requires `serde` feature</em></small>
+impl Qdrant {
+    /// Query points in a collection, deserializing their payload into `T`.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::qdrant::QueryPointsBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct City {
+    ///     name: String,
+    ///     population: u64,
+    /// }
+    ///
+    ///# async fn query_as(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let hits = client
+    ///     .query_as::<City>(
+    ///         QueryPointsBuilder::new("cities")
+    ///             .query(vec![0.2, 0.1, 0.9])
+    ///             .limit(10),
+    ///     )
+    ///     .await?;
+    ///
+    /// for hit in hits {
+    ///     println!("{}: {}", hit.payload.name, hit.score.unwrap_or_default());
+    /// }
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn query_as<T: DeserializeOwned>(
+        &self,
+        request: impl Into<QueryPoints>,
+    ) -> QdrantResult<Vec<TypedHit<T>>> {
+        let mut request = request.into();
+        request.with_payload = typed_payload_selector::<T>(request.with_payload);
+        self.query(request)
+            .await?
+            .result
+            .into_iter()
+            .map(TypedHit::try_from)
+            .collect()
+    }
+
+    /// Scroll points in a collection, deserializing their payload into `T`.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::qdrant::ScrollPointsBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct City {
+    ///     name: String,
+    /// }
+    ///
+    ///# async fn scroll_as(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let page = client
+    ///     .scroll_as::<City>(ScrollPointsBuilder::new("cities").limit(100))
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn scroll_as<T: DeserializeOwned>(
+        &self,
+        request: impl Into<ScrollPoints>,
+    ) -> QdrantResult<TypedScrollResponse<T>> {
+        let mut request = request.into();
+        request.with_payload = typed_payload_selector::<T>(request.with_payload);
+        let response = self.scroll(request).await?;
+        Ok(TypedScrollResponse {
+            result: response
+                .result
+                .into_iter()
+                .map(TypedHit::try_from)
+                .collect::<QdrantResult<_>>()?,
+            next_page_offset: response.next_page_offset,
+        })
+    }
+
+    /// Retrieve specific points from a collection, deserializing their payload into `T`.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::qdrant::GetPointsBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct City {
+    ///     name: String,
+    /// }
+    ///
+    ///# async fn get_points_as(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let points = client
+    ///     .get_points_as::<City>(GetPointsBuilder::new("cities", vec![1.into(), 2.into()]))
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn get_points_as<T: DeserializeOwned>(
+        &self,
+        request: impl Into<GetPoints>,
+    ) -> QdrantResult<Vec<TypedHit<T>>> {
+        let mut request = request.into();
+        request.with_payload = typed_payload_selector::<T>(request.with_payload);
+        self.get_points(request)
+            .await?
+            .result
+            .into_iter()
+            .map(TypedHit::try_from)
+            .collect()
+    }
+}
+
+/// Payload selector for deserializing into `T`, keeping an explicit selector
+fn typed_payload_selector<T: DeserializeOwned>(
+    selector: Option<WithPayloadSelector>,
+) -> Option<WithPayloadSelector> {
+    if selector.is_some() {
+        return selector;
+    }
+    let options = match struct_fields::<T>() {
+        Some(fields) => SelectorOptions::Include(PayloadIncludeSelector::new(
+            fields
+                .iter()
+                .map(|field| field.to_string())
+                .collect::<Vec<_>>(),
+        )),
+        None => SelectorOptions::Enable(true),
+    };
+    Some(options.into())
+}
+
+/// Field names of a struct deriving `Deserialize`, `None` for other types
+fn struct_fields<T: DeserializeOwned>() -> Option<&'static [&'static str]> {
+    let fields = RefCell::new(None);
+    let _ = T::deserialize(FieldsProbe { fields: &fields });
+    fields.into_inner()
+}
+
+/// Deserializer recording the fields a struct asks for, without deserializing anything
+struct FieldsProbe<'a> {
+    fields: &'a RefCell<Option<&'static [&'static str]>>,
+}
+
+impl<'de> Deserializer<'de> for FieldsProbe<'_> {
+    type Error = DeserPayloadError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(DeserPayloadError::Error("not a struct".to_string()))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.fields.borrow_mut() = Some(fields);
+        Err(DeserPayloadError::Error("fields recorded".to_string()))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::qdrant::Value;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct City {
+        name: String,
+        #[serde(rename = "pop")]
+        population: u64,
+        country: Option<String>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Wrapper(City);
+
+    #[derive(Deserialize)]
+    struct Flattened {
+        #[serde(flatten)]
+        _rest: HashMap<String, String>,
+    }
+
+    #[test]
+    fn selector_from_struct_fields() {
+        let include = |fields: &[&str]| {
+            Some(WithPayloadSelector::from(SelectorOptions::Include(
+                PayloadIncludeSelector::new(
+                    fields.iter().map(|f| f.to_string()).collect::<Vec<_>>(),
+                ),
+            )))
+        };
+
+        assert_eq!(
+            typed_payload_selector::<City>(None),
+            include(&["name", "pop", "country"]),
+        );
+        assert_eq!(
+            typed_payload_selector::<Wrapper>(None),
+            include(&["name", "pop", "country"]),
+        );
+
+        // Fetch everything when fields are unknown
+        let all = Some(WithPayloadSelector::from(true));
+        assert_eq!(typed_payload_selector::<Flattened>(None), all);
+        assert_eq!(typed_payload_selector::<HashMap<String, Value>>(None), all);
+
+        // Explicit selectors are kept
+        let explicit = Some(WithPayloadSelector::from(vec!["name"]));
+        assert_eq!(typed_payload_selector::<City>(explicit.clone()), explicit);
+    }
+
+    #[test]
+    fn typed_hits() {
+        let payload = HashMap::from([
+            ("name".to_string(), Value::from("Berlin")),
+            ("pop".to_string(), Value::from(3_700_000i64)),
+        ]);
+        let scored = ScoredPoint {
+            id: Some(1.into()),
+            payload: payload.clone(),
+            score: 0.5,
+            ..Default::default()
+        };
+        let hit = TypedHit::<City>::try_from(scored).unwrap();
+        assert_eq!(hit.score, Some(0.5));
+        assert_eq!(
+            hit.payload,
+            City {
+                name: "Berlin".to_string(),
+                population: 3_700_000,
+                country: None,
+            }
+        );
+
+        let retrieved = RetrievedPoint {
+            id: Some(1.into()),
+            payload: HashMap::from([("name".to_string(), Value::from("Berlin"))]),
+            ..Default::default()
+        };
+        assert!(TypedHit::<City>::try_from(retrieved).is_err());
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::qdrant::value::Kind;
+use crate::qdrant::{
+    Condition, Filter, GetPointsBuilder, PointId, PointStruct, RetrievedPoint, ShardKeySelector,
+    UpdateMode, UpsertPoints, Value, WriteOrdering,
+};
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+/// Default payload field holding the version of a point
+pub const DEFAULT_VERSION_FIELD: &str = "_version";
+
+/// Versioned upsert request, see [`Qdrant::versioned_upsert`]
+///
+/// ```rust
+/// use qdrant_client::qdrant::PointStruct;
+/// use qdrant_client::versioned::VersionedUpsert;
+/// use qdrant_client::Payload;
+///
+/// let request = VersionedUpsert::new("my_collection")
+///     // Insert a new point
+///     .point(PointStruct::new(1, vec![0.1, 0.2], Payload::new()), None)
+///     // Update a point last read at version 3
+///     .point(PointStruct::new(2, vec![0.3, 0.4], Payload::new()), Some(3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct VersionedUpsert {
+    collection_name: String,
+    points: Vec<(PointStruct, Option<u64>)>,
+    version_field: String,
+    ordering: Option<WriteOrdering>,
+    shard_key_selector: Option<ShardKeySelector>,
+}
+
+impl VersionedUpsert {
+    pub fn new(collection_name: impl Into<String>) -> Self {
+        Self {
+            collection_name: collection_name.into(),
+            points: Vec::new(),
+            version_field: DEFAULT_VERSION_FIELD.to_string(),
+            ordering: None,
+            shard_key_selector: None,
+        }
+    }
+
+    /// Add a point, along with the version it is expected to have in the collection
+    ///
+    /// Use `None` for points that are expected not to exist yet.
+    pub fn point(mut self, point: PointStruct, expected_version: Option<u64>) -> Self {
+        self.points.push((point, expected_version));
+        self
+    }
+
+    /// Set the payload field to store versions in
+    ///
+    /// An integer index on this field speeds up the version checks.
+    pub fn version_field(mut self, version_field: impl Into<String>) -> Self {
+        self.version_field = version_field.into();
+        self
+    }
+
+    /// Set the write ordering guarantees
+    pub fn ordering(mut self, ordering: impl Into<WriteOrdering>) -> Self {
+        self.ordering = Some(ordering.into());
+        self
+    }
+
+    /// Set the shard keys to write to
+    pub fn shard_key_selector(mut self, shard_key_selector: impl Into<ShardKeySelector>) -> Self {
+        self.shard_key_selector = Some(shard_key_selector.into());
+        self
+    }
+}
+
+/// Result of [`Qdrant::versioned_upsert`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VersionedUpsertResponse {
+    /// Points that were written, with their new version
+    pub applied: Vec<(PointId, u64)>,
+    /// Points that were not written because their version did not match
+    pub conflicts: Vec<VersionConflict>,
+}
+
+impl VersionedUpsertResponse {
+    /// Whether all points were written
+    pub fn is_success(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Point whose stored version did not match the expected one
+#[derive(Clone, Debug, PartialEq)]
+pub struct VersionConflict {
+    /// Point ID
+    pub id: PointId,
+    /// Version the point was expected to have, `None` if it was expected not to exist
+    pub expected: Option<u64>,
+    /// Version the point currently has, `None` if it does not exist or has no version
+    pub current: Option<u64>,
+}
+
+/// # Versioned upserts
+///
+/// Optimistic concurrency control for points, using a version number stored in their payload.
+impl Qdrant {
+    /// Write points only if their stored version matches the expected one.
+    ///
+    /// Each written point gets the expected version plus one, or `1` for new points, in the
+    /// version field. Points with an expected version are only updated if they exist with that
+    /// version, points without one are only inserted if they don't exist yet. Points are written
+    /// with `wait`, then read back to report per point whether it was written.
+    ///
+    /// A point counts as written if its stored payload equals the written payload, so a
+    /// concurrent identical write is not reported as a conflict.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use qdrant_client::qdrant::PointStruct;
+    /// use qdrant_client::versioned::VersionedUpsert;
+    /// use qdrant_client::Payload;
+    ///
+    ///# async fn versioned_upsert(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// let response = client
+    ///     .versioned_upsert(
+    ///         VersionedUpsert::new("my_collection")
+    ///             .point(PointStruct::new(1, vec![0.1, 0.2], Payload::new()), Some(3)),
+    ///     )
+    ///     .await?;
+    ///
+    /// for conflict in response.conflicts {
+    ///     println!("{} is at version {:?}, retrying", conflict.id, conflict.current);
+    /// }
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn versioned_upsert(
+        &self,
+        request: VersionedUpsert,
+    ) -> QdrantResult<VersionedUpsertResponse> {
+        let (upserts, written) = request.prepare()?;
+        for upsert in upserts {
+            self.upsert_points(upsert).await?;
+        }
+
+        let ids: Vec<PointId> = written.iter().map(|point| point.id.clone()).collect();
+        let mut lookup = GetPointsBuilder::new(&request.collection_name, ids)
+            .with_payload(true)
+            .with_vectors(false);
+        if let Some(shard_key_selector) = &request.shard_key_selector {
+            lookup = lookup.shard_key_selector(shard_key_selector.clone());
+        }
+        let stored = self.get_points(lookup).await?.result;
+        Ok(resolve(written, stored, &request.version_field))
+    }
+}
+
+/// Point as written by a versioned upsert
+struct WrittenPoint {
+    id: PointId,
+    expected: Option<u64>,
+    version: u64,
+    payload: HashMap<String, Value>,
+}
+
+impl VersionedUpsert {
+    /// Upsert requests, one per expected version as the update filter applies to a whole request
+    fn prepare(&self) -> QdrantResult<(Vec<UpsertPoints>, Vec<WrittenPoint>)> {
+        let mut groups: BTreeMap<Option<u64>, Vec<PointStruct>> = BTreeMap::new();
+        let mut written = Vec::with_capacity(self.points.len());
+        for (point, expected) in &self.points {
+            let id = point.id.clone().ok_or_else(|| {
+                QdrantError::ConversionError(
+                    "Versioned upserts require all points to have an ID".to_string(),
+                )
+            })?;
+            let version = expected.map_or(1, |expected| expected + 1);
+            let mut point = point.clone();
+            point
+                .payload
+                .insert(self.version_field.clone(), Value::from(version as i64));
+            written.push(WrittenPoint {
+                id,
+                expected: *expected,
+                version,
+                payload: point.payload.clone(),
+            });
+            groups.entry(*expected).or_default().push(point);
+        }
+
+        let upserts = groups
+            .into_iter()
+            .map(|(expected, points)| {
+                let (update_mode, update_filter) = match expected {
+                    None => (UpdateMode::InsertOnly, None),
+                    Some(expected) => (
+                        UpdateMode::UpdateOnly,
+                        Some(Filter::must([Condition::matches(
+                            self.version_field.clone(),
+                            expected as i64,
+                        )])),
+                    ),
+                };
+                UpsertPoints {
+                    collection_name: self.collection_name.clone(),
+                    wait: Some(true),
+                    points,
+                    ordering: self.ordering,
+                    shard_key_selector: self.shard_key_selector.clone(),
+                    update_filter,
+                    timeout: None,
+                    update_mode: Some(update_mode as i32),
+                }
+            })
+            .collect();
+        Ok((upserts, written))
+    }
+}
+
+/// Compare written points with the payloads of the `stored` points
+fn resolve(
+    written: Vec<WrittenPoint>,
+    stored: Vec<RetrievedPoint>,
+    version_field: &str,
+) -> VersionedUpsertResponse {
+    let stored: HashMap<PointId, HashMap<String, Value>> = stored
+        .into_iter()
+        .filter_map(|point| Some((point.id?.normalized(), point.payload)))
+        .collect();
+
+    let mut response = VersionedUpsertResponse::default();
+    for point in written {
+        match stored.get(&point.id.normalized()) {
+            Some(stored) if *stored == point.payload => {
+                response.applied.push((point.id, point.version))
+            }
+            stored => response.conflicts.push(VersionConflict {
+                id: point.id,
+                expected: point.expected,
+                current: stored.and_then(|stored| stored_version(stored, version_field)),
+            }),
+        }
+    }
+    response
+}
+
+fn stored_version(payload: &HashMap<String, Value>, version_field: &str) -> Option<u64> {
+    match payload.get(version_field)?.kind {
+        Some(Kind::IntegerValue(version)) => u64::try_from(version).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::condition::ConditionOneOf;
+    use crate::qdrant::r#match::MatchValue;
+    use crate::Payload;
+
+    fn point(id: u64) -> PointStruct {
+        PointStruct::new(id, vec![0.1], Payload::from([("name", Value::from("a"))]))
+    }
+
+    #[test]
+    fn versioned_upsert_requests() {
+        let request = VersionedUpsert::new("test")
+            .point(point(1), None)
+            .point(point(2), Some(3))
+            .point(point(3), Some(3))
+            .point(point(4), Some(7));
+        let (upserts, written) = request.prepare().unwrap();
+
+        assert_eq!(upserts.len(), 3);
+        assert_eq!(upserts[0].update_mode, Some(UpdateMode::InsertOnly as i32));
+        assert_eq!(upserts[0].update_filter, None);
+        assert_eq!(upserts[1].update_mode, Some(UpdateMode::UpdateOnly as i32));
+        assert_eq!(upserts[1].points.len(), 2);
+        assert!(upserts.iter().all(|upsert| upsert.wait == Some(true)));
+
+        let Some(ConditionOneOf::Field(condition)) =
+            &upserts[2].update_filter.as_ref().unwrap().must[0].condition_one_of
+        else {
+            panic!("expected a field condition");
+        };
+        assert_eq!(condition.key, DEFAULT_VERSION_FIELD);
+        assert_eq!(
+            condition.r#match.as_ref().unwrap().match_value,
+            Some(MatchValue::Integer(7)),
+        );
+        assert_eq!(
+            upserts[2].points[0].payload[DEFAULT_VERSION_FIELD],
+            Value::from(8i64),
+        );
+
+        let versions: Vec<u64> = written.iter().map(|point| point.version).collect();
+        assert_eq!(versions, [1, 4, 4, 8]);
+
+        let mut missing_id = point(5);
+        missing_id.id = None;
+        let request = VersionedUpsert::new("test").point(missing_id, None);
+        assert!(request.prepare().is_err());
+    }
+
+    #[test]
+    fn versioned_upsert_conflicts() {
+        let request = VersionedUpsert::new("test")
+            .point(point(1), None)
+            .point(point(2), Some(3))
+            .point(point(3), Some(3));
+        let (_, written) = request.prepare().unwrap();
+
+        let stored_point = |id: u64, payload| RetrievedPoint {
+            id: Some(PointId::from(id)),
+            payload,
+            ..Default::default()
+        };
+        // Point 1 was written as requested
+        let mut stored = vec![stored_point(1, written[0].payload.clone())];
+        // Point 2 was updated concurrently by someone else
+        let mut concurrent = written[1].payload.clone();
+        concurrent.insert("name".to_string(), Value::from("b"));
+        stored.push(stored_point(2, concurrent));
+        // Point 3 was deleted
+
+        let response = resolve(written, stored, DEFAULT_VERSION_FIELD);
+        assert!(!response.is_success());
+        assert_eq!(response.applied, vec![(PointId::from(1), 1)]);
+        assert_eq!(
+            response.conflicts,
+            vec![
+                VersionConflict {
+                    id: PointId::from(2),
+                    expected: Some(3),
+                    current: Some(4),
+                },
+                VersionConflict {
+                    id: PointId::from(3),
+                    expected: Some(3),
+                    current: None,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn versioned_upsert_normalizes_uuids() {
+        let uuid = "936DA01F9ABD4D9D80C702AF85C822A8";
+        let mut point = point(1);
+        point.id = Some(PointId::from(uuid));
+        let (_, written) = VersionedUpsert::new("test")
+            .point(point, Some(2))
+            .prepare()
+            .unwrap();
+
+        // The server returns the UUID lower-cased and hyphenated
+        let stored = vec![RetrievedPoint {
+            id: Some(PointId::from("936da01f-9abd-4d9d-80c7-02af85c822a8")),
+            payload: written[0].payload.clone(),
+            ..Default::default()
+        }];
+        let response = resolve(written, stored, DEFAULT_VERSION_FIELD);
+        assert!(response.is_success());
+        assert_eq!(response.applied, vec![(PointId::from(uuid), 3)]);
+    }
+}
@@ -0,0 +1,242 @@
+use std::time::{Duration, Instant};
+
+use crate::qdrant::{CollectionInfo, CollectionStatus, CountPoints};
+use crate::qdrant_client::{Qdrant, QdrantResult};
+use crate::QdrantError;
+
+/// Delay before polling again for the first time
+const INITIAL_BACKOFF: Duration = Duration::from_millis(50);
+
+/// Maximum delay between polls
+const MAX_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Collection state to wait for, see [`Qdrant::wait_for_collection`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitCondition {
+    /// Collection status is green, all points are indexed and optimized
+    Green,
+    /// At least this many vectors are indexed
+    IndexedVectorsAtLeast(u64),
+    /// Optimizers report no error
+    OptimizerOk,
+    /// No updates are waiting to be applied
+    ///
+    /// Servers not reporting the update queue are considered to have an empty queue.
+    UpdateQueueEmpty,
+}
+
+impl WaitCondition {
+    /// Whether the collection described by `info` meets this condition
+    pub fn is_met(&self, info: &CollectionInfo) -> bool {
+        match self {
+            WaitCondition::Green => info.status() == CollectionStatus::Green,
+            WaitCondition::IndexedVectorsAtLeast(count) => {
+                info.indexed_vectors_count.unwrap_or_default() >= *count
+            }
+            WaitCondition::OptimizerOk => info
+                .optimizer_status
+                .as_ref()
+                .is_some_and(|status| status.ok),
+            // `Option::is_none_or` needs Rust 1.82
+            #[allow(clippy::unnecessary_map_or)]
+            WaitCondition::UpdateQueueEmpty => info
+                .update_queue
+                .as_ref()
+                .map_or(true, |queue| queue.length == 0),
+        }
+    }
+}
+
+/// Delays between polls, doubling up to [`MAX_BACKOFF`] and ending at the deadline
+pub(crate) struct Backoff {
+    delay: Duration,
+    deadline: Instant,
+}
+
+impl Backoff {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            delay: INITIAL_BACKOFF,
+            deadline: Instant::now() + timeout,
+        }
+    }
+
+    /// Delay before the next poll, `None` once the deadline has passed
+    fn next_delay(&mut self, now: Instant) -> Option<Duration> {
+        let remaining = self.deadline.checked_duration_since(now)?;
+        if remaining.is_zero() {
+            return None;
+        }
+        let delay = self.delay.min(remaining);
+        self.delay = (self.delay * 2).min(MAX_BACKOFF);
+        Some(delay)
+    }
+
+    /// Sleep until the next poll, returns `false` once the deadline has passed
+    pub(crate) async fn wait(&mut self) -> bool {
+        match self.next_delay(Instant::now()) {
+            Some(delay) => {
+                tokio::time::sleep(delay).await;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// # Waiting
+///
+/// Poll a collection until it reaches a state, with exponential backoff.
+impl Qdrant {
+    /// Wait until a collection meets all given conditions.
+    ///
+    /// Polls [`collection_info`](Self::collection_info), starting after 50 milliseconds and
+    /// doubling the delay up to 2 seconds. Returns the info meeting the conditions. On timeout,
+    /// [`QdrantError::CollectionWaitTimeout`] holds the last observed status, optimizer status and
+    /// update queue.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use std::time::Duration;
+    ///
+    /// use qdrant_client::wait::WaitCondition;
+    ///
+    ///# async fn wait_for_collection(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// client
+    ///     .wait_for_collection(
+    ///         "my_collection",
+    ///         [WaitCondition::Green, WaitCondition::UpdateQueueEmpty],
+    ///         Duration::from_secs(60),
+    ///     )
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn wait_for_collection(
+        &self,
+        collection_name: impl Into<String>,
+        conditions: impl IntoIterator<Item = WaitCondition>,
+        timeout: Duration,
+    ) -> QdrantResult<CollectionInfo> {
+        let collection_name = collection_name.into();
+        let conditions: Vec<_> = conditions.into_iter().collect();
+        let mut backoff = Backoff::new(timeout);
+        loop {
+            let info = self
+                .collection_info(&collection_name)
+                .await?
+                .result
+                .unwrap_or_default();
+            if conditions.iter().all(|condition| condition.is_met(&info)) {
+                return Ok(info);
+            }
+            if !backoff.wait().await {
+                return Err(QdrantError::CollectionWaitTimeout {
+                    collection_name,
+                    status: info.status(),
+                    optimizer_status: info.optimizer_status,
+                    update_queue: info.update_queue,
+                });
+            }
+        }
+    }
+
+    /// Wait until a count request counts at least `count` points.
+    ///
+    /// Useful to wait for writes to become visible, for example on other replicas. Polls with the
+    /// same backoff as [`wait_for_collection`](Self::wait_for_collection) and returns the last
+    /// count.
+    ///
+    /// ```no_run
+    ///# use qdrant_client::{Qdrant, QdrantError};
+    /// use std::time::Duration;
+    ///
+    /// use qdrant_client::qdrant::{Condition, CountPointsBuilder, Filter};
+    ///
+    ///# async fn wait_for_count(client: &Qdrant)
+    ///# -> Result<(), QdrantError> {
+    /// client
+    ///     .wait_for_count(
+    ///         CountPointsBuilder::new("my_collection")
+    ///             .filter(Filter::must([Condition::matches("city", "Berlin".to_string())]))
+    ///             .exact(true),
+    ///         100,
+    ///         Duration::from_secs(10),
+    ///     )
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn wait_for_count(
+        &self,
+        request: impl Into<CountPoints>,
+        count: u64,
+        timeout: Duration,
+    ) -> QdrantResult<u64> {
+        let request = request.into();
+        let mut backoff = Backoff::new(timeout);
+        loop {
+            let counted = self
+                .count(request.clone())
+                .await?
+                .result
+                .map_or(0, |result| result.count);
+            if counted >= count {
+                return Ok(counted);
+            }
+            if !backoff.wait().await {
+                return Err(QdrantError::Timeout(format!(
+                    "counted {counted} of {count} points in collection {}",
+                    request.collection_name,
+                )));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::{OptimizerStatus, UpdateQueueInfo};
+
+    #[test]
+    fn wait_conditions() {
+        let mut info = CollectionInfo {
+            status: CollectionStatus::Yellow as i32,
+            indexed_vectors_count: Some(90),
+            optimizer_status: Some(OptimizerStatus {
+                ok: true,
+                error: String::new(),
+            }),
+            update_queue: Some(UpdateQueueInfo { length: 3 }),
+            ..Default::default()
+        };
+        assert!(!WaitCondition::Green.is_met(&info));
+        assert!(!WaitCondition::IndexedVectorsAtLeast(100).is_met(&info));
+        assert!(WaitCondition::IndexedVectorsAtLeast(90).is_met(&info));
+        assert!(WaitCondition::OptimizerOk.is_met(&info));
+        assert!(!WaitCondition::UpdateQueueEmpty.is_met(&info));
+
+        info.status = CollectionStatus::Green as i32;
+        info.optimizer_status = None;
+        info.update_queue = None;
+        assert!(WaitCondition::Green.is_met(&info));
+        assert!(!WaitCondition::OptimizerOk.is_met(&info));
+        assert!(WaitCondition::UpdateQueueEmpty.is_met(&info));
+    }
+
+    #[test]
+    fn backoff_doubles_until_deadline() {
+        let mut backoff = Backoff::new(Duration::from_secs(5));
+        let start = backoff.deadline - Duration::from_secs(5);
+        let delays: Vec<_> = (0..8)
+            .map(|_| backoff.next_delay(start).unwrap().as_millis())
+            .collect();
+        assert_eq!(delays, [50, 100, 200, 400, 800, 1600, 2000, 2000]);
+
+        let almost = backoff.deadline - Duration::from_millis(300);
+        assert_eq!(backoff.next_delay(almost), Some(Duration::from_millis(300)));
+        assert_eq!(backoff.next_delay(backoff.deadline), None);
+    }
+}
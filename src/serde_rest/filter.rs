@@ -0,0 +1,583 @@
+use serde::de::Error as _;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{from_json, missing_variant, one_or_many};
+use crate::qdrant::condition::ConditionOneOf;
+use crate::qdrant::point_id::PointIdOptions;
+use crate::qdrant::r#match::MatchValue;
+use crate::qdrant::{
+    Condition, DatetimeRange, FieldCondition, Filter, GeoBoundingBox, GeoLineString, GeoPoint,
+    GeoPolygon, GeoRadius, HasIdCondition, HasVectorCondition, IsEmptyCondition, IsNullCondition,
+    Match, MinShould, NestedCondition, PointId, Range, RepeatedIntegers, RepeatedStrings,
+    Timestamp, ValuesCount,
+};
+
+impl Serialize for PointId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.point_id_options {
+            Some(PointIdOptions::Num(num)) => serializer.serialize_u64(*num),
+            Some(PointIdOptions::Uuid(uuid)) => serializer.serialize_str(uuid),
+            None => Err(missing_variant("PointId")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PointId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum PointIdRest {
+            Num(u64),
+            Uuid(String),
+        }
+
+        Ok(match PointIdRest::deserialize(deserializer)? {
+            PointIdRest::Num(num) => PointId::from(num),
+            PointIdRest::Uuid(uuid) => PointId::from(uuid),
+        })
+    }
+}
+
+impl Serialize for GeoPoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("lon", &self.lon)?;
+        map.serialize_entry("lat", &self.lat)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoPoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct GeoPointRest {
+            lon: f64,
+            lat: f64,
+        }
+
+        let GeoPointRest { lon, lat } = GeoPointRest::deserialize(deserializer)?;
+        Ok(GeoPoint { lon, lat })
+    }
+}
+
+impl Serialize for GeoBoundingBox {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("top_left", &self.top_left)?;
+        map.serialize_entry("bottom_right", &self.bottom_right)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoBoundingBox {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct GeoBoundingBoxRest {
+            top_left: GeoPoint,
+            bottom_right: GeoPoint,
+        }
+
+        let rest = GeoBoundingBoxRest::deserialize(deserializer)?;
+        Ok(GeoBoundingBox {
+            top_left: Some(rest.top_left),
+            bottom_right: Some(rest.bottom_right),
+        })
+    }
+}
+
+impl Serialize for GeoRadius {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("center", &self.center)?;
+        map.serialize_entry("radius", &self.radius)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoRadius {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct GeoRadiusRest {
+            center: GeoPoint,
+            radius: f32,
+        }
+
+        let rest = GeoRadiusRest::deserialize(deserializer)?;
+        Ok(GeoRadius {
+            center: Some(rest.center),
+            radius: rest.radius,
+        })
+    }
+}
+
+impl Serialize for GeoLineString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("points", &self.points)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoLineString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct GeoLineStringRest {
+            points: Vec<GeoPoint>,
+        }
+
+        let GeoLineStringRest { points } = GeoLineStringRest::deserialize(deserializer)?;
+        Ok(GeoLineString { points })
+    }
+}
+
+impl Serialize for GeoPolygon {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("exterior", &self.exterior)?;
+        if !self.interiors.is_empty() {
+            map.serialize_entry("interiors", &self.interiors)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoPolygon {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct GeoPolygonRest {
+            exterior: GeoLineString,
+            #[serde(default)]
+            interiors: Option<Vec<GeoLineString>>,
+        }
+
+        let rest = GeoPolygonRest::deserialize(deserializer)?;
+        Ok(GeoPolygon {
+            exterior: Some(rest.exterior),
+            interiors: rest.interiors.unwrap_or_default(),
+        })
+    }
+}
+
+/// Serialize the `lt`, `gt`, `gte` and `lte` bounds of a range-like type, skipping unset bounds
+macro_rules! serialize_bounds {
+    ($serializer:expr, $range:expr, $convert:expr) => {{
+        let mut map = $serializer.serialize_map(None)?;
+        for (name, bound) in [
+            ("lt", &$range.lt),
+            ("gt", &$range.gt),
+            ("gte", &$range.gte),
+            ("lte", &$range.lte),
+        ] {
+            if let Some(bound) = bound {
+                map.serialize_entry(name, &$convert(bound))?;
+            }
+        }
+        map.end()
+    }};
+}
+
+/// Bounds of a range-like type, as used by [`Range`], [`DatetimeRange`] and [`ValuesCount`]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BoundsRest<T> {
+    lt: Option<T>,
+    gt: Option<T>,
+    gte: Option<T>,
+    lte: Option<T>,
+}
+
+impl Serialize for Range {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bounds!(serializer, self, |bound: &f64| *bound)
+    }
+}
+
+impl<'de> Deserialize<'de> for Range {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let BoundsRest { lt, gt, gte, lte } = BoundsRest::deserialize(deserializer)?;
+        Ok(Range { lt, gt, gte, lte })
+    }
+}
+
+impl Serialize for DatetimeRange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bounds!(serializer, self, Timestamp::to_string)
+    }
+}
+
+impl<'de> Deserialize<'de> for DatetimeRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let BoundsRest { lt, gt, gte, lte } = BoundsRest::<String>::deserialize(deserializer)?;
+        let parse = |bound: Option<String>| {
+            bound
+                .map(|bound| bound.parse::<Timestamp>())
+                .transpose()
+                .map_err(D::Error::custom)
+        };
+        Ok(DatetimeRange {
+            lt: parse(lt)?,
+            gt: parse(gt)?,
+            gte: parse(gte)?,
+            lte: parse(lte)?,
+        })
+    }
+}
+
+impl Serialize for ValuesCount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bounds!(serializer, self, |bound: &u64| *bound)
+    }
+}
+
+impl<'de> Deserialize<'de> for ValuesCount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let BoundsRest { lt, gt, gte, lte } = BoundsRest::deserialize(deserializer)?;
+        Ok(ValuesCount { lt, gt, gte, lte })
+    }
+}
+
+impl Serialize for Match {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        match &self.match_value {
+            Some(MatchValue::Keyword(keyword)) => map.serialize_entry("value", keyword)?,
+            Some(MatchValue::Integer(integer)) => map.serialize_entry("value", integer)?,
+            Some(MatchValue::Boolean(boolean)) => map.serialize_entry("value", boolean)?,
+            Some(MatchValue::Text(text)) => map.serialize_entry("text", text)?,
+            Some(MatchValue::Keywords(keywords)) => {
+                map.serialize_entry("any", &keywords.strings)?
+            }
+            Some(MatchValue::Integers(integers)) => {
+                map.serialize_entry("any", &integers.integers)?
+            }
+            Some(MatchValue::ExceptIntegers(integers)) => {
+                map.serialize_entry("except", &integers.integers)?
+            }
+            Some(MatchValue::ExceptKeywords(keywords)) => {
+                map.serialize_entry("except", &keywords.strings)?
+            }
+            Some(MatchValue::Phrase(phrase)) => map.serialize_entry("phrase", phrase)?,
+            Some(MatchValue::TextAny(text)) => map.serialize_entry("text_any", text)?,
+            None => return Err(missing_variant("Match")),
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Match {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ValueVariants {
+            Boolean(bool),
+            Integer(i64),
+            Keyword(String),
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum AnyVariants {
+            Integers(Vec<i64>),
+            Keywords(Vec<String>),
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        enum MatchRest {
+            Value(ValueVariants),
+            Text(String),
+            Phrase(String),
+            TextAny(String),
+            Any(AnyVariants),
+            Except(AnyVariants),
+        }
+
+        let match_value = match MatchRest::deserialize(deserializer)? {
+            MatchRest::Value(ValueVariants::Boolean(boolean)) => MatchValue::Boolean(boolean),
+            MatchRest::Value(ValueVariants::Integer(integer)) => MatchValue::Integer(integer),
+            MatchRest::Value(ValueVariants::Keyword(keyword)) => MatchValue::Keyword(keyword),
+            MatchRest::Text(text) => MatchValue::Text(text),
+            MatchRest::Phrase(phrase) => MatchValue::Phrase(phrase),
+            MatchRest::TextAny(text) => MatchValue::TextAny(text),
+            MatchRest::Any(AnyVariants::Integers(integers)) => {
+                MatchValue::Integers(RepeatedIntegers { integers })
+            }
+            MatchRest::Any(AnyVariants::Keywords(strings)) => {
+                MatchValue::Keywords(RepeatedStrings { strings })
+            }
+            MatchRest::Except(AnyVariants::Integers(integers)) => {
+                MatchValue::ExceptIntegers(RepeatedIntegers { integers })
+            }
+            MatchRest::Except(AnyVariants::Keywords(strings)) => {
+                MatchValue::ExceptKeywords(RepeatedStrings { strings })
+            }
+        };
+
+        Ok(Match {
+            match_value: Some(match_value),
+        })
+    }
+}
+
+impl Serialize for FieldCondition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("key", &self.key)?;
+        if let Some(r#match) = &self.r#match {
+            map.serialize_entry("match", r#match)?;
+        }
+        // Numeric and datetime ranges share the same `range` key in REST
+        if let Some(range) = &self.range {
+            map.serialize_entry("range", range)?;
+        }
+        if let Some(datetime_range) = &self.datetime_range {
+            map.serialize_entry("range", datetime_range)?;
+        }
+        if let Some(geo_bounding_box) = &self.geo_bounding_box {
+            map.serialize_entry("geo_bounding_box", geo_bounding_box)?;
+        }
+        if let Some(geo_radius) = &self.geo_radius {
+            map.serialize_entry("geo_radius", geo_radius)?;
+        }
+        if let Some(geo_polygon) = &self.geo_polygon {
+            map.serialize_entry("geo_polygon", geo_polygon)?;
+        }
+        if let Some(values_count) = &self.values_count {
+            map.serialize_entry("values_count", values_count)?;
+        }
+        if let Some(is_empty) = &self.is_empty {
+            map.serialize_entry("is_empty", is_empty)?;
+        }
+        if let Some(is_null) = &self.is_null {
+            map.serialize_entry("is_null", is_null)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldCondition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct FieldConditionRest {
+            key: String,
+            r#match: Option<Match>,
+            range: Option<serde_json::Value>,
+            geo_bounding_box: Option<GeoBoundingBox>,
+            geo_radius: Option<GeoRadius>,
+            geo_polygon: Option<GeoPolygon>,
+            values_count: Option<ValuesCount>,
+            is_empty: Option<bool>,
+            is_null: Option<bool>,
+        }
+
+        let rest = FieldConditionRest::deserialize(deserializer)?;
+
+        // A range with any string bound is a datetime range
+        let (range, datetime_range) = match rest.range {
+            Some(range) => {
+                let is_datetime = range
+                    .as_object()
+                    .is_some_and(|bounds| bounds.values().any(serde_json::Value::is_string));
+                if is_datetime {
+                    (None, Some(from_json::<DatetimeRange, D::Error>(range)?))
+                } else {
+                    (Some(from_json::<Range, D::Error>(range)?), None)
+                }
+            }
+            None => (None, None),
+        };
+
+        Ok(FieldCondition {
+            key: rest.key,
+            r#match: rest.r#match,
+            range,
+            geo_bounding_box: rest.geo_bounding_box,
+            geo_radius: rest.geo_radius,
+            values_count: rest.values_count,
+            geo_polygon: rest.geo_polygon,
+            datetime_range,
+            is_empty: rest.is_empty,
+            is_null: rest.is_null,
+        })
+    }
+}
+
+impl Serialize for NestedCondition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("key", &self.key)?;
+        match &self.filter {
+            Some(filter) => map.serialize_entry("filter", filter)?,
+            None => map.serialize_entry("filter", &Filter::default())?,
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for NestedCondition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct NestedConditionRest {
+            key: String,
+            filter: Filter,
+        }
+
+        let rest = NestedConditionRest::deserialize(deserializer)?;
+        Ok(NestedCondition {
+            key: rest.key,
+            filter: Some(rest.filter),
+        })
+    }
+}
+
+impl Serialize for MinShould {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("conditions", &self.conditions)?;
+        map.serialize_entry("min_count", &self.min_count)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for MinShould {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct MinShouldRest {
+            conditions: Vec<Condition>,
+            min_count: u64,
+        }
+
+        let rest = MinShouldRest::deserialize(deserializer)?;
+        Ok(MinShould {
+            conditions: rest.conditions,
+            min_count: rest.min_count,
+        })
+    }
+}
+
+/// A condition that only references a payload key, such as `is_empty` and `is_null`
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PayloadFieldRest<'a> {
+    key: std::borrow::Cow<'a, str>,
+}
+
+impl Serialize for Condition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.condition_one_of {
+            Some(ConditionOneOf::Field(field)) => field.serialize(serializer),
+            Some(ConditionOneOf::Filter(filter)) => filter.serialize(serializer),
+            Some(ConditionOneOf::IsEmpty(IsEmptyCondition { key })) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("is_empty", &PayloadFieldRest { key: key.into() })?;
+                map.end()
+            }
+            Some(ConditionOneOf::IsNull(IsNullCondition { key })) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("is_null", &PayloadFieldRest { key: key.into() })?;
+                map.end()
+            }
+            Some(ConditionOneOf::HasId(HasIdCondition { has_id })) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("has_id", has_id)?;
+                map.end()
+            }
+            Some(ConditionOneOf::HasVector(HasVectorCondition { has_vector })) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("has_vector", has_vector)?;
+                map.end()
+            }
+            Some(ConditionOneOf::Nested(nested)) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("nested", nested)?;
+                map.end()
+            }
+            None => Err(missing_variant("Condition")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Condition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Conditions are untagged in REST, dispatch on the keys of the JSON object
+        let object = serde_json::Map::deserialize(deserializer)?;
+
+        let condition_one_of = if object.contains_key("key") {
+            ConditionOneOf::Field(from_json(object.into())?)
+        } else if object.len() == 1 && object.contains_key("is_empty") {
+            let PayloadFieldRest { key } = from_json(object["is_empty"].clone())?;
+            ConditionOneOf::IsEmpty(IsEmptyCondition { key: key.into() })
+        } else if object.len() == 1 && object.contains_key("is_null") {
+            let PayloadFieldRest { key } = from_json(object["is_null"].clone())?;
+            ConditionOneOf::IsNull(IsNullCondition { key: key.into() })
+        } else if object.len() == 1 && object.contains_key("has_id") {
+            ConditionOneOf::HasId(HasIdCondition {
+                has_id: from_json(object["has_id"].clone())?,
+            })
+        } else if object.len() == 1 && object.contains_key("has_vector") {
+            ConditionOneOf::HasVector(HasVectorCondition {
+                has_vector: from_json(object["has_vector"].clone())?,
+            })
+        } else if object.len() == 1 && object.contains_key("nested") {
+            ConditionOneOf::Nested(from_json(object["nested"].clone())?)
+        } else {
+            ConditionOneOf::Filter(from_json(object.into())?)
+        };
+
+        Ok(Condition {
+            condition_one_of: Some(condition_one_of),
+        })
+    }
+}
+
+impl Serialize for Filter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if !self.should.is_empty() {
+            map.serialize_entry("should", &self.should)?;
+        }
+        if let Some(min_should) = &self.min_should {
+            map.serialize_entry("min_should", min_should)?;
+        }
+        if !self.must.is_empty() {
+            map.serialize_entry("must", &self.must)?;
+        }
+        if !self.must_not.is_empty() {
+            map.serialize_entry("must_not", &self.must_not)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Filter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct FilterRest {
+            #[serde(default, deserialize_with = "one_or_many")]
+            should: Vec<Condition>,
+            min_should: Option<MinShould>,
+            #[serde(default, deserialize_with = "one_or_many")]
+            must: Vec<Condition>,
+            #[serde(default, deserialize_with = "one_or_many")]
+            must_not: Vec<Condition>,
+        }
+
+        let rest = FilterRest::deserialize(deserializer)?;
+        Ok(Filter {
+            should: rest.should,
+            must: rest.must,
+            must_not: rest.must_not,
+            min_should: rest.min_should,
+        })
+    }
+}
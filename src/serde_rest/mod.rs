@@ -0,0 +1,272 @@
+//! Serde support for API request types in the JSON shape of Qdrant's REST API
+//!
+//! The generated gRPC types don't map one-to-one onto the REST schema. For example, a
+//! [`Condition`](crate::qdrant::Condition) is a `oneof` in gRPC, but an untagged object in
+//! REST. The implementations in this module translate between both representations so that
+//! JSON written for the REST API can be loaded and sent over gRPC, and the other way around.
+//!
+//! Parameters that are part of the URL in the REST API, such as the collection name, read
+//! consistency and timeout, are not part of the JSON body and are therefore not serialized.
+
+mod filter;
+mod query;
+mod request;
+
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Deserializer};
+
+/// Either a single item or a list of items
+///
+/// The REST API accepts both forms in various places, such as filter clauses and prefetches.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    Many(Vec<T>),
+    One(T),
+}
+
+/// Deserialize a single item or a list of items into a list
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::Many(items) => items,
+        OneOrMany::One(item) => vec![item],
+    })
+}
+
+/// Convert a materialized JSON value into `T`, reporting failures as a custom error of `E`
+fn from_json<T: DeserializeOwned, E: Error>(value: serde_json::Value) -> Result<T, E> {
+    serde_json::from_value(value).map_err(E::custom)
+}
+
+/// Take the only entry out of a JSON object, as used for externally tagged REST variants
+fn single_entry<E: Error>(
+    mut object: serde_json::Map<String, serde_json::Value>,
+    expected: &str,
+) -> Result<(String, serde_json::Value), E> {
+    if object.len() != 1 {
+        return Err(E::custom(format!(
+            "expected {expected} with exactly one key, got {} keys",
+            object.len()
+        )));
+    }
+    let key = object.keys().next().cloned().unwrap_or_default();
+    let value = object.remove(&key).unwrap_or_default();
+    Ok((key, value))
+}
+
+/// Error for a `oneof` that is not set, which has no REST representation
+fn missing_variant<E: serde::ser::Error>(type_name: &str) -> E {
+    E::custom(format!("cannot serialize {type_name} without a variant"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::qdrant::{
+        Condition, DatetimeRange, Filter, Fusion, GeoPoint, PayloadIncludeSelector,
+        PrefetchQueryBuilder, Query, QueryPoints, QueryPointsBuilder, Range, ScrollPoints,
+        ScrollPointsBuilder, Timestamp, VectorInput,
+    };
+
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        serde_json::from_value(serde_json::to_value(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn filter_rest_shape() {
+        let filter = Filter::must([Condition::matches("city", "Berlin".to_string())]);
+        assert_eq!(
+            serde_json::to_value(&filter).unwrap(),
+            json!({"must": [{"key": "city", "match": {"value": "Berlin"}}]}),
+        );
+
+        let parsed: Filter = serde_json::from_value(
+            json!({"must": [{"key": "city", "match": {"value": "Berlin"}}]}),
+        )
+        .unwrap();
+        assert_eq!(parsed, filter);
+    }
+
+    #[test]
+    fn filter_accepts_single_condition() {
+        let parsed: Filter =
+            serde_json::from_value(json!({"should": {"key": "count", "match": {"value": 3}}}))
+                .unwrap();
+        assert_eq!(parsed, Filter::should([Condition::matches("count", 3)]));
+    }
+
+    #[test]
+    fn condition_round_trip() {
+        let timestamp = "2024-01-01T00:00:00Z".parse::<Timestamp>().unwrap();
+        let filter = Filter {
+            must: vec![
+                Condition::matches("tags", vec!["a".to_string(), "b".to_string()]),
+                Condition::matches_text("description", "good"),
+                Condition::range(
+                    "price",
+                    Range {
+                        gte: Some(10.0),
+                        lt: Some(20.5),
+                        ..Default::default()
+                    },
+                ),
+                Condition::datetime_range(
+                    "created",
+                    DatetimeRange {
+                        gt: Some(timestamp),
+                        ..Default::default()
+                    },
+                ),
+                Condition::geo_radius(
+                    "location",
+                    crate::qdrant::GeoRadius {
+                        center: Some(GeoPoint {
+                            lat: 52.5,
+                            lon: 13.4,
+                        }),
+                        radius: 1000.0,
+                    },
+                ),
+                Condition::is_empty("reports"),
+                Condition::is_null("comment"),
+                Condition::has_id([1, 2]),
+                Condition::has_vector("image"),
+                Condition::nested(
+                    "diet",
+                    Filter::must([Condition::matches("food", "meat".to_string())]),
+                ),
+            ],
+            must_not: vec![Filter::should([Condition::matches("color", true)]).into()],
+            min_should: Some(crate::qdrant::MinShould {
+                conditions: vec![Condition::matches("a", 1), Condition::matches("b", 2)],
+                min_count: 1,
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(round_trip(&filter), filter);
+
+        let json = serde_json::to_value(&filter).unwrap();
+        assert_eq!(
+            json["must"][3],
+            json!({"key": "created", "range": {"gt": "2024-01-01T00:00:00Z"}})
+        );
+        assert_eq!(json["must"][5], json!({"is_empty": {"key": "reports"}}));
+    }
+
+    #[test]
+    fn query_points_rest_shape() {
+        let request: QueryPoints = QueryPointsBuilder::new("my_collection")
+            .add_prefetch(
+                PrefetchQueryBuilder::default()
+                    .query(Query::new_nearest(vec![0.1, 0.2]))
+                    .using("dense")
+                    .limit(20u64),
+            )
+            .add_prefetch(
+                PrefetchQueryBuilder::default()
+                    .query(Query::new_nearest(VectorInput::new_sparse(
+                        vec![1, 42],
+                        vec![0.5, 0.3],
+                    )))
+                    .using("sparse")
+                    .limit(20u64),
+            )
+            .query(Query::new_fusion(Fusion::Rrf))
+            .filter(Filter::must([Condition::matches(
+                "city",
+                "Berlin".to_string(),
+            )]))
+            .limit(10)
+            .with_payload(true)
+            .build();
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "prefetch": [
+                    {"query": {"nearest": [0.1f32, 0.2f32]}, "using": "dense", "limit": 20},
+                    {
+                        "query": {"nearest": {"indices": [1, 42], "values": [0.5f32, 0.3f32]}},
+                        "using": "sparse",
+                        "limit": 20,
+                    },
+                ],
+                "query": {"fusion": "rrf"},
+                "filter": {"must": [{"key": "city", "match": {"value": "Berlin"}}]},
+                "limit": 10,
+                "with_payload": true,
+            }),
+        );
+
+        // The collection name is part of the URL in the REST API
+        let mut parsed: QueryPoints = serde_json::from_value(json).unwrap();
+        assert!(parsed.collection_name.is_empty());
+        parsed.collection_name = "my_collection".to_string();
+        assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn query_shorthand_vector() {
+        let parsed: QueryPoints =
+            serde_json::from_value(json!({"query": [0.5, 0.25], "using": "dense"})).unwrap();
+        assert_eq!(parsed.query, Some(Query::new_nearest(vec![0.5, 0.25])));
+
+        let parsed: QueryPoints = serde_json::from_value(json!({"query": 42})).unwrap();
+        assert_eq!(
+            parsed.query,
+            Some(Query::new_nearest(VectorInput::new_id(42)))
+        );
+    }
+
+    #[test]
+    fn query_formula_round_trip() {
+        let value = json!({
+            "prefetch": {"query": [0.1, 0.2], "limit": 50},
+            "query": {
+                "formula": {
+                    "sum": [
+                        "$score",
+                        {"mult": [0.5, {"key": "tag", "match": {"any": ["h1", "h2"]}}]},
+                        {"gauss_decay": {"x": {"geo_distance": {"origin": {"lat": 52.5, "lon": 13.4}, "to": "geo.location"}}, "scale": 5000.0}},
+                    ]
+                },
+                "defaults": {"tag": "none"},
+            },
+        });
+        let parsed: QueryPoints = serde_json::from_value(value).unwrap();
+        assert_eq!(round_trip(&parsed), parsed);
+    }
+
+    #[test]
+    fn scroll_round_trip() {
+        let request: ScrollPoints = ScrollPointsBuilder::new("")
+            .filter(Filter::must([Condition::matches(
+                "city",
+                "Berlin".to_string(),
+            )]))
+            .limit(10)
+            .with_payload(PayloadIncludeSelector::new(vec!["city".to_string()]))
+            .with_vectors(false)
+            .build();
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["with_payload"], json!(["city"]));
+        assert_eq!(json["with_vector"], json!(false));
+        assert_eq!(round_trip(&request), request);
+    }
+
+    #[test]
+    fn rejects_unknown_condition() {
+        let result = serde_json::from_value::<Filter>(json!({"must": [{"foo": "bar"}]}));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,969 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use serde::de::Error as _;
+use serde::ser::{Error as _, SerializeMap};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{from_json, missing_variant, one_or_many, single_entry};
+use crate::qdrant::{
+    expression, feedback_strategy, query, start_from, vector_input, ContextInput, ContextInputPair,
+    DecayParamsExpression, DenseVector, Direction, DiscoverInput, DivExpression, Document,
+    Expression, FeedbackItem, FeedbackStrategy, Formula, Fusion, GeoDistance, GeoPoint, Image,
+    InferenceObject, Mmr, MultExpression, MultiDenseVector, NaiveFeedbackStrategy,
+    NearestInputWithMmr, OrderBy, PowExpression, Query, RecommendInput, RecommendStrategy,
+    RelevanceFeedbackInput, Rrf, Sample, SparseVector, StartFrom, SumExpression, Value,
+    VectorInput,
+};
+
+/// Keys of an object that is a [`Query`], rather than a [`VectorInput`] shorthand
+const QUERY_KEYS: &[&str] = &[
+    "nearest",
+    "recommend",
+    "discover",
+    "context",
+    "order_by",
+    "fusion",
+    "sample",
+    "formula",
+    "rrf",
+    "relevance_feedback",
+];
+
+/// Keys of an object that is an [`Expression`], rather than a condition
+const EXPRESSION_KEYS: &[&str] = &[
+    "geo_distance",
+    "datetime",
+    "datetime_key",
+    "mult",
+    "sum",
+    "div",
+    "neg",
+    "abs",
+    "sqrt",
+    "pow",
+    "exp",
+    "log10",
+    "ln",
+    "exp_decay",
+    "gauss_decay",
+    "lin_decay",
+];
+
+fn fusion_name(fusion: i32) -> Option<&'static str> {
+    match Fusion::try_from(fusion).ok()? {
+        Fusion::Rrf => Some("rrf"),
+        Fusion::Dbsf => Some("dbsf"),
+    }
+}
+
+fn sample_name(sample: i32) -> Option<&'static str> {
+    match Sample::try_from(sample).ok()? {
+        Sample::Random => Some("random"),
+    }
+}
+
+fn direction_name(direction: i32) -> Option<&'static str> {
+    match Direction::try_from(direction).ok()? {
+        Direction::Asc => Some("asc"),
+        Direction::Desc => Some("desc"),
+    }
+}
+
+fn recommend_strategy_name(strategy: i32) -> Option<&'static str> {
+    match RecommendStrategy::try_from(strategy).ok()? {
+        RecommendStrategy::AverageVector => Some("average_vector"),
+        RecommendStrategy::BestScore => Some("best_score"),
+        RecommendStrategy::SumScores => Some("sum_scores"),
+    }
+}
+
+/// Serialize an `i32` encoded gRPC enum by its REST name
+fn serialize_enum<S: Serializer>(
+    serializer: S,
+    value: i32,
+    name: fn(i32) -> Option<&'static str>,
+) -> Result<S::Ok, S::Error> {
+    let name =
+        name(value).ok_or_else(|| S::Error::custom(format!("unknown enum value {value}")))?;
+    serializer.serialize_str(name)
+}
+
+/// Deserialize an `i32` encoded gRPC enum from its REST name
+fn deserialize_enum<E: serde::de::Error>(
+    name: &str,
+    variants: &'static [&'static str],
+    from_name: fn(&str) -> Option<i32>,
+) -> Result<i32, E> {
+    from_name(name).ok_or_else(|| E::unknown_variant(name, variants))
+}
+
+/// Serialize model options, which are omitted in REST if empty
+fn serialize_inference<S: Serializer>(
+    serializer: S,
+    input_key: &str,
+    input: &impl Serialize,
+    model: &str,
+    options: &HashMap<String, Value>,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(None)?;
+    map.serialize_entry(input_key, input)?;
+    map.serialize_entry("model", model)?;
+    if !options.is_empty() {
+        map.serialize_entry("options", options)?;
+    }
+    map.end()
+}
+
+impl Serialize for VectorInput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.variant {
+            Some(vector_input::Variant::Id(id)) => id.serialize(serializer),
+            Some(vector_input::Variant::Dense(dense)) => dense.data.serialize(serializer),
+            Some(vector_input::Variant::Sparse(sparse)) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("indices", &sparse.indices)?;
+                map.serialize_entry("values", &sparse.values)?;
+                map.end()
+            }
+            Some(vector_input::Variant::MultiDense(multi)) => {
+                let vectors: Vec<_> = multi.vectors.iter().map(|v| &v.data).collect();
+                vectors.serialize(serializer)
+            }
+            Some(vector_input::Variant::Document(document)) => serialize_inference(
+                serializer,
+                "text",
+                &document.text,
+                &document.model,
+                &document.options,
+            ),
+            Some(vector_input::Variant::Image(image)) => serialize_inference(
+                serializer,
+                "image",
+                &image.image,
+                &image.model,
+                &image.options,
+            ),
+            Some(vector_input::Variant::Object(object)) => serialize_inference(
+                serializer,
+                "object",
+                &object.object,
+                &object.model,
+                &object.options,
+            ),
+            None => Err(missing_variant("VectorInput")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VectorInput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct SparseVectorRest {
+            indices: Vec<u32>,
+            values: Vec<f32>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct InferenceRest<T> {
+            #[serde(alias = "text", alias = "image", alias = "object")]
+            input: T,
+            model: String,
+            #[serde(default)]
+            options: HashMap<String, Value>,
+        }
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        let variant = match value {
+            serde_json::Value::Number(_) | serde_json::Value::String(_) => {
+                vector_input::Variant::Id(from_json(value)?)
+            }
+            serde_json::Value::Array(ref items) if items.first().is_some_and(|i| i.is_array()) => {
+                let vectors: Vec<Vec<f32>> = from_json(value)?;
+                vector_input::Variant::MultiDense(MultiDenseVector {
+                    vectors: vectors
+                        .into_iter()
+                        .map(|data| DenseVector { data })
+                        .collect(),
+                })
+            }
+            serde_json::Value::Array(_) => {
+                vector_input::Variant::Dense(DenseVector {
+                    data: from_json(value)?,
+                })
+            }
+            serde_json::Value::Object(ref object) if object.contains_key("indices") => {
+                let SparseVectorRest { indices, values } = from_json(value)?;
+                vector_input::Variant::Sparse(SparseVector { values, indices })
+            }
+            serde_json::Value::Object(ref object) if object.contains_key("text") => {
+                let rest: InferenceRest<String> = from_json(value)?;
+                vector_input::Variant::Document(Document {
+                    text: rest.input,
+                    model: rest.model,
+                    options: rest.options,
+                })
+            }
+            serde_json::Value::Object(ref object) if object.contains_key("image") => {
+                let rest: InferenceRest<Value> = from_json(value)?;
+                vector_input::Variant::Image(Image {
+                    image: Some(rest.input),
+                    model: rest.model,
+                    options: rest.options,
+                })
+            }
+            serde_json::Value::Object(ref object) if object.contains_key("object") => {
+                let rest: InferenceRest<Value> = from_json(value)?;
+                vector_input::Variant::Object(InferenceObject {
+                    object: Some(rest.input),
+                    model: rest.model,
+                    options: rest.options,
+                })
+            }
+            other => {
+                return Err(D::Error::custom(format!(
+                    "expected a vector, sparse vector, multi vector, point ID or inference object, got {other}"
+                )))
+            }
+        };
+
+        Ok(VectorInput {
+            variant: Some(variant),
+        })
+    }
+}
+
+impl Serialize for RecommendInput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Strategy(i32);
+
+        impl Serialize for Strategy {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_enum(serializer, self.0, recommend_strategy_name)
+            }
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+        if !self.positive.is_empty() {
+            map.serialize_entry("positive", &self.positive)?;
+        }
+        if !self.negative.is_empty() {
+            map.serialize_entry("negative", &self.negative)?;
+        }
+        if let Some(strategy) = self.strategy {
+            map.serialize_entry("strategy", &Strategy(strategy))?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for RecommendInput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct RecommendInputRest {
+            #[serde(default)]
+            positive: Vec<VectorInput>,
+            #[serde(default)]
+            negative: Vec<VectorInput>,
+            strategy: Option<String>,
+        }
+
+        let rest = RecommendInputRest::deserialize(deserializer)?;
+        let strategy = rest
+            .strategy
+            .map(|strategy| {
+                deserialize_enum(
+                    &strategy,
+                    &["average_vector", "best_score", "sum_scores"],
+                    |name| match name {
+                        "average_vector" => Some(RecommendStrategy::AverageVector as i32),
+                        "best_score" => Some(RecommendStrategy::BestScore as i32),
+                        "sum_scores" => Some(RecommendStrategy::SumScores as i32),
+                        _ => None,
+                    },
+                )
+            })
+            .transpose()?;
+
+        Ok(RecommendInput {
+            positive: rest.positive,
+            negative: rest.negative,
+            strategy,
+        })
+    }
+}
+
+impl Serialize for ContextInputPair {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("positive", &self.positive)?;
+        map.serialize_entry("negative", &self.negative)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ContextInputPair {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct ContextInputPairRest {
+            positive: VectorInput,
+            negative: VectorInput,
+        }
+
+        let rest = ContextInputPairRest::deserialize(deserializer)?;
+        Ok(ContextInputPair {
+            positive: Some(rest.positive),
+            negative: Some(rest.negative),
+        })
+    }
+}
+
+impl Serialize for ContextInput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.pairs.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ContextInput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(ContextInput {
+            pairs: one_or_many(deserializer)?,
+        })
+    }
+}
+
+impl Serialize for DiscoverInput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("target", &self.target)?;
+        match &self.context {
+            Some(context) => map.serialize_entry("context", context)?,
+            None => map.serialize_entry("context", &ContextInput::default())?,
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for DiscoverInput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct DiscoverInputRest {
+            target: VectorInput,
+            context: ContextInput,
+        }
+
+        let rest = DiscoverInputRest::deserialize(deserializer)?;
+        Ok(DiscoverInput {
+            target: Some(rest.target),
+            context: Some(rest.context),
+        })
+    }
+}
+
+impl Serialize for StartFrom {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.value {
+            Some(start_from::Value::Float(float)) => serializer.serialize_f64(*float),
+            Some(start_from::Value::Integer(integer)) => serializer.serialize_i64(*integer),
+            Some(start_from::Value::Timestamp(timestamp)) => {
+                serializer.serialize_str(&timestamp.to_string())
+            }
+            Some(start_from::Value::Datetime(datetime)) => serializer.serialize_str(datetime),
+            None => Err(missing_variant("StartFrom")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StartFrom {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StartFromRest {
+            Integer(i64),
+            Float(f64),
+            Datetime(String),
+        }
+
+        let value = match StartFromRest::deserialize(deserializer)? {
+            StartFromRest::Integer(integer) => start_from::Value::Integer(integer),
+            StartFromRest::Float(float) => start_from::Value::Float(float),
+            StartFromRest::Datetime(datetime) => start_from::Value::Datetime(datetime),
+        };
+        Ok(StartFrom { value: Some(value) })
+    }
+}
+
+impl Serialize for OrderBy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct DirectionRest(i32);
+
+        impl Serialize for DirectionRest {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_enum(serializer, self.0, direction_name)
+            }
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("key", &self.key)?;
+        if let Some(direction) = self.direction {
+            map.serialize_entry("direction", &DirectionRest(direction))?;
+        }
+        if let Some(start_from) = &self.start_from {
+            map.serialize_entry("start_from", start_from)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderBy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct OrderByRest {
+            key: String,
+            direction: Option<String>,
+            start_from: Option<StartFrom>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OrderByInterface {
+            Key(String),
+            Struct(OrderByRest),
+        }
+
+        let rest = match OrderByInterface::deserialize(deserializer)? {
+            OrderByInterface::Key(key) => return Ok(OrderBy::from(key)),
+            OrderByInterface::Struct(rest) => rest,
+        };
+
+        let direction = rest
+            .direction
+            .map(|direction| {
+                deserialize_enum(&direction, &["asc", "desc"], |name| match name {
+                    "asc" => Some(Direction::Asc as i32),
+                    "desc" => Some(Direction::Desc as i32),
+                    _ => None,
+                })
+            })
+            .transpose()?;
+
+        Ok(OrderBy {
+            key: rest.key,
+            direction,
+            start_from: rest.start_from,
+        })
+    }
+}
+
+impl Serialize for Mmr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(diversity) = self.diversity {
+            map.serialize_entry("diversity", &diversity)?;
+        }
+        if let Some(candidates_limit) = self.candidates_limit {
+            map.serialize_entry("candidates_limit", &candidates_limit)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Mmr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct MmrRest {
+            diversity: Option<f32>,
+            candidates_limit: Option<u32>,
+        }
+
+        let rest = MmrRest::deserialize(deserializer)?;
+        Ok(Mmr {
+            diversity: rest.diversity,
+            candidates_limit: rest.candidates_limit,
+        })
+    }
+}
+
+impl Serialize for Rrf {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(k) = self.k {
+            map.serialize_entry("k", &k)?;
+        }
+        if !self.weights.is_empty() {
+            map.serialize_entry("weights", &self.weights)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Rrf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct RrfRest {
+            k: Option<u32>,
+            #[serde(default)]
+            weights: Vec<f32>,
+        }
+
+        let rest = RrfRest::deserialize(deserializer)?;
+        Ok(Rrf {
+            k: rest.k,
+            weights: rest.weights,
+        })
+    }
+}
+
+impl Serialize for GeoDistance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("origin", &self.origin)?;
+        map.serialize_entry("to", &self.to)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoDistance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct GeoDistanceRest {
+            origin: GeoPoint,
+            to: String,
+        }
+
+        let rest = GeoDistanceRest::deserialize(deserializer)?;
+        Ok(GeoDistance {
+            origin: Some(rest.origin),
+            to: rest.to,
+        })
+    }
+}
+
+impl Serialize for DecayParamsExpression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("x", &self.x)?;
+        if let Some(target) = &self.target {
+            map.serialize_entry("target", target)?;
+        }
+        if let Some(scale) = self.scale {
+            map.serialize_entry("scale", &scale)?;
+        }
+        if let Some(midpoint) = self.midpoint {
+            map.serialize_entry("midpoint", &midpoint)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for DecayParamsExpression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct DecayParamsExpressionRest {
+            x: Expression,
+            target: Option<Expression>,
+            scale: Option<f32>,
+            midpoint: Option<f32>,
+        }
+
+        let rest = DecayParamsExpressionRest::deserialize(deserializer)?;
+        Ok(DecayParamsExpression {
+            x: Some(Box::new(rest.x)),
+            target: rest.target.map(Box::new),
+            scale: rest.scale,
+            midpoint: rest.midpoint,
+        })
+    }
+}
+
+impl Serialize for Expression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use expression::Variant;
+
+        fn entry<S: Serializer>(
+            serializer: S,
+            key: &str,
+            value: &impl Serialize,
+        ) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry(key, value)?;
+            map.end()
+        }
+
+        match &self.variant {
+            Some(Variant::Constant(constant)) => serializer.serialize_f32(*constant),
+            Some(Variant::Variable(variable)) => serializer.serialize_str(variable),
+            Some(Variant::Condition(condition)) => condition.serialize(serializer),
+            Some(Variant::GeoDistance(geo_distance)) => {
+                entry(serializer, "geo_distance", geo_distance)
+            }
+            Some(Variant::Datetime(datetime)) => entry(serializer, "datetime", datetime),
+            Some(Variant::DatetimeKey(key)) => entry(serializer, "datetime_key", key),
+            Some(Variant::Mult(MultExpression { mult })) => entry(serializer, "mult", mult),
+            Some(Variant::Sum(SumExpression { sum })) => entry(serializer, "sum", sum),
+            Some(Variant::Div(div)) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("div", &DivRest::from(div.as_ref()))?;
+                map.end()
+            }
+            Some(Variant::Neg(expression)) => entry(serializer, "neg", expression),
+            Some(Variant::Abs(expression)) => entry(serializer, "abs", expression),
+            Some(Variant::Sqrt(expression)) => entry(serializer, "sqrt", expression),
+            Some(Variant::Pow(pow)) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("pow", &PowRest::from(pow.as_ref()))?;
+                map.end()
+            }
+            Some(Variant::Exp(expression)) => entry(serializer, "exp", expression),
+            Some(Variant::Log10(expression)) => entry(serializer, "log10", expression),
+            Some(Variant::Ln(expression)) => entry(serializer, "ln", expression),
+            Some(Variant::ExpDecay(decay)) => entry(serializer, "exp_decay", decay),
+            Some(Variant::GaussDecay(decay)) => entry(serializer, "gauss_decay", decay),
+            Some(Variant::LinDecay(decay)) => entry(serializer, "lin_decay", decay),
+            None => Err(missing_variant("Expression")),
+        }
+    }
+}
+
+/// Borrow a boxed sub-expression, falling back to an empty one which fails to serialize
+fn borrow_expression(expression: Option<&Expression>) -> Cow<'_, Expression> {
+    expression.map_or_else(Default::default, Cow::Borrowed)
+}
+
+/// REST shape of [`DivExpression`]
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DivRest<'a> {
+    left: Cow<'a, Expression>,
+    right: Cow<'a, Expression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    by_zero_default: Option<f32>,
+}
+
+impl<'a> From<&'a DivExpression> for DivRest<'a> {
+    fn from(div: &'a DivExpression) -> Self {
+        Self {
+            left: borrow_expression(div.left.as_deref()),
+            right: borrow_expression(div.right.as_deref()),
+            by_zero_default: div.by_zero_default,
+        }
+    }
+}
+
+/// REST shape of [`PowExpression`]
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PowRest<'a> {
+    base: Cow<'a, Expression>,
+    exponent: Cow<'a, Expression>,
+}
+
+impl<'a> From<&'a PowExpression> for PowRest<'a> {
+    fn from(pow: &'a PowExpression) -> Self {
+        Self {
+            base: borrow_expression(pow.base.as_deref()),
+            exponent: borrow_expression(pow.exponent.as_deref()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use expression::Variant;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        let variant = match value {
+            serde_json::Value::Number(_) => Variant::Constant(from_json(value)?),
+            serde_json::Value::String(variable) => Variant::Variable(variable),
+            serde_json::Value::Object(object)
+                if object.len() == 1
+                    && object
+                        .keys()
+                        .all(|key| EXPRESSION_KEYS.contains(&key.as_str())) =>
+            {
+                let (key, value) = single_entry::<D::Error>(object, "an expression")?;
+                match key.as_str() {
+                    "geo_distance" => Variant::GeoDistance(from_json(value)?),
+                    "datetime" => Variant::Datetime(from_json(value)?),
+                    "datetime_key" => Variant::DatetimeKey(from_json(value)?),
+                    "mult" => Variant::Mult(MultExpression {
+                        mult: from_json(value)?,
+                    }),
+                    "sum" => Variant::Sum(SumExpression {
+                        sum: from_json(value)?,
+                    }),
+                    "div" => {
+                        let div: DivRest = from_json(value)?;
+                        Variant::Div(Box::new(DivExpression {
+                            left: Some(Box::new(div.left.into_owned())),
+                            right: Some(Box::new(div.right.into_owned())),
+                            by_zero_default: div.by_zero_default,
+                        }))
+                    }
+                    "neg" => Variant::Neg(Box::new(from_json(value)?)),
+                    "abs" => Variant::Abs(Box::new(from_json(value)?)),
+                    "sqrt" => Variant::Sqrt(Box::new(from_json(value)?)),
+                    "pow" => {
+                        let pow: PowRest = from_json(value)?;
+                        Variant::Pow(Box::new(PowExpression {
+                            base: Some(Box::new(pow.base.into_owned())),
+                            exponent: Some(Box::new(pow.exponent.into_owned())),
+                        }))
+                    }
+                    "exp" => Variant::Exp(Box::new(from_json(value)?)),
+                    "log10" => Variant::Log10(Box::new(from_json(value)?)),
+                    "ln" => Variant::Ln(Box::new(from_json(value)?)),
+                    "exp_decay" => Variant::ExpDecay(Box::new(from_json(value)?)),
+                    "gauss_decay" => Variant::GaussDecay(Box::new(from_json(value)?)),
+                    "lin_decay" => Variant::LinDecay(Box::new(from_json(value)?)),
+                    _ => unreachable!("expression keys are checked above"),
+                }
+            }
+            // Any other object must be a condition
+            serde_json::Value::Object(object) => Variant::Condition(from_json(object.into())?),
+            other => {
+                return Err(D::Error::custom(format!(
+                    "expected a constant, variable, condition or expression, got {other}"
+                )))
+            }
+        };
+
+        Ok(Expression {
+            variant: Some(variant),
+        })
+    }
+}
+
+impl Serialize for FeedbackItem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("example", &self.example)?;
+        map.serialize_entry("score", &self.score)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for FeedbackItem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct FeedbackItemRest {
+            example: VectorInput,
+            score: f32,
+        }
+
+        let rest = FeedbackItemRest::deserialize(deserializer)?;
+        Ok(FeedbackItem {
+            example: Some(rest.example),
+            score: rest.score,
+        })
+    }
+}
+
+/// REST shape of [`NaiveFeedbackStrategy`]
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NaiveFeedbackStrategyRest {
+    a: f32,
+    b: f32,
+    c: f32,
+}
+
+impl Serialize for FeedbackStrategy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.variant {
+            Some(feedback_strategy::Variant::Naive(NaiveFeedbackStrategy { a, b, c })) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(
+                    "naive",
+                    &NaiveFeedbackStrategyRest {
+                        a: *a,
+                        b: *b,
+                        c: *c,
+                    },
+                )?;
+                map.end()
+            }
+            None => Err(missing_variant("FeedbackStrategy")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FeedbackStrategy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        enum FeedbackStrategyRest {
+            Naive(NaiveFeedbackStrategyRest),
+        }
+
+        let variant = match FeedbackStrategyRest::deserialize(deserializer)? {
+            FeedbackStrategyRest::Naive(NaiveFeedbackStrategyRest { a, b, c }) => {
+                feedback_strategy::Variant::Naive(NaiveFeedbackStrategy { a, b, c })
+            }
+        };
+        Ok(FeedbackStrategy {
+            variant: Some(variant),
+        })
+    }
+}
+
+impl Serialize for RelevanceFeedbackInput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("target", &self.target)?;
+        map.serialize_entry("feedback", &self.feedback)?;
+        map.serialize_entry("strategy", &self.strategy)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for RelevanceFeedbackInput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct RelevanceFeedbackInputRest {
+            target: VectorInput,
+            feedback: Vec<FeedbackItem>,
+            strategy: FeedbackStrategy,
+        }
+
+        let rest = RelevanceFeedbackInputRest::deserialize(deserializer)?;
+        Ok(RelevanceFeedbackInput {
+            target: Some(rest.target),
+            feedback: rest.feedback,
+            strategy: Some(rest.strategy),
+        })
+    }
+}
+
+impl Serialize for Query {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        match &self.variant {
+            Some(query::Variant::Nearest(nearest)) => map.serialize_entry("nearest", nearest)?,
+            Some(query::Variant::NearestWithMmr(NearestInputWithMmr { nearest, mmr })) => {
+                map.serialize_entry("nearest", nearest)?;
+                map.serialize_entry("mmr", mmr)?;
+            }
+            Some(query::Variant::Recommend(recommend)) => {
+                map.serialize_entry("recommend", recommend)?
+            }
+            Some(query::Variant::Discover(discover)) => {
+                map.serialize_entry("discover", discover)?
+            }
+            Some(query::Variant::Context(context)) => map.serialize_entry("context", context)?,
+            Some(query::Variant::OrderBy(order_by)) => map.serialize_entry("order_by", order_by)?,
+            Some(query::Variant::Fusion(fusion)) => {
+                let name = fusion_name(*fusion)
+                    .ok_or_else(|| S::Error::custom(format!("unknown fusion {fusion}")))?;
+                map.serialize_entry("fusion", name)?
+            }
+            Some(query::Variant::Sample(sample)) => {
+                let name = sample_name(*sample)
+                    .ok_or_else(|| S::Error::custom(format!("unknown sample {sample}")))?;
+                map.serialize_entry("sample", name)?
+            }
+            Some(query::Variant::Formula(Formula {
+                expression,
+                defaults,
+            })) => {
+                map.serialize_entry("formula", expression)?;
+                if !defaults.is_empty() {
+                    map.serialize_entry("defaults", defaults)?;
+                }
+            }
+            Some(query::Variant::Rrf(rrf)) => map.serialize_entry("rrf", rrf)?,
+            Some(query::Variant::RelevanceFeedback(feedback)) => {
+                map.serialize_entry("relevance_feedback", feedback)?
+            }
+            None => return Err(missing_variant("Query")),
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Query {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        let mut object = match value {
+            serde_json::Value::Object(object)
+                if object.keys().any(|key| QUERY_KEYS.contains(&key.as_str())) =>
+            {
+                object
+            }
+            // Anything else is a shorthand for a nearest query
+            other => return Ok(Query::new_nearest(from_json::<VectorInput, _>(other)?)),
+        };
+
+        // Queries with a secondary key
+        if object.contains_key("nearest") && object.contains_key("mmr") {
+            let nearest = object.remove("nearest").unwrap_or_default();
+            let mmr = object.remove("mmr").unwrap_or_default();
+            if let Some(key) = object.keys().next() {
+                return Err(D::Error::unknown_field(key, &["nearest", "mmr"]));
+            }
+            return Ok(Query::new_nearest_with_mmr(
+                from_json::<VectorInput, _>(nearest)?,
+                from_json::<Mmr, _>(mmr)?,
+            ));
+        }
+        if object.contains_key("formula") {
+            let expression = object.remove("formula").unwrap_or_default();
+            let defaults = object.remove("defaults").unwrap_or_default();
+            if let Some(key) = object.keys().next() {
+                return Err(D::Error::unknown_field(key, &["formula", "defaults"]));
+            }
+            return Ok(Query::new_formula(Formula {
+                expression: Some(from_json(expression)?),
+                defaults: match defaults {
+                    serde_json::Value::Null => HashMap::new(),
+                    defaults => from_json(defaults)?,
+                },
+            }));
+        }
+
+        let (key, value) = single_entry::<D::Error>(object, "a query")?;
+        let query = match key.as_str() {
+            "nearest" => Query::new_nearest(from_json::<VectorInput, _>(value)?),
+            "recommend" => Query::new_recommend(from_json::<RecommendInput, _>(value)?),
+            "discover" => Query::new_discover(from_json::<DiscoverInput, _>(value)?),
+            "context" => Query::new_context(from_json::<ContextInput, _>(value)?),
+            "order_by" => Query::new_order_by(from_json::<OrderBy, _>(value)?),
+            "fusion" => {
+                let fusion = match from_json::<String, D::Error>(value)?.as_str() {
+                    "rrf" => Fusion::Rrf,
+                    "dbsf" => Fusion::Dbsf,
+                    other => return Err(D::Error::unknown_variant(other, &["rrf", "dbsf"])),
+                };
+                Query::new_fusion(fusion)
+            }
+            "sample" => {
+                let sample = match from_json::<String, D::Error>(value)?.as_str() {
+                    "random" => Sample::Random,
+                    other => return Err(D::Error::unknown_variant(other, &["random"])),
+                };
+                Query::new_sample(sample)
+            }
+            "rrf" => Query::new_rrf(from_json::<Rrf, _>(value)?),
+            "relevance_feedback" => {
+                Query::new_relevance_feedback(from_json::<RelevanceFeedbackInput, _>(value)?)
+            }
+            other => return Err(D::Error::unknown_field(other, QUERY_KEYS)),
+        };
+        Ok(query)
+    }
+}
@@ -0,0 +1,517 @@
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{missing_variant, one_or_many};
+use crate::qdrant::with_payload_selector::SelectorOptions as PayloadSelectorOptions;
+use crate::qdrant::with_vectors_selector::SelectorOptions as VectorsSelectorOptions;
+use crate::qdrant::{
+    shard_key, AcornSearchParams, CountPoints, Filter, LookupLocation, OrderBy,
+    PayloadExcludeSelector, PayloadIncludeSelector, PointId, PrefetchQuery,
+    QuantizationSearchParams, Query, QueryBatchPoints, QueryPoints, ScrollPoints, SearchParams,
+    ShardKey, ShardKeySelector, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+};
+
+/// Serialize a map entry only if the value is set
+fn serialize_some<M: SerializeMap, T: Serialize>(
+    map: &mut M,
+    key: &'static str,
+    value: &Option<T>,
+) -> Result<(), M::Error> {
+    match value {
+        Some(value) => map.serialize_entry(key, value),
+        None => Ok(()),
+    }
+}
+
+impl Serialize for ShardKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.key {
+            Some(shard_key::Key::Keyword(keyword)) => serializer.serialize_str(keyword),
+            Some(shard_key::Key::Number(number)) => serializer.serialize_u64(*number),
+            None => Err(missing_variant("ShardKey")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ShardKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ShardKeyRest {
+            Number(u64),
+            Keyword(String),
+        }
+
+        let key = match ShardKeyRest::deserialize(deserializer)? {
+            ShardKeyRest::Number(number) => shard_key::Key::Number(number),
+            ShardKeyRest::Keyword(keyword) => shard_key::Key::Keyword(keyword),
+        };
+        Ok(ShardKey { key: Some(key) })
+    }
+}
+
+impl Serialize for ShardKeySelector {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match (&self.shard_keys[..], &self.fallback) {
+            ([shard_key], None) => shard_key.serialize(serializer),
+            (shard_keys, None) => shard_keys.serialize(serializer),
+            (shard_keys, Some(fallback)) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                match shard_keys {
+                    [shard_key] => map.serialize_entry("target", shard_key)?,
+                    shard_keys => map.serialize_entry("target", shard_keys)?,
+                }
+                map.serialize_entry("fallback", fallback)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ShardKeySelector {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct ShardKeyWithFallbackRest {
+            #[serde(deserialize_with = "one_or_many")]
+            target: Vec<ShardKey>,
+            fallback: ShardKey,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ShardKeySelectorRest {
+            Keys(Vec<ShardKey>),
+            Key(ShardKey),
+            WithFallback(ShardKeyWithFallbackRest),
+        }
+
+        Ok(match ShardKeySelectorRest::deserialize(deserializer)? {
+            ShardKeySelectorRest::Keys(shard_keys) => ShardKeySelector {
+                shard_keys,
+                fallback: None,
+            },
+            ShardKeySelectorRest::Key(shard_key) => ShardKeySelector {
+                shard_keys: vec![shard_key],
+                fallback: None,
+            },
+            ShardKeySelectorRest::WithFallback(rest) => ShardKeySelector {
+                shard_keys: rest.target,
+                fallback: Some(rest.fallback),
+            },
+        })
+    }
+}
+
+impl Serialize for WithPayloadSelector {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.selector_options {
+            Some(PayloadSelectorOptions::Enable(enable)) => serializer.serialize_bool(*enable),
+            Some(PayloadSelectorOptions::Include(PayloadIncludeSelector { fields })) => {
+                fields.serialize(serializer)
+            }
+            Some(PayloadSelectorOptions::Exclude(PayloadExcludeSelector { fields })) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("exclude", fields)?;
+                map.end()
+            }
+            None => Err(missing_variant("WithPayloadSelector")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WithPayloadSelector {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "snake_case", deny_unknown_fields)]
+        enum PayloadSelectorRest {
+            Include(#[serde(deserialize_with = "one_or_many")] Vec<String>),
+            Exclude(#[serde(deserialize_with = "one_or_many")] Vec<String>),
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum WithPayloadInterface {
+            Enable(bool),
+            Fields(Vec<String>),
+            Selector(PayloadSelectorRest),
+        }
+
+        let selector_options = match WithPayloadInterface::deserialize(deserializer)? {
+            WithPayloadInterface::Enable(enable) => PayloadSelectorOptions::Enable(enable),
+            WithPayloadInterface::Fields(fields)
+            | WithPayloadInterface::Selector(PayloadSelectorRest::Include(fields)) => {
+                PayloadSelectorOptions::Include(PayloadIncludeSelector { fields })
+            }
+            WithPayloadInterface::Selector(PayloadSelectorRest::Exclude(fields)) => {
+                PayloadSelectorOptions::Exclude(PayloadExcludeSelector { fields })
+            }
+        };
+        Ok(WithPayloadSelector {
+            selector_options: Some(selector_options),
+        })
+    }
+}
+
+impl Serialize for WithVectorsSelector {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.selector_options {
+            Some(VectorsSelectorOptions::Enable(enable)) => serializer.serialize_bool(*enable),
+            Some(VectorsSelectorOptions::Include(VectorsSelector { names })) => {
+                names.serialize(serializer)
+            }
+            None => Err(missing_variant("WithVectorsSelector")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WithVectorsSelector {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum WithVectorsRest {
+            Enable(bool),
+            Names(Vec<String>),
+        }
+
+        let selector_options = match WithVectorsRest::deserialize(deserializer)? {
+            WithVectorsRest::Enable(enable) => VectorsSelectorOptions::Enable(enable),
+            WithVectorsRest::Names(names) => {
+                VectorsSelectorOptions::Include(VectorsSelector { names })
+            }
+        };
+        Ok(WithVectorsSelector {
+            selector_options: Some(selector_options),
+        })
+    }
+}
+
+impl Serialize for QuantizationSearchParams {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        serialize_some(&mut map, "ignore", &self.ignore)?;
+        serialize_some(&mut map, "rescore", &self.rescore)?;
+        serialize_some(&mut map, "oversampling", &self.oversampling)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for QuantizationSearchParams {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct QuantizationSearchParamsRest {
+            ignore: Option<bool>,
+            rescore: Option<bool>,
+            oversampling: Option<f64>,
+        }
+
+        let rest = QuantizationSearchParamsRest::deserialize(deserializer)?;
+        Ok(QuantizationSearchParams {
+            ignore: rest.ignore,
+            rescore: rest.rescore,
+            oversampling: rest.oversampling,
+        })
+    }
+}
+
+impl Serialize for AcornSearchParams {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        serialize_some(&mut map, "enable", &self.enable)?;
+        serialize_some(&mut map, "max_selectivity", &self.max_selectivity)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for AcornSearchParams {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct AcornSearchParamsRest {
+            enable: Option<bool>,
+            max_selectivity: Option<f64>,
+        }
+
+        let rest = AcornSearchParamsRest::deserialize(deserializer)?;
+        Ok(AcornSearchParams {
+            enable: rest.enable,
+            max_selectivity: rest.max_selectivity,
+        })
+    }
+}
+
+impl Serialize for SearchParams {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        serialize_some(&mut map, "hnsw_ef", &self.hnsw_ef)?;
+        serialize_some(&mut map, "exact", &self.exact)?;
+        serialize_some(&mut map, "quantization", &self.quantization)?;
+        serialize_some(&mut map, "indexed_only", &self.indexed_only)?;
+        serialize_some(&mut map, "acorn", &self.acorn)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SearchParams {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct SearchParamsRest {
+            hnsw_ef: Option<u64>,
+            exact: Option<bool>,
+            quantization: Option<QuantizationSearchParams>,
+            indexed_only: Option<bool>,
+            acorn: Option<AcornSearchParams>,
+        }
+
+        let rest = SearchParamsRest::deserialize(deserializer)?;
+        Ok(SearchParams {
+            hnsw_ef: rest.hnsw_ef,
+            exact: rest.exact,
+            quantization: rest.quantization,
+            indexed_only: rest.indexed_only,
+            acorn: rest.acorn,
+        })
+    }
+}
+
+impl Serialize for LookupLocation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("collection", &self.collection_name)?;
+        serialize_some(&mut map, "vector", &self.vector_name)?;
+        serialize_some(&mut map, "shard_key", &self.shard_key_selector)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for LookupLocation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct LookupLocationRest {
+            collection: String,
+            vector: Option<String>,
+            shard_key: Option<ShardKeySelector>,
+        }
+
+        let rest = LookupLocationRest::deserialize(deserializer)?;
+        Ok(LookupLocation {
+            collection_name: rest.collection,
+            vector_name: rest.vector,
+            shard_key_selector: rest.shard_key,
+        })
+    }
+}
+
+impl Serialize for PrefetchQuery {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if !self.prefetch.is_empty() {
+            map.serialize_entry("prefetch", &self.prefetch)?;
+        }
+        serialize_some(&mut map, "query", &self.query)?;
+        serialize_some(&mut map, "using", &self.using)?;
+        serialize_some(&mut map, "filter", &self.filter)?;
+        serialize_some(&mut map, "params", &self.params)?;
+        serialize_some(&mut map, "score_threshold", &self.score_threshold)?;
+        serialize_some(&mut map, "limit", &self.limit)?;
+        serialize_some(&mut map, "lookup_from", &self.lookup_from)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PrefetchQuery {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct PrefetchQueryRest {
+            #[serde(default, deserialize_with = "one_or_many")]
+            prefetch: Vec<PrefetchQuery>,
+            query: Option<Query>,
+            using: Option<String>,
+            filter: Option<Filter>,
+            params: Option<SearchParams>,
+            score_threshold: Option<f32>,
+            limit: Option<u64>,
+            lookup_from: Option<LookupLocation>,
+        }
+
+        let rest = PrefetchQueryRest::deserialize(deserializer)?;
+        Ok(PrefetchQuery {
+            prefetch: rest.prefetch,
+            query: rest.query,
+            using: rest.using,
+            filter: rest.filter,
+            params: rest.params,
+            score_threshold: rest.score_threshold,
+            limit: rest.limit,
+            lookup_from: rest.lookup_from,
+        })
+    }
+}
+
+impl Serialize for QueryPoints {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        serialize_some(&mut map, "shard_key", &self.shard_key_selector)?;
+        if !self.prefetch.is_empty() {
+            map.serialize_entry("prefetch", &self.prefetch)?;
+        }
+        serialize_some(&mut map, "query", &self.query)?;
+        serialize_some(&mut map, "using", &self.using)?;
+        serialize_some(&mut map, "filter", &self.filter)?;
+        serialize_some(&mut map, "params", &self.params)?;
+        serialize_some(&mut map, "score_threshold", &self.score_threshold)?;
+        serialize_some(&mut map, "limit", &self.limit)?;
+        serialize_some(&mut map, "offset", &self.offset)?;
+        serialize_some(&mut map, "with_vector", &self.with_vectors)?;
+        serialize_some(&mut map, "with_payload", &self.with_payload)?;
+        serialize_some(&mut map, "lookup_from", &self.lookup_from)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for QueryPoints {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct QueryPointsRest {
+            shard_key: Option<ShardKeySelector>,
+            #[serde(default, deserialize_with = "one_or_many")]
+            prefetch: Vec<PrefetchQuery>,
+            query: Option<Query>,
+            using: Option<String>,
+            filter: Option<Filter>,
+            params: Option<SearchParams>,
+            score_threshold: Option<f32>,
+            limit: Option<u64>,
+            offset: Option<u64>,
+            with_vector: Option<WithVectorsSelector>,
+            with_payload: Option<WithPayloadSelector>,
+            lookup_from: Option<LookupLocation>,
+        }
+
+        let rest = QueryPointsRest::deserialize(deserializer)?;
+        Ok(QueryPoints {
+            collection_name: String::new(),
+            prefetch: rest.prefetch,
+            query: rest.query,
+            using: rest.using,
+            filter: rest.filter,
+            params: rest.params,
+            score_threshold: rest.score_threshold,
+            limit: rest.limit,
+            offset: rest.offset,
+            with_vectors: rest.with_vector,
+            with_payload: rest.with_payload,
+            read_consistency: None,
+            shard_key_selector: rest.shard_key,
+            lookup_from: rest.lookup_from,
+            timeout: None,
+        })
+    }
+}
+
+impl Serialize for QueryBatchPoints {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("searches", &self.query_points)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for QueryBatchPoints {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct QueryBatchPointsRest {
+            searches: Vec<QueryPoints>,
+        }
+
+        let rest = QueryBatchPointsRest::deserialize(deserializer)?;
+        Ok(QueryBatchPoints {
+            collection_name: String::new(),
+            query_points: rest.searches,
+            read_consistency: None,
+            timeout: None,
+        })
+    }
+}
+
+impl Serialize for ScrollPoints {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        serialize_some(&mut map, "shard_key", &self.shard_key_selector)?;
+        serialize_some(&mut map, "offset", &self.offset)?;
+        serialize_some(&mut map, "limit", &self.limit)?;
+        serialize_some(&mut map, "filter", &self.filter)?;
+        serialize_some(&mut map, "with_payload", &self.with_payload)?;
+        serialize_some(&mut map, "with_vector", &self.with_vectors)?;
+        serialize_some(&mut map, "order_by", &self.order_by)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ScrollPoints {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct ScrollPointsRest {
+            shard_key: Option<ShardKeySelector>,
+            offset: Option<PointId>,
+            limit: Option<u32>,
+            filter: Option<Filter>,
+            with_payload: Option<WithPayloadSelector>,
+            with_vector: Option<WithVectorsSelector>,
+            order_by: Option<OrderBy>,
+        }
+
+        let rest = ScrollPointsRest::deserialize(deserializer)?;
+        Ok(ScrollPoints {
+            collection_name: String::new(),
+            filter: rest.filter,
+            offset: rest.offset,
+            limit: rest.limit,
+            with_payload: rest.with_payload,
+            with_vectors: rest.with_vector,
+            read_consistency: None,
+            shard_key_selector: rest.shard_key,
+            order_by: rest.order_by,
+            timeout: None,
+        })
+    }
+}
+
+impl Serialize for CountPoints {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        serialize_some(&mut map, "shard_key", &self.shard_key_selector)?;
+        serialize_some(&mut map, "filter", &self.filter)?;
+        serialize_some(&mut map, "exact", &self.exact)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for CountPoints {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct CountPointsRest {
+            shard_key: Option<ShardKeySelector>,
+            filter: Option<Filter>,
+            exact: Option<bool>,
+        }
+
+        let rest = CountPointsRest::deserialize(deserializer)?;
+        Ok(CountPoints {
+            collection_name: String::new(),
+            filter: rest.filter,
+            exact: rest.exact,
+            read_consistency: None,
+            shard_key_selector: rest.shard_key,
+            timeout: None,
+        })
+    }
+}
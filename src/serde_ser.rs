@@ -0,0 +1,753 @@
+//! Serialize any serde type into payload values
+//!
+//! Modeled after <https://github.com/serde-rs/json/blob/master/src/value/ser.rs>
+
+use std::collections::HashMap;
+
+use serde::ser::{Impossible, Serialize};
+use serde::Serializer;
+
+use crate::qdrant::value::Kind;
+use crate::qdrant::{ListValue, Struct, Value};
+
+#[derive(PartialEq, Eq, Clone)]
+pub enum SerPayloadError {
+    Error(String),
+    IntegerConversion { got: i128, max: i64 },
+    KeyMustBeString,
+    NotAnObject,
+}
+
+impl std::fmt::Debug for SerPayloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SerPayloadError::Error(error) => write!(f, "{error}"),
+            SerPayloadError::IntegerConversion { got, max } => {
+                write!(f, "Overflowing integer conversion: {got} > {max}")
+            }
+            SerPayloadError::KeyMustBeString => write!(f, "Map key must be a string"),
+            SerPayloadError::NotAnObject => {
+                write!(
+                    f,
+                    "Payload must be a struct or map, other types are not supported"
+                )
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SerPayloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for SerPayloadError {}
+
+impl serde::ser::Error for SerPayloadError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        Self::Error(msg.to_string())
+    }
+}
+
+/// Serialize `T` into a payload [`Value`]
+///
+/// Integers are kept as integers, and must fit into an `i64`. Types serializing as strings, such
+/// as UUIDs and date-times, become string values.
+///
+/// ```rust
+/// use qdrant_client::qdrant::Value;
+/// use qdrant_client::serde_ser::to_value;
+///
+/// let value = to_value(&vec![1, 2, 3]).unwrap();
+/// assert_eq!(value, Value::from(vec![1i64, 2, 3]));
+/// ```
+pub fn to_value<T>(value: &T) -> Result<Value, SerPayloadError>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(ValueSerializer)
+}
+
+fn kind(kind: Kind) -> Value {
+    Value { kind: Some(kind) }
+}
+
+fn null() -> Value {
+    kind(Kind::NullValue(0))
+}
+
+fn integer<T>(value: T) -> Result<Value, SerPayloadError>
+where
+    T: Copy + Into<i128> + TryInto<i64>,
+{
+    value
+        .try_into()
+        .map(|integer| kind(Kind::IntegerValue(integer)))
+        .map_err(|_| SerPayloadError::IntegerConversion {
+            got: value.into(),
+            max: i64::MAX,
+        })
+}
+
+fn list(values: Vec<Value>) -> Value {
+    kind(Kind::ListValue(ListValue { values }))
+}
+
+fn object(fields: HashMap<String, Value>) -> Value {
+    kind(Kind::StructValue(Struct { fields }))
+}
+
+/// Externally tagged enum variant, like `{"variant": value}`
+fn variant(variant: &'static str, value: Value) -> Value {
+    object(HashMap::from([(variant.to_string(), value)]))
+}
+
+/// Serializer producing payload [`Value`]s
+struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = SerPayloadError;
+
+    type SerializeSeq = SerializeVec;
+    type SerializeTuple = SerializeVec;
+    type SerializeTupleStruct = SerializeVec;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, SerPayloadError> {
+        Ok(kind(Kind::BoolValue(v)))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, SerPayloadError> {
+        integer(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, SerPayloadError> {
+        integer(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, SerPayloadError> {
+        integer(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, SerPayloadError> {
+        integer(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value, SerPayloadError> {
+        integer(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, SerPayloadError> {
+        integer(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, SerPayloadError> {
+        integer(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, SerPayloadError> {
+        integer(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, SerPayloadError> {
+        integer(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value, SerPayloadError> {
+        // Saturates integers beyond `i128`, they overflow `i64` regardless
+        integer(i128::try_from(v).unwrap_or(i128::MAX))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, SerPayloadError> {
+        Ok(kind(Kind::DoubleValue(f64::from(v))))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, SerPayloadError> {
+        Ok(kind(Kind::DoubleValue(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, SerPayloadError> {
+        Ok(kind(Kind::StringValue(v.to_string())))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, SerPayloadError> {
+        Ok(kind(Kind::StringValue(v.to_string())))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, SerPayloadError> {
+        Ok(list(
+            v.iter()
+                .map(|byte| kind(Kind::IntegerValue(i64::from(*byte))))
+                .collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Value, SerPayloadError> {
+        Ok(null())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value, SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, SerPayloadError> {
+        Ok(null())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, SerPayloadError> {
+        Ok(null())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, SerPayloadError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant_name: &'static str,
+        value: &T,
+    ) -> Result<Value, SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(variant(variant_name, to_value(value)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVec, SerPayloadError> {
+        Ok(SerializeVec {
+            values: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeVec, SerPayloadError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeVec, SerPayloadError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant, SerPayloadError> {
+        Ok(SerializeTupleVariant {
+            variant,
+            values: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeMap, SerPayloadError> {
+        Ok(SerializeMap {
+            fields: HashMap::with_capacity(len.unwrap_or(0)),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeMap, SerPayloadError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeStructVariant, SerPayloadError> {
+        Ok(SerializeStructVariant {
+            variant,
+            fields: HashMap::with_capacity(len),
+        })
+    }
+}
+
+struct SerializeVec {
+    values: Vec<Value>,
+}
+
+impl serde::ser::SerializeSeq for SerializeVec {
+    type Ok = Value;
+    type Error = SerPayloadError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.values.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerPayloadError> {
+        Ok(list(self.values))
+    }
+}
+
+impl serde::ser::SerializeTuple for SerializeVec {
+    type Ok = Value;
+    type Error = SerPayloadError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        serde::ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, SerPayloadError> {
+        serde::ser::SerializeSeq::end(self)
+    }
+}
+
+impl serde::ser::SerializeTupleStruct for SerializeVec {
+    type Ok = Value;
+    type Error = SerPayloadError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        serde::ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, SerPayloadError> {
+        serde::ser::SerializeSeq::end(self)
+    }
+}
+
+struct SerializeTupleVariant {
+    variant: &'static str,
+    values: Vec<Value>,
+}
+
+impl serde::ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Value;
+    type Error = SerPayloadError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.values.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerPayloadError> {
+        Ok(variant(self.variant, list(self.values)))
+    }
+}
+
+struct SerializeMap {
+    fields: HashMap<String, Value>,
+    next_key: Option<String>,
+}
+
+impl serde::ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = SerPayloadError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.next_key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| SerPayloadError::Error("Map value without a key".to_string()))?;
+        self.fields.insert(key, to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerPayloadError> {
+        Ok(object(self.fields))
+    }
+}
+
+impl serde::ser::SerializeStruct for SerializeMap {
+    type Ok = Value;
+    type Error = SerPayloadError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.fields.insert(key.to_string(), to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerPayloadError> {
+        Ok(object(self.fields))
+    }
+}
+
+struct SerializeStructVariant {
+    variant: &'static str,
+    fields: HashMap<String, Value>,
+}
+
+impl serde::ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Value;
+    type Error = SerPayloadError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.fields.insert(key.to_string(), to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerPayloadError> {
+        Ok(variant(self.variant, object(self.fields)))
+    }
+}
+
+/// Serializer for map keys, which must be strings or be representable as strings
+struct MapKeySerializer;
+
+macro_rules! serialize_key_display {
+    ($($method:ident: $ty:ty),* $(,)?) => {
+        $(
+            fn $method(self, v: $ty) -> Result<String, SerPayloadError> {
+                Ok(v.to_string())
+            }
+        )*
+    };
+}
+
+macro_rules! serialize_key_unsupported {
+    ($($method:ident$(: $ty:ty)?),* $(,)?) => {
+        $(
+            fn $method(self $(, _v: $ty)?) -> Result<String, SerPayloadError> {
+                Err(SerPayloadError::KeyMustBeString)
+            }
+        )*
+    };
+}
+
+impl Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = SerPayloadError;
+
+    type SerializeSeq = Impossible<String, SerPayloadError>;
+    type SerializeTuple = Impossible<String, SerPayloadError>;
+    type SerializeTupleStruct = Impossible<String, SerPayloadError>;
+    type SerializeTupleVariant = Impossible<String, SerPayloadError>;
+    type SerializeMap = Impossible<String, SerPayloadError>;
+    type SerializeStruct = Impossible<String, SerPayloadError>;
+    type SerializeStructVariant = Impossible<String, SerPayloadError>;
+
+    serialize_key_display! {
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_char: char,
+        serialize_str: &str,
+    }
+
+    serialize_key_unsupported! {
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_bytes: &[u8],
+        serialize_none,
+        serialize_unit,
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<String, SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(SerPayloadError::KeyMustBeString)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, SerPayloadError> {
+        Err(SerPayloadError::KeyMustBeString)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, SerPayloadError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, SerPayloadError>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(SerPayloadError::KeyMustBeString)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerPayloadError> {
+        Err(SerPayloadError::KeyMustBeString)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerPayloadError> {
+        Err(SerPayloadError::KeyMustBeString)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerPayloadError> {
+        Err(SerPayloadError::KeyMustBeString)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerPayloadError> {
+        Err(SerPayloadError::KeyMustBeString)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerPayloadError> {
+        Err(SerPayloadError::KeyMustBeString)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerPayloadError> {
+        Err(SerPayloadError::KeyMustBeString)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerPayloadError> {
+        Err(SerPayloadError::KeyMustBeString)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use serde::{Deserialize, Serialize};
+
+    use super::{to_value, SerPayloadError};
+    use crate::qdrant::value::Kind;
+    use crate::qdrant::Value;
+    use crate::{Payload, QdrantError};
+
+    #[test]
+    fn test_round_trip() {
+        #[derive(Serialize)]
+        enum Shape {
+            Point,
+            Circle(f64),
+            Line(i32, i32),
+            Rect { width: u32, height: u32 },
+        }
+
+        #[derive(Deserialize, Serialize, PartialEq, Debug)]
+        struct S {
+            some_string: String,
+            some_bool: bool,
+            some_int: i32,
+            some_float: f64,
+            some_option: Option<u8>,
+            some_seq: Vec<String>,
+            some_obj: HashMap<String, String>,
+            tuple: (String, u32),
+        }
+
+        let value = S {
+            some_string: "Bar".into(),
+            some_bool: true,
+            some_int: -12,
+            some_float: 1.5,
+            some_option: None,
+            some_seq: vec!["elem1".into(), "elem2".into()],
+            some_obj: HashMap::from([("key".into(), "value".into())]),
+            tuple: ("abc".to_string(), 42),
+        };
+
+        let payload = Payload::from_serializable(&value).unwrap();
+
+        // Same representation as going through JSON
+        let json = Payload::try_from(serde_json::to_value(&value).unwrap()).unwrap();
+        assert_eq!(payload, json);
+
+        let deserialized: S = payload.deserialize().unwrap();
+        assert_eq!(deserialized, value);
+
+        // Enums are externally tagged
+        let shapes = vec![
+            Shape::Point,
+            Shape::Circle(2.0),
+            Shape::Line(1, 2),
+            Shape::Rect {
+                width: 3,
+                height: 4,
+            },
+        ];
+        assert_eq!(
+            to_value(&shapes).unwrap(),
+            Value::from(serde_json::to_value(&shapes).unwrap()),
+        );
+
+        // Integer map keys become strings
+        let int_keys = BTreeMap::from([(1, "one")]);
+        assert_eq!(
+            to_value(&int_keys).unwrap(),
+            Value::from(serde_json::to_value(&int_keys).unwrap()),
+        );
+    }
+
+    #[test]
+    fn test_numbers_keep_their_kind() {
+        let value = to_value(&(i64::MAX, 2.0f64, 3u8)).unwrap();
+        let Some(Kind::ListValue(list)) = value.kind else {
+            panic!("expected list");
+        };
+        assert_eq!(list.values[0].kind, Some(Kind::IntegerValue(i64::MAX)));
+        assert_eq!(list.values[1].kind, Some(Kind::DoubleValue(2.0)));
+        assert_eq!(list.values[2].kind, Some(Kind::IntegerValue(3)));
+
+        assert_eq!(
+            to_value(&u64::MAX),
+            Err(SerPayloadError::IntegerConversion {
+                got: i128::from(u64::MAX),
+                max: i64::MAX,
+            }),
+        );
+        assert_eq!(
+            to_value(&u128::MAX),
+            Err(SerPayloadError::IntegerConversion {
+                got: i128::MAX,
+                max: i64::MAX,
+            }),
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() {
+        use chrono::{TimeZone, Utc};
+
+        let at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let payload = Payload::from_serializable(&HashMap::from([("at", at)])).unwrap();
+        assert_eq!(payload.0["at"], Value::from("2024-01-01T00:00:00Z"));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time() {
+        #[derive(Serialize)]
+        struct Event {
+            #[serde(with = "time::serde::rfc3339")]
+            at: time::OffsetDateTime,
+        }
+
+        let event = Event {
+            at: time::OffsetDateTime::UNIX_EPOCH,
+        };
+        let payload = Payload::from_serializable(&event).unwrap();
+        assert_eq!(payload.0["at"], Value::from("1970-01-01T00:00:00Z"));
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_uuid() {
+        let uuid = uuid::Uuid::nil();
+        assert_eq!(to_value(&uuid).unwrap(), Value::from(uuid.to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            Payload::from_serializable(&[1, 2, 3]),
+            Err(QdrantError::PayloadSerialization(
+                SerPayloadError::NotAnObject
+            )),
+        ));
+        assert_eq!(
+            to_value(&HashMap::from([((1, 2), "tuple key")])),
+            Err(SerPayloadError::KeyMustBeString),
+        );
+    }
+}